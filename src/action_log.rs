@@ -0,0 +1,97 @@
+//! The optional log of the actions taken on entries, e.g. for reviewing a
+//! session in regulated environments.
+//!
+//! Lines name the entry and what was done with it, never a secret. They are
+//! appended to a file in the data directory and kept in memory for the
+//! session, which can be exported.
+
+use std::{
+    fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::data;
+
+#[derive(Debug, Default, Clone)]
+pub struct ActionLog {
+    /// Name of the log file in the data directory, `None` if the log is off
+    file_name: Option<String>,
+    /// Lines recorded in this session
+    session: Vec<String>,
+}
+
+impl ActionLog {
+    pub fn new(file_name: Option<String>) -> Self {
+        Self {
+            file_name,
+            session: Vec::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.file_name.is_some()
+    }
+
+    /// Appends a line of the form `<time>\t<pass_id>\t<what>` with the time
+    /// in UTC, the line is kept for the session even if appending failed.
+    pub fn record(
+        &mut self,
+        data_dir: Option<&Path>,
+        now: SystemTime,
+        pass_id: &str,
+        what: &str,
+    ) -> io::Result<()> {
+        let Some(file_name) = &self.file_name else {
+            return Ok(());
+        };
+        let line = format!("{}\t{pass_id}\t{what}", timestamp(now));
+        let result = data::append_line(data_dir, file_name, &line);
+        self.session.push(line);
+        result
+    }
+
+    pub fn session(&self) -> &[String] {
+        &self.session
+    }
+
+    /// Writes the lines of this session to a file.
+    pub fn export(&self, path: &Path) -> io::Result<()> {
+        let mut content = self.session.join("\n");
+        content.push('\n');
+        fs::write(path, content)
+    }
+}
+
+/// Formats a time as `YYYY-MM-DDTHH:MM:SSZ`.
+fn timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+
+    // Civil date from the days since 1970-01-01, counted in eras of 400
+    // years starting on March 1st
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
@@ -1,17 +1,83 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    components::{Conflict, SortColumn},
+    diff::DiffLine,
+    event::Finding,
+    git::Revision,
+    secret::Secret,
+};
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     Navigation(NavigationAction),
     Password(PasswordAction),
     Search(SearchAction),
+    Form(FormAction),
+    Audit(AuditAction),
+    OtpAudit(OtpAuditAction),
+    Duplicates(DuplicatesAction),
+    Strength(StrengthAction),
+    Scan(ScanAction),
+    History(HistoryAction),
+    Conflict(ConflictAction),
+    Store(StoreAction),
+    Table(TableAction),
+    Dialog(DialogAction),
     SetStatus(String),
+    /// Shows a redacted error in the status bar, the full message only in
+    /// the error dialog
+    ReportError(String),
     ResetStatus,
     DisplaySecrets {
         pass_id: String,
-        file_contents: String,
+        /// Lock generation the decryption was started in, results of an
+        /// earlier one are dropped
+        generation: u64,
+        /// Shared with the cache, the contents are not copied
+        file_contents: Arc<Secret>,
+        modified: Option<SystemTime>,
+        /// Entry the alias stands in for
+        target: Option<String>,
+    },
+    DisplayGitStatus(Option<String>),
+    DisplayFileModified {
+        pass_id: String,
+        modified: Option<SystemTime>,
+    },
+    DisplayFetchError {
+        pass_id: String,
+        message: String,
     },
     DisplayOneTimePassword {
         pass_id: String,
+        /// Lock generation the decryption was started in, results of an
+        /// earlier one are dropped
+        generation: u64,
         otp: String,
+        valid_until: SystemTime,
+        period: Duration,
+    },
+    DisplayLastChange {
+        pass_id: String,
+        last_change: Option<String>,
+    },
+    DisplayHistory {
+        pass_id: String,
+        revisions: Vec<Revision>,
+    },
+    /// Decrypted changes between two versions of an entry
+    DisplayDiff {
+        pass_id: String,
+        /// Lock generation the decryption was started in, results of an
+        /// earlier one are dropped
+        generation: u64,
+        old: String,
+        new: String,
+        lines: Vec<DiffLine>,
     },
     NoOp,
     Redraw,
@@ -33,13 +99,80 @@ pub enum NavigationAction {
     Search,
     Help,
     File,
+    RecoveryCodes,
+    WifiQr,
+    /// Shows QR codes of the one-time passwords and the password
+    Qr,
+    Commands,
+    Audit,
+    /// Lists the entries without a one-time password
+    OtpAudit,
+    /// Lists the entries sharing a password and login
+    Duplicates,
+    /// Lists the entries with weak passwords
+    Strength,
+    /// Lists the commits that changed the selected entry
+    History,
+    /// Lists the actions taken on entries in this session
+    SessionLog,
+    /// Prompts for the file to export the session log to
+    ExportSessionLog,
+    Generator,
+    Insert,
+    Move,
+    /// Prompts for the path of an entry to select
+    Goto,
+    /// Prompts for a URL to select the matching entry
+    LookupUrl,
+    NewFolder,
+    ErrorDetails,
+    /// Hides the warning shown above the menu
+    DismissBanner,
     Select(usize),
+    JumpToLetter(char),
+    /// Remembers the selected entry under the letter for the session
+    SetMark(char),
+    JumpToMark(char),
+    /// Selects the entry that was selected before the last jump
+    JumpBack,
     SelectAndFetch(usize),
+    /// The selection rested after moving in the secrets view, see
+    /// `follow_selection`
+    SelectionRested,
+    /// Changes the size of the details pane by a step
+    GrowDetails,
+    ShrinkDetails,
+    /// Collapses the details pane or shows it again
+    ToggleDetails,
+    /// Moves the details pane between below and on the right of the table
+    SwitchLayout,
+    /// Moves the keyboard focus over the buttons of the details pane or the
+    /// popup
+    FocusNext,
+    FocusPrevious,
+    PressFocused,
     Quit,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum SearchAction {
+    Insert(char),
+    /// A character typed in the table that is not bound to an action,
+    /// starts a search if `type_to_search` is enabled
+    Type(char),
+    RemoveLeft,
+    RemoveRight,
+    MoveLeft,
+    MoveRight,
+    MoveToStart,
+    MoveToEnd,
+    /// Searches the decrypted password files for the pattern
+    SearchContents,
+}
+
+/// Editing of the focused input in the form for new entries
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormAction {
     Insert(char),
     RemoveLeft,
     RemoveRight,
@@ -47,6 +180,110 @@ pub enum SearchAction {
     MoveRight,
     MoveToStart,
     MoveToEnd,
+    Enter,
+    NextField,
+    PreviousField,
+    Focus(usize),
+    /// Completes the input to the paths of the store
+    Complete,
+    Submit,
+}
+
+/// Batch actions in the list of stale entries
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditAction {
+    ToggleMark,
+    ToggleMarkAll,
+    Archive,
+    Delete,
+    /// Deletes the marked entries after the dialog was confirmed
+    DeleteConfirmed,
+    /// Removes the permission bits of the umask from all files and folders
+    /// that have them
+    FixPermissions,
+}
+
+/// Checking which entries lack a one-time password
+#[derive(Debug, Clone, PartialEq)]
+pub enum OtpAuditAction {
+    /// Selects the entry in the table
+    Open,
+}
+
+/// Checking which entries have weak passwords
+#[derive(Debug, Clone, PartialEq)]
+pub enum StrengthAction {
+    /// Selects the entry in the table
+    Open,
+}
+
+/// Finding entries with the same password and login and merging them
+#[derive(Debug, Clone, PartialEq)]
+pub enum DuplicatesAction {
+    /// Asks how to merge the group of the selected entry into it
+    Merge,
+    /// Replaces the other entries of the group with aliases of the
+    /// selected one
+    MergeAliases,
+    /// Moves the other entries of the group to the archive folder
+    MergeArchive,
+    /// Deletes the other entries of the group
+    MergeDelete,
+}
+
+/// Audits that decrypt the entries they could not check from the cache
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Audit {
+    Otp,
+    Duplicates,
+    Strength,
+}
+
+/// Decrypting entries one after the other in the background, for an audit
+/// or the content search
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanAction {
+    /// Asks before decrypting the entries the audit could not check
+    Decrypt(Audit),
+    /// Decrypts the unchecked entries after the dialog was confirmed
+    DecryptConfirmed(Audit),
+    /// A password file was decrypted by the scan with the ID
+    Scanned {
+        scan_id: u64,
+        pass_id: String,
+        finding: Finding,
+    },
+    Failed {
+        scan_id: u64,
+        message: String,
+    },
+}
+
+/// Comparing versions in the history of an entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum HistoryAction {
+    /// Marks the selected version as the one the others are compared with
+    MarkBase,
+    Diff,
+    /// Compares the versions with the configured difftool
+    Difftool,
+}
+
+/// Resolving the selected merge conflict
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictAction {
+    KeepLocal,
+    KeepRemote,
+    /// Edits the local version with `pass edit`
+    Edit,
+}
+
+/// Buttons of the open dialog
+#[derive(Debug, Clone, PartialEq)]
+pub enum DialogAction {
+    Press(usize),
+    /// Presses the button with the key
+    Key(char),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -55,6 +292,95 @@ pub enum PasswordAction {
     FetchOtp,
     CopyPassId,
     CopyPassword,
+    CopyDefault,
     CopyLogin,
     CopyOtp,
+    /// Copies the value of the `key: value` line with the key
+    CopyField(String),
+    /// Copies the line with the number counted from 1
+    CopyLine(usize),
+    NextOtp,
+    /// Switches the QR code of a one-time password to its URI and back
+    ShowOtpUri,
+    /// Runs the configured export command with the URI of the shown
+    /// one-time password on stdin
+    ExportOtp,
+    CopyFileLine,
+    CopyRecoveryCode,
+    UseRecoveryCode,
+    OpenUrl,
+    OpenUrlAndCopyLogin,
+    Insert,
+    /// Replaces an existing entry with the contents of the form
+    Overwrite,
+    CopyGenerated,
+    Generate,
+    RunCommand,
+    /// Moves an entry to the archive folder or back out of it
+    Archive,
+    /// Forgets decrypted files and the passphrases cached by the agent
+    Lock,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreAction {
+    Refresh,
+    Reconcile,
+    /// The store was changed outside of passepartui and is scanned again
+    Changed,
+    /// A new entry was added to the store
+    Created(String),
+    /// An entry was renamed or moved to another folder
+    Moved {
+        from: String,
+        to: String,
+    },
+    /// Stale entries were moved to the archive folder or deleted
+    Cleaned {
+        moved: Vec<(String, String)>,
+        removed: Vec<String>,
+    },
+    /// Entries were replaced with aliases of the target
+    Aliased {
+        target: String,
+        pass_ids: Vec<String>,
+    },
+    GitPull,
+    /// Pulls with `--rebase`, e.g. when starting
+    GitPullRebase,
+    GitPush,
+    GitStatus,
+    /// Changes were pulled from the git remote of the store
+    Pulled(String),
+    /// Files that are left to merge after pulling
+    Conflicts(Vec<Conflict>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableAction {
+    TogglePin,
+    EnterFolder,
+    LeaveFolder,
+    WidenScope,
+    ToggleArchive,
+    /// Sorts by a column or reverses the order if already sorted by it
+    Sort(SortColumn),
+    /// Copies the login shown in the login column for the row
+    CopyLogin(usize),
+    /// A row is dragged over another one or back to where it started
+    DragOver {
+        from: usize,
+        to: Option<usize>,
+    },
+    /// Asks to move the entry of a dragged row to the folder of the row it
+    /// was dropped on
+    Drop {
+        from: usize,
+        to: usize,
+    },
+    /// Moves an entry after the dialog for a drop was confirmed
+    MoveConfirmed {
+        from: String,
+        to: String,
+    },
 }
@@ -0,0 +1,59 @@
+//! Alias entries stand in for another entry, e.g. for services sharing one
+//! credential. The first line of their password file is
+//! `alias: <pass-id>` instead of a password, fetching or copying their
+//! secrets uses the password file of the target.
+
+use std::path::Path;
+
+use crate::backend::Backend;
+
+/// Aliases followed before giving up, e.g. because of a loop
+const MAX_ALIASES: usize = 8;
+
+/// Returns the pass ID the password file is an alias of.
+pub fn target(file_contents: &str) -> Option<String> {
+    let (key, value) = file_contents.lines().next()?.split_once(':')?;
+    let target = value.trim().trim_end_matches(".gpg");
+    (key.trim().eq_ignore_ascii_case("alias") && !target.is_empty()).then(|| target.to_string())
+}
+
+/// Decrypts a password file, aliases are followed to the entry they stand
+/// in for.
+pub fn decrypt(backend: &dyn Backend, file_path: &Path) -> Result<String, passepartout::Error> {
+    resolve(backend, file_path).map(|(file_contents, _)| file_contents)
+}
+
+/// Decrypts a password file and follows aliases, returns the contents
+/// together with the pass ID of the entry they belong to if the file is an
+/// alias.
+pub fn resolve(
+    backend: &dyn Backend,
+    file_path: &Path,
+) -> Result<(String, Option<String>), passepartout::Error> {
+    let mut file_contents = backend.decrypt(file_path)?;
+    let mut resolved = None;
+    for _ in 0..MAX_ALIASES {
+        let Some(target) = target(&file_contents) else {
+            return Ok((file_contents, resolved));
+        };
+        if target.starts_with('/') || target.split('/').any(|part| part == "..") {
+            return Err(passepartout::Error::Pass(format!(
+                "alias target {target} is outside of the store"
+            )));
+        }
+        let store_dir = backend
+            .store_dir()
+            .map_err(|e| passepartout::Error::Pass(e.message))?;
+        let target_path = store_dir.join(format!("{target}.gpg"));
+        if backend.modified(&target_path).is_none() {
+            return Err(passepartout::Error::Pass(format!(
+                "alias target {target} does not exist"
+            )));
+        }
+        file_contents = backend.decrypt(&target_path)?;
+        resolved = Some(target);
+    }
+    Err(passepartout::Error::Pass(format!(
+        "gave up after {MAX_ALIASES} aliases, check for a loop"
+    )))
+}
@@ -1,24 +1,50 @@
 use anyhow::Result;
 use ratatui::{
-    crossterm::event::{self, Event as TerminalEvent, KeyCode, KeyEvent, KeyEventKind, MouseEvent},
+    buffer::Buffer,
+    crossterm::event::{
+        self, Event as TerminalEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent,
+    },
+    layout::Rect,
+    widgets::Widget,
     DefaultTerminal,
 };
 use std::{
-    sync::mpsc::{self, Receiver},
+    sync::{
+        mpsc::{self, Receiver},
+        Arc,
+    },
     time::Duration,
 };
 
+mod startup_error;
 mod state;
 
 use crate::{
-    actions::{Action, NavigationAction, PasswordAction, SearchAction},
-    components::{Component, Dashboard, MouseSupport},
+    actions::{Action, NavigationAction, PasswordAction, ScanAction, StoreAction},
+    backend::{Backend, System},
+    clock::Clock,
+    components::{Component, Dashboard, ErrorScreen, MouseSupport},
+    config::Config,
     event::PasswordEvent,
+    hardening,
+    keymap::{self, Key, KeyAction, Keymap, Lookup, Mode},
 };
+pub use startup_error::StartupError;
 pub use state::{MainState, OverlayState, SearchState, State};
 
+/// Largest count typed before a movement
+const MAX_COUNT: usize = 999;
+
 pub struct App<'a> {
     running: bool,
+    /// Set after a prefix key like the one for jumping was pressed, the
+    /// next letter completes the action
+    prefix: Option<KeyAction>,
+    keymap: Keymap,
+    /// Keys pressed so far of a chord like `gg`
+    pending_keys: Vec<Key>,
+    /// Digits typed before a movement like `15j`, which repeat it
+    count: Option<usize>,
     complete_redraw: bool,
     tick_rate: Duration,
     event_rx: Receiver<PasswordEvent>,
@@ -26,15 +52,45 @@ pub struct App<'a> {
 }
 
 impl App<'_> {
-    pub fn new(tty_pinentry: bool) -> Self {
+    pub fn new(
+        tty_pinentry: bool,
+        initial_scan: bool,
+        config: Config,
+        clock: Clock,
+    ) -> Result<Self, StartupError> {
+        let backend = Arc::new(System::new(
+            config.pass.clone(),
+            config.clear_after,
+            config.clipboard.clone(),
+            clock,
+        ));
+        if !config.allow_world_readable_store {
+            hardening::check_store_dir(&backend.store_dir()?)?;
+        }
+        Self::with_backend(tty_pinentry, initial_scan, config, backend)
+    }
+
+    /// Creates the app with a different backend, e.g. a fake store that
+    /// allows rendering the app deterministically into a test buffer.
+    pub fn with_backend(
+        tty_pinentry: bool,
+        initial_scan: bool,
+        config: Config,
+        backend: Arc<dyn Backend>,
+    ) -> Result<Self, StartupError> {
         let (event_tx, event_rx) = mpsc::channel();
-        Self {
-            dashboard: Dashboard::new(tty_pinentry, event_tx),
+        let keymap = config.keymap.clone();
+        Ok(Self {
+            dashboard: Dashboard::new(tty_pinentry, initial_scan, config, backend, event_tx)?,
             running: false,
+            prefix: None,
+            keymap,
+            pending_keys: Vec::new(),
+            count: None,
             complete_redraw: false,
             tick_rate: Duration::from_millis(80),
             event_rx,
-        }
+        })
     }
 
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
@@ -45,24 +101,61 @@ impl App<'_> {
                 let _ = terminal.clear();
                 self.complete_redraw = false;
             }
-            terminal.draw(|frame| frame.render_widget(&mut self.dashboard, frame.area()))?;
+            terminal.draw(|frame| frame.render_widget(&mut *self, frame.area()))?;
             self.handle_events()?;
         }
         Ok(())
     }
 
+    /// Opens the search with the pattern already typed, e.g. given on the
+    /// command line.
+    pub fn search(&mut self, pattern: &str) {
+        self.dashboard.search(pattern);
+    }
+
+    /// Selects the entry and starts decrypting it for showing its secrets.
+    pub fn open_entry(&mut self, pass_id: &str) -> Result<()> {
+        match self.dashboard.open_entry(pass_id) {
+            Some(action) => self.dispatch(action),
+            None => Ok(()),
+        }
+    }
+
+    /// Selects the entry matching the host of the URL best and starts
+    /// decrypting it.
+    pub fn lookup_url(&mut self, url: &str) -> Result<()> {
+        match self.dashboard.lookup_url(url) {
+            Some(action) => self.dispatch(action),
+            None => Ok(()),
+        }
+    }
+
+    /// Shows an error that prevented the start until a key to quit is
+    /// pressed.
+    pub fn show_startup_error(terminal: &mut DefaultTerminal, error: StartupError) -> Result<()> {
+        let screen = ErrorScreen::new(error);
+        loop {
+            terminal.draw(|frame| frame.render_widget(&screen, frame.area()))?;
+            if let TerminalEvent::Key(event) = event::read()? {
+                if event.kind == KeyEventKind::Press
+                    && matches!(event.code, KeyCode::Char('q') | KeyCode::Esc)
+                {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
     fn handle_events(&mut self) -> Result<()> {
         if event::poll(self.tick_rate)? {
             if let Ok(terminal_event) = event::read() {
                 match terminal_event {
                     TerminalEvent::Key(event) if event.kind == KeyEventKind::Press => {
-                        if let Some(action) = self.handle_key_event(event) {
-                            self.dispatch_action(action)?;
-                        }
+                        self.handle_key(event)?;
                     }
                     TerminalEvent::Mouse(mouse_event) => {
                         if let Some(action) = self.handle_mouse_event(mouse_event) {
-                            self.dispatch_action(action)?;
+                            self.dispatch(action)?;
                         }
                     }
                     TerminalEvent::Resize(_, _) => (),
@@ -70,133 +163,139 @@ impl App<'_> {
                 }
             }
         }
+        self.process_events()
+    }
+
+    /// Handles a key press like it was typed by the user.
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> Result<()> {
+        if let Some(count) = self.extend_count(key_event) {
+            self.count = Some(count);
+            return self.dispatch(Action::SetStatus(format!("Count: {count}")));
+        }
+        let action = self.handle_key_event(key_event);
+        // The count is kept while a chord is pending and used up otherwise
+        let count = if self.pending_keys.is_empty() {
+            self.count.take()
+        } else {
+            None
+        };
+        match (action, count) {
+            (Some(action), Some(count)) => self.dispatch_counted(action, count),
+            (Some(action), None) => self.dispatch(action),
+            (None, Some(_)) => self.dispatch(Action::ResetStatus),
+            (None, None) => Ok(()),
+        }
+    }
+
+    /// Returns the count extended by a typed digit. Digits are only a count
+    /// in states without text input and if they are not bound themselves,
+    /// a leading 0 is not a count.
+    fn extend_count(&self, key_event: KeyEvent) -> Option<usize> {
+        let KeyCode::Char(digit) = key_event.code else {
+            return None;
+        };
+        let digit = digit.to_digit(10)? as usize;
+        let mode = Mode::from_state(&self.dashboard.app_state);
+        if self.prefix.is_some()
+            || !self.pending_keys.is_empty()
+            || !mode.takes_count()
+            || key_event
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            || (digit == 0 && self.count.is_none())
+            || !matches!(
+                self.keymap.lookup(mode, &[Key::from(key_event)]),
+                Lookup::NotFound
+            )
+        {
+            return None;
+        }
+        Some((self.count.unwrap_or(0) * 10 + digit).min(MAX_COUNT))
+    }
+
+    /// Repeats a movement by the count, (g) and (G) go to the row with the
+    /// number in the table instead and copying copies the line with the
+    /// number. Other actions ignore the count.
+    fn dispatch_counted(&mut self, action: Action, count: usize) -> Result<()> {
+        self.dispatch(Action::ResetStatus)?;
+        match action {
+            Action::Navigation(
+                NavigationAction::Down
+                | NavigationAction::Up
+                | NavigationAction::PageDown
+                | NavigationAction::PageUp,
+            ) => {
+                for _ in 0..count {
+                    self.dispatch(action.clone())?;
+                }
+                Ok(())
+            }
+            Action::Navigation(NavigationAction::Top | NavigationAction::Bottom)
+                if self.dashboard.app_state.overlay == OverlayState::Inactive =>
+            {
+                self.dispatch(Action::Navigation(NavigationAction::Select(count - 1)))
+            }
+            Action::Password(PasswordAction::CopyDefault | PasswordAction::CopyPassword)
+                if self.dashboard.app_state.overlay == OverlayState::Inactive =>
+            {
+                self.dispatch(Action::Password(PasswordAction::CopyLine(count)))
+            }
+            action => self.dispatch(action),
+        }
+    }
+
+    /// Processes the results of background operations that arrived so far.
+    pub fn process_events(&mut self) -> Result<()> {
         while let Ok(event) = self.event_rx.try_recv() {
             if let Some(action) = self.handle_channel_event(event) {
-                self.dispatch_action(action)?;
+                self.dispatch(action)?;
             }
         }
         Ok(())
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<Action> {
-        match self.dashboard.app_state {
-            State {
-                main: MainState::Preview | MainState::Secrets,
-                search: SearchState::Inactive | SearchState::Suspended,
-                overlay: OverlayState::Inactive,
-            } => match key_event.code {
-                KeyCode::Char('j') | KeyCode::Down => {
-                    Some(Action::Navigation(NavigationAction::Down))
-                }
-                KeyCode::Char('k') | KeyCode::Up => Some(Action::Navigation(NavigationAction::Up)),
-                KeyCode::PageDown | KeyCode::Char('f') => {
-                    Some(Action::Navigation(NavigationAction::PageDown))
-                }
-                KeyCode::PageUp | KeyCode::Char('b') => {
-                    Some(Action::Navigation(NavigationAction::PageUp))
-                }
-                KeyCode::Char('g') | KeyCode::Home => {
-                    Some(Action::Navigation(NavigationAction::Top))
-                }
-                KeyCode::Char('G') | KeyCode::End => {
-                    Some(Action::Navigation(NavigationAction::Bottom))
-                }
-                KeyCode::Char('y') => Some(Action::Password(PasswordAction::CopyPassword)),
-                KeyCode::Char('h') | KeyCode::Left => {
-                    Some(Action::Navigation(NavigationAction::Back))
-                }
-                KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
-                    Some(Action::Navigation(NavigationAction::Secrets))
-                }
-                KeyCode::Char('/') => Some(Action::Navigation(NavigationAction::Search)),
-                KeyCode::F(1) => Some(Action::Navigation(NavigationAction::Help)),
-                KeyCode::Char('i') => Some(Action::Navigation(NavigationAction::File)),
-                KeyCode::Char('r') => Some(Action::Password(PasswordAction::FetchOtp)),
-                KeyCode::Char('x') => Some(Action::Password(PasswordAction::CopyOtp)),
-                KeyCode::Char('c') => Some(Action::Password(PasswordAction::CopyPassId)),
-                KeyCode::Char('v') => Some(Action::Password(PasswordAction::CopyLogin)),
-                KeyCode::Esc => Some(Action::Navigation(NavigationAction::Leave)),
-                KeyCode::Char('q') | KeyCode::Char('Q') => {
-                    Some(Action::Navigation(NavigationAction::Quit))
-                }
-                _ => None,
-            },
-            State {
-                main: MainState::Table,
-                search: SearchState::Inactive | SearchState::Suspended,
-                overlay: OverlayState::Inactive,
-            } => match key_event.code {
-                KeyCode::Char('j') | KeyCode::Down => {
-                    Some(Action::Navigation(NavigationAction::Down))
-                }
-                KeyCode::Char('k') | KeyCode::Up => Some(Action::Navigation(NavigationAction::Up)),
-                KeyCode::PageDown | KeyCode::Char('f') => {
-                    Some(Action::Navigation(NavigationAction::PageDown))
-                }
-                KeyCode::PageUp | KeyCode::Char('b') => {
-                    Some(Action::Navigation(NavigationAction::PageUp))
-                }
-                KeyCode::Char('g') | KeyCode::Home => {
-                    Some(Action::Navigation(NavigationAction::Top))
-                }
-                KeyCode::Char('G') | KeyCode::End => {
-                    Some(Action::Navigation(NavigationAction::Bottom))
-                }
-                KeyCode::Char('y') => Some(Action::Password(PasswordAction::CopyPassword)),
-                KeyCode::Char('l') | KeyCode::Right | KeyCode::Enter => {
-                    Some(Action::Navigation(NavigationAction::Preview))
-                }
-                KeyCode::Char('/') => Some(Action::Navigation(NavigationAction::Search)),
-                KeyCode::F(1) => Some(Action::Navigation(NavigationAction::Help)),
-                KeyCode::Char('i') => Some(Action::Navigation(NavigationAction::File)),
-                KeyCode::Char('x') => Some(Action::Password(PasswordAction::CopyOtp)),
-                KeyCode::Char('c') => Some(Action::Password(PasswordAction::CopyPassId)),
-                KeyCode::Char('v') => Some(Action::Password(PasswordAction::CopyLogin)),
-                KeyCode::Esc => Some(Action::Navigation(NavigationAction::Leave)),
-                KeyCode::Char('q') | KeyCode::Char('Q') => {
-                    Some(Action::Navigation(NavigationAction::Quit))
+        if let Some(prefix) = self.prefix.take() {
+            return match key_event.code {
+                KeyCode::Char(letter) => prefix.with_letter(letter),
+                _ => Some(Action::ResetStatus),
+            };
+        }
+
+        // A focused button is pressed instead of running the bound action
+        if self.pending_keys.is_empty()
+            && matches!(key_event.code, KeyCode::Enter | KeyCode::Char(' '))
+            && self.dashboard.has_focused_button()
+        {
+            return Some(Action::Navigation(NavigationAction::PressFocused));
+        }
+
+        let mode = Mode::from_state(&self.dashboard.app_state);
+        self.pending_keys.push(Key::from(key_event));
+        match self.keymap.lookup(mode, &self.pending_keys) {
+            Lookup::Pending => None,
+            Lookup::Found(key_action) => {
+                let key_action = key_action.clone();
+                self.pending_keys.clear();
+                match key_action {
+                    KeyAction::Run(action) => Some(action),
+                    prefix => {
+                        let prompt = prefix.prompt().map(String::from);
+                        self.prefix = Some(prefix);
+                        prompt.map(Action::SetStatus)
+                    }
                 }
-                _ => None,
-            },
-            State {
-                main: _,
-                search: SearchState::Active,
-                overlay: OverlayState::Inactive,
-            } => match key_event.code {
-                KeyCode::Esc | KeyCode::Enter => Some(Action::Navigation(NavigationAction::Leave)),
-                KeyCode::Down => Some(Action::Navigation(NavigationAction::Down)),
-                KeyCode::Up => Some(Action::Navigation(NavigationAction::Up)),
-                KeyCode::PageDown => Some(Action::Navigation(NavigationAction::PageDown)),
-                KeyCode::PageUp => Some(Action::Navigation(NavigationAction::PageUp)),
-                KeyCode::F(1) => Some(Action::Navigation(NavigationAction::Help)),
-                KeyCode::Char(key) => Some(Action::Search(SearchAction::Insert(key))),
-                KeyCode::Backspace => Some(Action::Search(SearchAction::RemoveLeft)),
-                KeyCode::Delete => Some(Action::Search(SearchAction::RemoveRight)),
-                KeyCode::Left => Some(Action::Search(SearchAction::MoveLeft)),
-                KeyCode::Right => Some(Action::Search(SearchAction::MoveRight)),
-                KeyCode::Home => Some(Action::Search(SearchAction::MoveToStart)),
-                KeyCode::End => Some(Action::Search(SearchAction::MoveToEnd)),
-                _ => None,
-            },
-            State {
-                main: _,
-                search: _,
-                overlay: OverlayState::Help,
-            } => match key_event.code {
-                KeyCode::Esc | KeyCode::F(1) => Some(Action::Navigation(NavigationAction::Back)),
-                _ => None,
-            },
-            State {
-                main: _,
-                search: _,
-                overlay: OverlayState::File,
-            } => match key_event.code {
-                KeyCode::Esc | KeyCode::Char('i') => {
-                    Some(Action::Navigation(NavigationAction::Back))
+            }
+            Lookup::NotFound => {
+                let chord = self.pending_keys.len() > 1;
+                self.pending_keys.clear();
+                if chord {
+                    // The key did not continue the chord, try it on its own
+                    self.handle_key_event(key_event)
+                } else {
+                    keymap::unbound_key_action(mode, key_event)
                 }
-                KeyCode::F(1) => Some(Action::Navigation(NavigationAction::Help)),
-                _ => None,
-            },
+            }
         }
     }
 
@@ -209,26 +308,114 @@ impl App<'_> {
             PasswordEvent::Status(Ok(None)) => Some(Action::ResetStatus),
             PasswordEvent::Status(Ok(Some(message))) => Some(Action::SetStatus(message)),
             PasswordEvent::Status(Err(passepartout::Error::Pass(e))) => {
-                Some(Action::SetStatus(format!("✗ (pass) {e:?}")))
+                Some(Action::ReportError(format!("(pass) {e}")))
             }
             PasswordEvent::Status(Err(passepartout::Error::Clipboard(e))) => {
-                Some(Action::SetStatus(format!("✗ Clipboard error: {e:?}")))
+                Some(Action::ReportError(format!("Clipboard error: {e}")))
             }
-            PasswordEvent::Status(Err(e)) => Some(Action::SetStatus(format!("✗ {e:?}"))),
+            PasswordEvent::Status(Err(e)) => Some(Action::ReportError(e.to_string())),
             PasswordEvent::PasswordFile {
                 pass_id,
+                generation,
                 file_contents,
+                modified,
+                target,
             } => Some(Action::DisplaySecrets {
                 pass_id,
+                generation,
                 file_contents,
+                modified,
+                target,
             }),
-            PasswordEvent::OneTimePassword { pass_id, otp } => {
-                Some(Action::DisplayOneTimePassword { pass_id, otp })
+            PasswordEvent::FileModified { pass_id, modified } => {
+                Some(Action::DisplayFileModified { pass_id, modified })
+            }
+            PasswordEvent::EntryCreated { pass_id } => {
+                Some(Action::Store(StoreAction::Created(pass_id)))
             }
+            PasswordEvent::EntryMoved { from, to } => {
+                Some(Action::Store(StoreAction::Moved { from, to }))
+            }
+            PasswordEvent::EntriesCleaned { moved, removed } => {
+                Some(Action::Store(StoreAction::Cleaned { moved, removed }))
+            }
+            PasswordEvent::EntriesAliased { target, pass_ids } => {
+                Some(Action::Store(StoreAction::Aliased { target, pass_ids }))
+            }
+            PasswordEvent::StorePulled { message } => {
+                Some(Action::Store(StoreAction::Pulled(message)))
+            }
+            PasswordEvent::Conflicts { conflicts } => {
+                Some(Action::Store(StoreAction::Conflicts(conflicts)))
+            }
+            PasswordEvent::GitStatus { summary } => Some(Action::DisplayGitStatus(summary)),
+            PasswordEvent::StoreScanned => Some(Action::Store(StoreAction::Reconcile)),
+            PasswordEvent::StoreChanged => Some(Action::Store(StoreAction::Changed)),
+            PasswordEvent::SessionLocked => Some(Action::Password(PasswordAction::Lock)),
+            PasswordEvent::QuitReady => Some(Action::Navigation(NavigationAction::Quit)),
+            PasswordEvent::SelectionRested => {
+                Some(Action::Navigation(NavigationAction::SelectionRested))
+            }
+            PasswordEvent::FetchFailed { pass_id, error } => Some(Action::DisplayFetchError {
+                pass_id,
+                message: error.to_string(),
+            }),
+            PasswordEvent::OneTimePassword {
+                pass_id,
+                generation,
+                otp,
+                valid_until,
+                period,
+            } => Some(Action::DisplayOneTimePassword {
+                pass_id,
+                generation,
+                otp,
+                valid_until,
+                period,
+            }),
+            PasswordEvent::EntryScanned {
+                scan_id,
+                pass_id,
+                finding,
+            } => Some(Action::Scan(ScanAction::Scanned {
+                scan_id,
+                pass_id,
+                finding,
+            })),
+            PasswordEvent::EntryScanFailed { scan_id, error } => {
+                Some(Action::Scan(ScanAction::Failed {
+                    scan_id,
+                    message: error.to_string(),
+                }))
+            }
+            PasswordEvent::LastChange {
+                pass_id,
+                last_change,
+            } => Some(Action::DisplayLastChange {
+                pass_id,
+                last_change,
+            }),
+            PasswordEvent::History { pass_id, revisions } => {
+                Some(Action::DisplayHistory { pass_id, revisions })
+            }
+            PasswordEvent::Diff {
+                pass_id,
+                generation,
+                old,
+                new,
+                lines,
+            } => Some(Action::DisplayDiff {
+                pass_id,
+                generation,
+                old,
+                new,
+                lines,
+            }),
         }
     }
 
-    fn dispatch_action(&mut self, action: Action) -> Result<()> {
+    /// Runs an action and all actions that follow from it.
+    pub fn dispatch(&mut self, action: Action) -> Result<()> {
         let mut current_action = action;
         loop {
             // Actions from App take precedence
@@ -249,7 +436,10 @@ impl App<'_> {
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::Navigation(NavigationAction::Quit) => self.quit(),
+            // The dashboard pushes first, the app quits once it is done
+            Action::Navigation(NavigationAction::Quit) if !self.dashboard.pushes_before_quit() => {
+                self.quit()
+            }
             Action::Redraw => self.request_redraw(),
             _ => (),
         }
@@ -264,3 +454,160 @@ impl App<'_> {
         self.running = false;
     }
 }
+
+impl Widget for &mut App<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.dashboard.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::*;
+    use crate::{actions::SearchAction, backend::Demo};
+
+    /// Returns the app on the demo store with a clock that stands still
+    /// half a second into a time step of one-time passwords.
+    fn demo_app() -> (App<'static>, Clock) {
+        let config = Config {
+            clear_after: Duration::from_secs(45),
+            ..Config::default()
+        };
+        let clock = Clock::stopped_at(UNIX_EPOCH + Duration::from_millis(1_699_999_980_500));
+        let demo = Demo::new(config.clear_after, clock).unwrap();
+        let app = App::with_backend(true, true, config, Arc::new(demo)).unwrap();
+        (app, clock)
+    }
+
+    fn draw(app: &mut App) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut *app, frame.area()))
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// Returns the text of the cells in a row from a column on.
+    fn row_text(buffer: &Buffer, x: u16, y: u16, width: u16) -> String {
+        (x..buffer.area.width.min(x + width))
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    fn render(app: &mut App) -> String {
+        let buffer = draw(app);
+        (0..buffer.area.height)
+            .map(|y| row_text(&buffer, 0, y, buffer.area.width) + "\n")
+            .collect()
+    }
+
+    /// Returns the one-time password and the countdown below it, which are
+    /// rendered below the title of the field.
+    fn otp_field(app: &mut App) -> (String, String) {
+        const TITLE: &str = "One-time password";
+        let buffer = draw(app);
+        let (x, y) = (0..buffer.area.height)
+            .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+            .find(|&(x, y)| row_text(&buffer, x, y, TITLE.len() as u16) == TITLE)
+            .expect("no one-time password shown");
+        let field = |y| row_text(&buffer, x, y, 24).trim().to_string();
+        (field(y + 2), field(y + 3))
+    }
+
+    fn search(app: &mut App, pattern: &str) {
+        app.dispatch(Action::Navigation(NavigationAction::Search))
+            .unwrap();
+        for c in pattern.chars() {
+            app.dispatch(Action::Search(SearchAction::Insert(c)))
+                .unwrap();
+        }
+        app.process_events().unwrap();
+    }
+
+    #[test]
+    fn lists_the_store() {
+        let (mut app, _) = demo_app();
+        let screen = render(&mut app);
+        assert!(screen.contains("email/personal"));
+        assert!(screen.contains("wifi/office"));
+    }
+
+    #[test]
+    fn search_filters_the_table() {
+        let (mut app, _) = demo_app();
+        search(&mut app, "wifi");
+        let screen = render(&mut app);
+        assert!(screen.contains("wifi/home"));
+        assert!(screen.contains("wifi/office"));
+        assert!(!screen.contains("email/personal"));
+    }
+
+    #[test]
+    fn lock_hides_the_secrets() {
+        let (mut app, _) = demo_app();
+        search(&mut app, "email/personal");
+        app.dispatch(Action::Navigation(NavigationAction::Secrets))
+            .unwrap();
+        app.process_events().unwrap();
+        let screen = render(&mut app);
+        assert!(screen.contains("correct-horse-battery-staple"));
+        assert!(screen.contains("jane.doe@example.com"));
+
+        app.dispatch(Action::Password(PasswordAction::Lock))
+            .unwrap();
+        app.process_events().unwrap();
+        let screen = render(&mut app);
+        assert!(!screen.contains("correct-horse-battery-staple"));
+        assert!(!screen.contains("jane.doe@example.com"));
+    }
+
+    #[test]
+    fn otp_counts_down() {
+        let (mut app, clock) = demo_app();
+        search(&mut app, "email/work");
+        app.dispatch(Action::Navigation(NavigationAction::Secrets))
+            .unwrap();
+        app.process_events().unwrap();
+        // The countdown is shown below the password with enough room
+        for _ in 0..4 {
+            app.dispatch(Action::Navigation(NavigationAction::GrowDetails))
+                .unwrap();
+        }
+        let (otp, countdown) = otp_field(&mut app);
+        assert!(otp.chars().all(|c| c.is_ascii_digit()) && otp.len() == 6);
+        assert!(countdown.starts_with("30s "));
+
+        clock.advance(Duration::from_secs(20));
+        let (_, countdown) = otp_field(&mut app);
+        assert!(countdown.starts_with("10s "));
+
+        // The expired password is hidden, generating the next one is left
+        // to the user when decrypting might ask for the passphrase
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(otp_field(&mut app), ("******".to_string(), String::new()));
+    }
+
+    #[test]
+    fn clipboard_counts_down_until_cleared() {
+        let (mut app, clock) = demo_app();
+        search(&mut app, "email/personal");
+        app.dispatch(Action::Password(PasswordAction::CopyPassword))
+            .unwrap();
+        app.process_events().unwrap();
+        clock.advance(Duration::from_millis(500));
+        assert!(render(&mut app).contains("clipboard 45s"));
+
+        clock.advance(Duration::from_secs(30));
+        assert!(render(&mut app).contains("clipboard 15s"));
+
+        clock.advance(Duration::from_millis(14_500));
+        // The status message stays, only the countdown is gone
+        let screen = render(&mut app);
+        assert!(screen.contains("clears after 45 seconds"));
+        assert!(!screen.contains("│ clipboard"));
+    }
+}
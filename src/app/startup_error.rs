@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// An error that prevents the dashboard from starting, shown on an error
+/// screen together with a hint on how to fix it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartupError {
+    pub message: String,
+    pub hint: String,
+}
+
+impl StartupError {
+    pub fn new(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            hint: hint.into(),
+        }
+    }
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.hint)
+    }
+}
+
+impl std::error::Error for StartupError {}
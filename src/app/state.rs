@@ -19,6 +19,8 @@ pub enum SearchState {
     Inactive,
     Suspended,
     Active,
+    /// The search completes the path of an entry to go to
+    Goto,
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
@@ -27,4 +29,19 @@ pub enum OverlayState {
     Inactive,
     Help,
     File,
+    RecoveryCodes,
+    WifiQr,
+    Qr,
+    Commands,
+    Audit,
+    OtpAudit,
+    Duplicates,
+    Strength,
+    History,
+    SessionLog,
+    Conflicts,
+    Generator,
+    Insert,
+    Prompt,
+    Dialog,
 }
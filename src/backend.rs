@@ -0,0 +1,280 @@
+//! Access to the environment of the app: the password store, the clipboard,
+//! the data directory and the clock.
+//!
+//! The app uses [`System`], [`Demo`] runs the dashboard against a fake store
+//! without touching the clipboard, e.g. for screenshots or for rendering it
+//! into a test buffer.
+
+use passepartout::{PasswordInfo, PasswordStore};
+use ratatui::crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{
+    io::stdout,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+mod demo;
+
+use crate::{
+    app::StartupError,
+    clipboard,
+    clock::{Clock, Moment},
+    command, data, generator,
+    git::{self, Revision},
+    gpg, pass,
+    store::{self, Excludes, Scan, StoreSnapshot},
+};
+
+pub use demo::Demo;
+
+pub trait Backend: Send + Sync {
+    /// Returns the directory of the password store.
+    fn store_dir(&self) -> Result<PathBuf, StartupError>;
+
+    /// Returns the directory for pinned entries and caches, `None` disables
+    /// persistence.
+    fn data_dir(&self) -> Option<PathBuf>;
+
+    /// Scans the store, see [`store::scan`].
+    fn scan(
+        &self,
+        store_dir: &Path,
+        passwords: Vec<PasswordInfo>,
+        snapshot: StoreSnapshot,
+        excludes: &Excludes,
+    ) -> Scan;
+
+    /// Returns the modification time of a password file.
+    fn modified(&self, file_path: &Path) -> Option<SystemTime>;
+
+    /// Decrypts a password file, blocks until decryption completes.
+    fn decrypt(&self, file_path: &Path) -> Result<String, passepartout::Error>;
+
+    /// Inserts a new password file into the store.
+    fn insert(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error>;
+
+    /// Replaces the contents of an existing password file.
+    fn overwrite(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error>;
+
+    /// Generates a new password file with a random password.
+    fn generate(
+        &self,
+        pass_id: &str,
+        options: &generator::Options,
+    ) -> Result<(), passepartout::Error>;
+
+    /// Renames a password file or moves it to another folder.
+    fn rename(&self, from: &str, to: &str) -> Result<(), passepartout::Error>;
+
+    /// Edits a password file in the editor of the user, blocks until the
+    /// editor is closed.
+    fn edit(&self, pass_id: &str) -> Result<(), passepartout::Error>;
+
+    /// Runs git in the store and returns its output.
+    fn git(&self, args: &[&str]) -> Result<String, passepartout::Error>;
+
+    /// Runs a command with the input on stdin.
+    fn run_command(&self, args: &[String], input: &str) -> Result<(), passepartout::Error>;
+
+    /// Returns the commits that changed a password file, the latest first.
+    fn history(
+        &self,
+        store_dir: &Path,
+        file_path: &Path,
+    ) -> Result<Vec<Revision>, passepartout::Error>;
+
+    /// Decrypts a password file as it was at a commit.
+    fn decrypt_revision(
+        &self,
+        file_path: &Path,
+        revision: &str,
+    ) -> Result<String, passepartout::Error>;
+
+    /// Compares two decrypted versions with a difftool, see
+    /// [`command::run_difftool`].
+    fn run_difftool(
+        &self,
+        args: &[String],
+        old: (&str, &str),
+        new: (&str, &str),
+    ) -> Result<(), passepartout::Error>;
+
+    /// Removes a password file from the store.
+    fn remove(&self, pass_id: &str) -> Result<(), passepartout::Error>;
+
+    /// Forgets the passphrases cached by the GnuPG agent.
+    fn forget_passphrases(&self) -> Result<(), passepartout::Error>;
+
+    /// Copies text with the provider configured for the content, secrets
+    /// are cleared after a while.
+    fn copy(&self, text: &str, content: clipboard::Content) -> Result<(), passepartout::Error>;
+
+    /// Returns when a copied secret is cleared from the clipboard.
+    fn clipboard_expiry(&self) -> Option<Moment>;
+
+    /// Returns why a copied secret could not be cleared from the clipboard,
+    /// once per failure.
+    fn take_clear_failure(&self) -> Option<String>;
+
+    /// Returns the clock of the wall time, e.g. for generating one-time
+    /// passwords.
+    fn clock(&self) -> Clock;
+}
+
+/// The environment passepartui runs in.
+#[derive(Debug, Clone)]
+pub struct System {
+    pass: pass::Pass,
+    /// How long copied secrets stay on the clipboard
+    clear_after: Duration,
+    clipboard: clipboard::Options,
+    clock: Clock,
+}
+
+impl System {
+    pub fn new(
+        pass: pass::Pass,
+        clear_after: Duration,
+        clipboard: clipboard::Options,
+        clock: Clock,
+    ) -> Self {
+        Self {
+            pass,
+            clear_after,
+            clipboard,
+            clock,
+        }
+    }
+}
+
+impl Backend for System {
+    fn store_dir(&self) -> Result<PathBuf, StartupError> {
+        // The store directory is looked up relative to the home directory
+        if dirs::home_dir().is_none() {
+            return Err(StartupError::new(
+                "Could not determine the home directory",
+                "Set $HOME to your home directory and start passepartui again.",
+            ));
+        }
+        Ok(PasswordStore::get_store_dir())
+    }
+
+    fn data_dir(&self) -> Option<PathBuf> {
+        data::default_dir()
+    }
+
+    fn scan(
+        &self,
+        store_dir: &Path,
+        passwords: Vec<PasswordInfo>,
+        snapshot: StoreSnapshot,
+        excludes: &Excludes,
+    ) -> Scan {
+        store::scan(store_dir, passwords, snapshot, excludes)
+    }
+
+    fn modified(&self, file_path: &Path) -> Option<SystemTime> {
+        store::modified(file_path)
+    }
+
+    fn decrypt(&self, file_path: &Path) -> Result<String, passepartout::Error> {
+        passepartout::decrypt_password_file(file_path)
+    }
+
+    fn insert(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        self.pass.insert(pass_id, file_contents)
+    }
+
+    fn overwrite(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        self.pass.overwrite(pass_id, file_contents)
+    }
+
+    fn generate(
+        &self,
+        pass_id: &str,
+        options: &generator::Options,
+    ) -> Result<(), passepartout::Error> {
+        self.pass.generate(pass_id, options)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), passepartout::Error> {
+        self.pass.rename(from, to)
+    }
+
+    /// The interface is suspended while the editor uses the terminal, it has
+    /// to be redrawn completely afterwards.
+    fn edit(&self, pass_id: &str) -> Result<(), passepartout::Error> {
+        execute!(stdout(), DisableMouseCapture, LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        let result = self.pass.edit(pass_id);
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        result
+    }
+
+    fn remove(&self, pass_id: &str) -> Result<(), passepartout::Error> {
+        self.pass.remove(pass_id)
+    }
+
+    fn forget_passphrases(&self) -> Result<(), passepartout::Error> {
+        gpg::forget_passphrases()
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String, passepartout::Error> {
+        self.pass.git(args)
+    }
+
+    fn run_command(&self, args: &[String], input: &str) -> Result<(), passepartout::Error> {
+        command::run(args, input)
+    }
+
+    fn history(
+        &self,
+        store_dir: &Path,
+        file_path: &Path,
+    ) -> Result<Vec<Revision>, passepartout::Error> {
+        Ok(git::history(store_dir, file_path)?)
+    }
+
+    fn decrypt_revision(
+        &self,
+        file_path: &Path,
+        revision: &str,
+    ) -> Result<String, passepartout::Error> {
+        gpg::decrypt(&git::show(file_path, revision)?)
+    }
+
+    fn run_difftool(
+        &self,
+        args: &[String],
+        old: (&str, &str),
+        new: (&str, &str),
+    ) -> Result<(), passepartout::Error> {
+        command::run_difftool(args, old, new)
+    }
+
+    fn copy(&self, text: &str, content: clipboard::Content) -> Result<(), passepartout::Error> {
+        let options = self.clipboard.for_content(content);
+        if content.is_secret() {
+            clipboard::copy_secret(text, self.clear_after, &options)
+        } else {
+            clipboard::copy(text, &options)
+        }
+    }
+
+    fn clipboard_expiry(&self) -> Option<Moment> {
+        clipboard::secret_expiry()
+    }
+
+    fn take_clear_failure(&self) -> Option<String> {
+        clipboard::take_clear_failure()
+    }
+
+    fn clock(&self) -> Clock {
+        self.clock
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, DirBuilder, OpenOptions},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+
+use passepartout::PasswordInfo;
+
+use super::Backend;
+use crate::{
+    app::StartupError,
+    clipboard,
+    clock::{Clock, Moment},
+    generator,
+    git::Revision,
+    pass,
+    store::{self, Excludes, Scan, StoreSnapshot},
+};
+
+/// Entries of the demo store, the data is made up.
+const ENTRIES: &[(&str, &str)] = &[
+    (
+        "email/personal",
+        "correct-horse-battery-staple\njane.doe@example.com\nurl: https://mail.example.com\n",
+    ),
+    (
+        "email/work",
+        "Tr0ub4dor&3\njane.doe@example.org\nurl: https://webmail.example.org\notpauth://totp/Example%20Mail:jane.doe@example.org?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=Example%20Mail\n",
+    ),
+    (
+        "finance/bank",
+        "h4T9!qLm2#vX\njdoe1984\nurl: https://bank.example.com/login\npin: 4711\n",
+    ),
+    (
+        "finance/broker",
+        "pl3nty-0f-st0cks\njane.doe\nurl: https://broker.example.net\n",
+    ),
+    (
+        "shopping/bookshop",
+        "r3ad1ng-1s-fun\njane.doe@example.com\nurl: https://books.example.com\n",
+    ),
+    (
+        "shopping/groceries",
+        "apples&oranges42\njane.doe@example.com\n",
+    ),
+    (
+        "social/forum",
+        "f0rum-p4ss\njdoe\nurl: https://forum.example.net\nnote: Signature is set to \"Cheers, Jane\"\n",
+    ),
+    (
+        "social/microblog",
+        "sh0rt-and-sw33t\n@janedoe\nurl: https://microblog.example.com\notpauth://totp/Microblog:janedoe?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=Microblog\nrecovery codes:\n4f8a-21c9\n93bd-7e02 (used)\nc1d5-88fa\n0a6e-3b47\n",
+    ),
+    (
+        "wifi/home",
+        "a-very-long-wifi-passphrase\nssid: Doe Family\n",
+    ),
+    (
+        "wifi/office",
+        "guest-2024\nssid: Example Guest\n",
+    ),
+    (
+        "work/api/token",
+        "tok_5f3e2d1c0b9a8f7e6d5c4b3a\nci-bot\ntoken: tok_5f3e2d1c0b9a8f7e6d5c4b3a\n",
+    ),
+    (
+        "work/registry",
+        "r3g1stry-t0ken\njdoe\nregistry: registry.example.org\n",
+    ),
+    (
+        "work/vpn",
+        "vpn-s3cr3t\njdoe\nurl: https://vpn.example.org\n",
+    ),
+    ("work/wiki", "alias: work/vpn\n"),
+];
+
+/// Commits of the made-up history every entry has, the latest first
+const REVISIONS: &[(&str, &str, &str)] = &[
+    ("5e1f0c2", "2024-06-03 18:21", "Rotate password"),
+    ("a93b7d4", "2023-11-14 09:02", "Add given password"),
+];
+
+/// A fake store for trying out the interface, nothing is encrypted, the
+/// clipboard is left alone and neither commands nor git are run.
+///
+/// The password files are empty files in a temporary directory so that the
+/// store can be scanned like a real one, their contents are kept in memory.
+#[derive(Debug)]
+pub struct Demo {
+    store_dir: PathBuf,
+    files: Mutex<HashMap<String, String>>,
+    /// When the last copied secret would be cleared
+    clipboard_expiry: Mutex<Option<Moment>>,
+    clear_after: Duration,
+    clock: Clock,
+}
+
+impl Demo {
+    pub fn new(clear_after: Duration, clock: Clock) -> Result<Self, StartupError> {
+        // Several demo stores may exist in one process, e.g. in tests
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let store_dir = env::temp_dir().join(format!(
+            "passepartui-demo-{}-{}",
+            process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let demo = Demo {
+            store_dir,
+            files: Mutex::new(HashMap::new()),
+            clipboard_expiry: Mutex::new(None),
+            clear_after,
+            clock,
+        };
+        for (pass_id, file_contents) in ENTRIES {
+            demo.write(pass_id, file_contents).map_err(|e| {
+                StartupError::new(
+                    format!("Failed to create the demo store: {e}"),
+                    "Check that the temporary directory is writable, see $TMPDIR.",
+                )
+            })?;
+        }
+        Ok(demo)
+    }
+
+    fn write(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        let file_path = self.store_dir.join(format!("{pass_id}.gpg"));
+        // Files and folders are created with the umask of `pass`
+        let mut dir_builder = DirBuilder::new();
+        let mut options = OpenOptions::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+
+            dir_builder.mode(0o777 & !pass::umask());
+            options.mode(0o666 & !pass::umask());
+        }
+        if let Some(parent) = file_path.parent() {
+            dir_builder.recursive(true).create(parent)?;
+        }
+        options
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&file_path)?
+            .set_modified(self.clock.now())?;
+        self.files
+            .lock()
+            .expect("demo store poisoned")
+            .insert(pass_id.to_string(), file_contents.to_string());
+        Ok(())
+    }
+}
+
+impl Drop for Demo {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.store_dir);
+    }
+}
+
+impl Backend for Demo {
+    fn store_dir(&self) -> Result<PathBuf, StartupError> {
+        Ok(self.store_dir.clone())
+    }
+
+    fn data_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn scan(
+        &self,
+        store_dir: &Path,
+        passwords: Vec<PasswordInfo>,
+        snapshot: StoreSnapshot,
+        excludes: &Excludes,
+    ) -> Scan {
+        store::scan(store_dir, passwords, snapshot, excludes)
+    }
+
+    fn modified(&self, file_path: &Path) -> Option<SystemTime> {
+        store::modified(file_path)
+    }
+
+    fn decrypt(&self, file_path: &Path) -> Result<String, passepartout::Error> {
+        store::pass_id(&self.store_dir, file_path)
+            .and_then(|pass_id| {
+                let files = self.files.lock().expect("demo store poisoned");
+                files.get(&pass_id).cloned()
+            })
+            .ok_or_else(|| passepartout::Error::Pass("entry not found".to_string()))
+    }
+
+    fn insert(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        self.write(pass_id, file_contents)
+    }
+
+    fn overwrite(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        self.write(pass_id, file_contents)
+    }
+
+    fn generate(
+        &self,
+        pass_id: &str,
+        options: &generator::Options,
+    ) -> Result<(), passepartout::Error> {
+        self.write(pass_id, &format!("{}\n", generator::generate(options)))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), passepartout::Error> {
+        let file_contents = self
+            .files
+            .lock()
+            .expect("demo store poisoned")
+            .remove(from)
+            .ok_or_else(|| passepartout::Error::Pass(format!("{from} not found")))?;
+        fs::remove_file(self.store_dir.join(format!("{from}.gpg")))?;
+        self.write(to, &file_contents)
+    }
+
+    fn edit(&self, _pass_id: &str) -> Result<(), passepartout::Error> {
+        Err(passepartout::Error::Pass(
+            "entries of the demo store can not be edited".to_string(),
+        ))
+    }
+
+    fn remove(&self, pass_id: &str) -> Result<(), passepartout::Error> {
+        self.files
+            .lock()
+            .expect("demo store poisoned")
+            .remove(pass_id)
+            .ok_or_else(|| passepartout::Error::Pass(format!("{pass_id} not found")))?;
+        fs::remove_file(self.store_dir.join(format!("{pass_id}.gpg")))?;
+        Ok(())
+    }
+
+    fn forget_passphrases(&self) -> Result<(), passepartout::Error> {
+        Ok(())
+    }
+
+    fn git(&self, _args: &[&str]) -> Result<String, passepartout::Error> {
+        Ok(String::new())
+    }
+
+    fn run_command(&self, _args: &[String], _input: &str) -> Result<(), passepartout::Error> {
+        Ok(())
+    }
+
+    fn history(
+        &self,
+        _store_dir: &Path,
+        file_path: &Path,
+    ) -> Result<Vec<Revision>, passepartout::Error> {
+        let pass_id = store::pass_id(&self.store_dir, file_path).unwrap_or_default();
+        Ok(REVISIONS
+            .iter()
+            .map(|(hash, date, subject)| Revision {
+                hash: hash.to_string(),
+                date: date.to_string(),
+                author: "Jane Doe".to_string(),
+                subject: format!("{subject} for {pass_id}"),
+            })
+            .collect())
+    }
+
+    /// The latest commit has the current contents, the password in the
+    /// one before was different.
+    fn decrypt_revision(
+        &self,
+        file_path: &Path,
+        revision: &str,
+    ) -> Result<String, passepartout::Error> {
+        let file_contents = self.decrypt(file_path)?;
+        if revision == REVISIONS[0].0 {
+            return Ok(file_contents);
+        }
+        let rest = file_contents.split_once('\n').map_or("", |(_, rest)| rest);
+        Ok(format!("old-password-2023\n{rest}"))
+    }
+
+    fn run_difftool(
+        &self,
+        _args: &[String],
+        _old: (&str, &str),
+        _new: (&str, &str),
+    ) -> Result<(), passepartout::Error> {
+        Ok(())
+    }
+
+    fn copy(&self, _text: &str, content: clipboard::Content) -> Result<(), passepartout::Error> {
+        *self.clipboard_expiry.lock().expect("demo store poisoned") = (content.is_secret()
+            && !self.clear_after.is_zero())
+        .then(|| self.clock.moment() + self.clear_after);
+        Ok(())
+    }
+
+    fn clipboard_expiry(&self) -> Option<Moment> {
+        *self.clipboard_expiry.lock().expect("demo store poisoned")
+    }
+
+    fn take_clear_failure(&self) -> Option<String> {
+        None
+    }
+
+    fn clock(&self) -> Clock {
+        self.clock
+    }
+}
@@ -0,0 +1,215 @@
+//! `passepartui bench-scan` times the steps of starting up on the user's
+//! store, e.g. for reports about slow starts on large or networked stores.
+
+use anyhow::{anyhow, bail, Context, Result};
+use passepartout::PasswordInfo;
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alias,
+    backend::{Backend, System},
+    clock::Clock,
+    components::{DateFormat, PasswordTable},
+    config::Config,
+    store::{self, StoreSnapshot},
+};
+
+/// Size of the terminal the table is rendered into
+const AREA: Rect = Rect::new(0, 0, 140, 40);
+
+/// Runs of each step unless `--runs` is given
+const DEFAULT_RUNS: usize = 3;
+
+/// Durations of the runs of a step.
+struct Timing {
+    name: &'static str,
+    runs: Vec<Duration>,
+    detail: String,
+}
+
+impl Timing {
+    fn median(&self) -> Duration {
+        let mut runs = self.runs.clone();
+        runs.sort();
+        runs[runs.len() / 2]
+    }
+}
+
+/// Times the steps and prints a breakdown, the decryption is only timed if
+/// an entry is given with `--decrypt <pass-id>`.
+pub fn run(args: &[String]) -> Result<()> {
+    let (runs, decrypt) = parse_args(args)?;
+    let started = Instant::now();
+    let config = Config::load()?;
+    let config_duration = started.elapsed();
+    let clock = Clock::from_env().map_err(|e| anyhow!(e))?;
+    let backend = System::new(
+        config.pass.clone(),
+        config.clear_after,
+        config.clipboard.clone(),
+        clock,
+    );
+    let store_dir = backend.store_dir().map_err(|e| anyhow!(e.message))?;
+    if !store_dir.is_dir() {
+        bail!("the password store {} does not exist", store_dir.display());
+    }
+
+    let mut timings = vec![Timing {
+        name: "Configuration",
+        runs: vec![config_duration],
+        detail: String::new(),
+    }];
+
+    let (walk, _) = time_runs(runs, || StoreSnapshot::new(&store_dir, &config.excludes));
+    timings.push(Timing {
+        name: "Directory walk",
+        runs: walk,
+        detail: String::new(),
+    });
+
+    let (scan, result) = time_runs(runs, || {
+        store::scan(
+            &store_dir,
+            Vec::new(),
+            StoreSnapshot::default(),
+            &config.excludes,
+        )
+    });
+    let passwords = result.passwords;
+    timings.push(Timing {
+        name: "Full scan",
+        runs: scan,
+        detail: format!("{} entries", passwords.len()),
+    });
+
+    // What the file watcher triggers when nothing changed
+    let mut snapshot = Some(result.snapshot);
+    let (rescan, _) = time_runs(runs, || {
+        let scan = store::scan(
+            &store_dir,
+            passwords.clone(),
+            snapshot.take().unwrap_or_default(),
+            &config.excludes,
+        );
+        snapshot = Some(scan.snapshot);
+    });
+    timings.push(Timing {
+        name: "Rescan, unchanged",
+        runs: rescan,
+        detail: String::new(),
+    });
+
+    let password_refs: Vec<&PasswordInfo> = passwords.iter().collect();
+    let mut table = PasswordTable::new(&[]);
+    table.date_format = DateFormat::new(config.date_locale.clone());
+    table.row_format = config.row_format.clone();
+    table.fuzzy = config.fuzzy_search;
+    let (rows, _) = time_runs(runs, || table.replace_passwords(&password_refs));
+    timings.push(Timing {
+        name: "Table rows",
+        runs: rows,
+        detail: String::new(),
+    });
+    let mut buffer = Buffer::empty(AREA);
+    let (render, _) = time_runs(runs, || table.render(AREA, &mut buffer));
+    timings.push(Timing {
+        name: "Table rendering",
+        runs: render,
+        detail: format!("{}x{} cells", AREA.width, AREA.height),
+    });
+
+    if let Some(pass_id) = &decrypt {
+        let file_path = store_dir.join(format!("{pass_id}.gpg"));
+        if !file_path.is_file() {
+            bail!("{pass_id} is not in the password store");
+        }
+        let mut durations = Vec::new();
+        for _ in 0..runs {
+            let started = Instant::now();
+            alias::decrypt(&backend, &file_path)
+                .map_err(|e| anyhow!("{e}"))
+                .context("failed to decrypt")?;
+            durations.push(started.elapsed());
+        }
+        timings.push(Timing {
+            name: "Decryption",
+            runs: durations,
+            detail: format!("{pass_id}, the first run may include pinentry"),
+        });
+    }
+
+    println!(
+        "passepartui {} bench-scan of {}, median of {runs} runs",
+        env!("CARGO_PKG_VERSION"),
+        store_dir.display()
+    );
+    for timing in &timings {
+        let range = match (timing.runs.iter().min(), timing.runs.iter().max()) {
+            (Some(min), Some(max)) if timing.runs.len() > 1 => {
+                format!("  {} – {}", milliseconds(*min), milliseconds(*max))
+            }
+            _ => String::new(),
+        };
+        let detail = match timing.detail.as_str() {
+            "" => String::new(),
+            detail => format!("  ({detail})"),
+        };
+        println!(
+            "{:<20}{:>12}{range}{detail}",
+            timing.name,
+            milliseconds(timing.median())
+        );
+    }
+    Ok(())
+}
+
+/// Reads `--runs <n>` and `--decrypt <pass-id>`.
+fn parse_args(args: &[String]) -> Result<(usize, Option<String>)> {
+    let mut runs = DEFAULT_RUNS;
+    let mut decrypt = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                runs = args
+                    .next()
+                    .and_then(|runs| runs.parse().ok())
+                    .filter(|runs| *runs > 0)
+                    .ok_or_else(|| anyhow!("--runs needs a positive number"))?;
+            }
+            "--decrypt" => {
+                let pass_id = args
+                    .next()
+                    .ok_or_else(|| anyhow!("--decrypt needs the ID of an entry"))?;
+                decrypt = Some(pass_id.trim_end_matches(".gpg").to_string());
+            }
+            _ => bail!(
+                "unexpected argument `{arg}`, usage: passepartui bench-scan [--runs <n>] \
+                 [--decrypt <pass-id>]"
+            ),
+        }
+    }
+    Ok((runs, decrypt))
+}
+
+/// Runs the step the given number of times, returns the durations and the
+/// result of the last run.
+fn time_runs<T>(runs: usize, mut step: impl FnMut() -> T) -> (Vec<Duration>, T) {
+    let mut durations = Vec::new();
+    loop {
+        let started = Instant::now();
+        let result = step();
+        durations.push(started.elapsed());
+        if durations.len() == runs {
+            return (durations, result);
+        }
+    }
+}
+
+fn milliseconds(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
@@ -0,0 +1,82 @@
+use std::{
+    io,
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Returns the URL stored in a password file.
+///
+/// Lines of the form `url: <url>` take precedence, otherwise the first line
+/// after the password that looks like a web address is used.
+pub fn find_url(file_contents: &str) -> Option<String> {
+    let mut lines = file_contents.lines().skip(1);
+    let explicit = lines.clone().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim().eq_ignore_ascii_case("url") && !value.trim().is_empty() {
+            Some(value.trim().to_string())
+        } else {
+            None
+        }
+    });
+    explicit.or_else(|| {
+        lines
+            .find(|line| {
+                let line = line.trim();
+                line.starts_with("https://") || line.starts_with("http://")
+            })
+            .map(|line| line.trim().to_string())
+    })
+}
+
+/// Returns the lowercase host of a URL without a leading `www.`, a URL
+/// without a scheme like `example.com/login` is accepted as well.
+pub fn host(url: &str) -> Option<String> {
+    let url = url.trim();
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match authority.strip_prefix('[') {
+        // IPv6 address
+        Some(address) => address.split(']').next()?,
+        None => authority.split(':').next()?,
+    };
+    let host = host.trim_end_matches('.').to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    (!host.is_empty() && !host.contains(char::is_whitespace)).then(|| host.to_string())
+}
+
+/// Returns how many labels of the host the domain of an entry matches,
+/// `None` unless it is the host itself or a parent domain of it, e.g.
+/// `example.com` for `login.example.com`. Top-level domains alone don't
+/// match.
+pub fn match_labels(domain: &str, host: &str) -> Option<usize> {
+    let labels = domain.split('.').count();
+    if labels < 2 && domain != host {
+        return None;
+    }
+    let matches = domain == host
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|subdomain| subdomain.ends_with('.'));
+    matches.then_some(labels)
+}
+
+/// Opens the URL with the default browser of the desktop environment.
+pub fn open(url: &str) -> io::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let mut child = Command::new(opener)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Reap the opener process without blocking the caller
+    thread::spawn(move || child.wait());
+    Ok(())
+}
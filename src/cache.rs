@@ -0,0 +1,97 @@
+//! In-memory cache of decrypted password files for the current session.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use crate::{clock::Moment, secret::Secret};
+
+#[derive(Debug)]
+struct CachedFile {
+    pass_id: String,
+    file_contents: Arc<Secret>,
+    /// Modification time of the file when it was decrypted
+    modified: Option<SystemTime>,
+    stored: Moment,
+}
+
+/// Keeps the contents of recently decrypted password files so that showing
+/// an entry again does not require another call to gpg. The contents are
+/// kept in locked memory, see [`Secret`].
+///
+/// Entries expire after `ttl`, the least recently used entry is dropped when
+/// more than `capacity` files are cached.
+#[derive(Debug, Default)]
+pub struct DetailsCache {
+    ttl: Duration,
+    capacity: usize,
+    // Least recently used first
+    files: Vec<CachedFile>,
+}
+
+impl DetailsCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            files: Vec::new(),
+        }
+    }
+
+    /// Returns the cached contents and modification time of a file if they
+    /// have not expired yet, the contents are shared and not copied.
+    pub fn get(&mut self, pass_id: &str) -> Option<(Arc<Secret>, Option<SystemTime>)> {
+        let ttl = self.ttl;
+        self.files.retain(|file| file.stored.elapsed() < ttl);
+        let index = self.files.iter().position(|file| file.pass_id == pass_id)?;
+        let file = self.files.remove(index);
+        let result = (Arc::clone(&file.file_contents), file.modified);
+        self.files.push(file);
+        Some(result)
+    }
+
+    /// Returns the cached contents of a file without counting it as used.
+    pub fn peek(&self, pass_id: &str) -> Option<&str> {
+        self.files
+            .iter()
+            .find(|file| file.pass_id == pass_id && file.stored.elapsed() < self.ttl)
+            .map(|file| file.file_contents.as_str())
+    }
+
+    pub fn insert(
+        &mut self,
+        pass_id: &str,
+        file_contents: Arc<Secret>,
+        modified: Option<SystemTime>,
+    ) {
+        self.invalidate(pass_id);
+        if self.capacity == 0 || self.ttl.is_zero() {
+            return;
+        }
+        if self.files.len() >= self.capacity {
+            self.files.remove(0);
+        }
+        self.files.push(CachedFile {
+            pass_id: pass_id.to_string(),
+            file_contents,
+            modified,
+            stored: Moment::now(),
+        });
+    }
+
+    pub fn invalidate(&mut self, pass_id: &str) {
+        self.files.retain(|file| file.pass_id != pass_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.files.clear();
+    }
+
+    /// Drops the files for which `is_current` returns `false` given the ID
+    /// and the modification time at decryption.
+    pub fn retain(&mut self, mut is_current: impl FnMut(&str, Option<SystemTime>) -> bool) {
+        self.files
+            .retain(|file| is_current(&file.pass_id, file.modified));
+    }
+}
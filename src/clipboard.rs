@@ -0,0 +1,600 @@
+use anyhow::{anyhow, Result};
+use arboard::{Clipboard, Set};
+use std::{
+    env,
+    io::{self, Write},
+    process::{Command, Stdio},
+    sync::{Mutex, PoisonError},
+    thread,
+    time::Duration,
+};
+
+use crate::{clock::Moment, session};
+
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+/// The secret on the clipboard that is cleared next
+static PENDING_CLEAR: Mutex<Option<PendingClear>> = Mutex::new(None);
+/// Why the last secret could not be cleared, taken by the interface
+static CLEAR_FAILURE: Mutex<Option<String>> = Mutex::new(None);
+/// Seconds until a secret is cleared, like the default of `pass --clip`
+pub const EXPIRATION_INTERVAL: Duration = Duration::from_secs(45);
+/// Time after clearing until the clipboard is checked again, clipboard
+/// managers put back what they consider lost a moment later
+const VERIFY_DELAY: Duration = Duration::from_millis(500);
+/// Longest escape sequence accepted by common terminals, e.g. hterm drops
+/// larger ones
+pub const OSC52_LIMIT: usize = 100_000;
+/// Name of the tmux paste buffer that holds copies made inside tmux
+const TMUX_BUFFER: &str = "passepartui";
+
+/// How text is copied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub provider: Provider,
+    /// Providers that replace `provider` for some of the copied contents
+    pub providers: Vec<(Content, Provider)>,
+    /// Selection the terminal sets with OSC 52
+    pub selection: Selection,
+    /// Maximum length of the OSC 52 escape sequence in bytes, 0 for none
+    pub osc52_limit: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        // The clipboard of the remote host is of no use over SSH
+        let provider = if session::over_ssh() {
+            Provider::Osc52
+        } else {
+            Provider::default()
+        };
+        Self {
+            provider,
+            providers: Vec::new(),
+            selection: Selection::default(),
+            osc52_limit: OSC52_LIMIT,
+        }
+    }
+}
+
+impl Options {
+    /// Returns the options for copying the content with its provider.
+    pub fn for_content(&self, content: Content) -> Self {
+        let provider = self
+            .providers
+            .iter()
+            .find(|(configured, _)| *configured == content)
+            .map_or(&self.provider, |(_, provider)| provider);
+        Self {
+            provider: provider.clone(),
+            providers: Vec::new(),
+            ..*self
+        }
+    }
+}
+
+/// Where copied text ends up.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum Provider {
+    /// The system clipboard, the terminal if it is not available, e.g. over
+    /// SSH or without X11 and Wayland
+    #[default]
+    Auto,
+    /// The system clipboard through X11 or Wayland
+    System,
+    /// The primary selection of X11 or Wayland that is pasted with the
+    /// middle mouse button, the clipboard on other platforms
+    Primary,
+    /// The clipboard of the terminal, set with an OSC 52 escape sequence
+    Osc52,
+    /// A program with its arguments that reads the text from stdin, e.g.
+    /// `wl-copy`
+    Command(Vec<String>),
+}
+
+impl Provider {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "auto" => Ok(Provider::Auto),
+            "system" => Ok(Provider::System),
+            "primary" => Ok(Provider::Primary),
+            "osc52" => Ok(Provider::Osc52),
+            _ => Err(anyhow!("unknown clipboard provider `{name}`")),
+        }
+    }
+
+    /// Returns true if the copied text can't be read back, so it is
+    /// overwritten unconditionally when clearing.
+    fn is_write_only(&self) -> bool {
+        matches!(self, Provider::Osc52 | Provider::Command(_))
+    }
+}
+
+/// What is copied, each can be given its own provider.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Content {
+    Password,
+    Login,
+    Otp,
+    /// Value of a `key: value` line
+    Field,
+    /// Path of an entry
+    Path,
+    /// Line of the decrypted file
+    Line,
+    RecoveryCode,
+    Generated,
+}
+
+impl Content {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "password" => Ok(Content::Password),
+            "login" => Ok(Content::Login),
+            "otp" => Ok(Content::Otp),
+            "field" => Ok(Content::Field),
+            "path" => Ok(Content::Path),
+            "line" => Ok(Content::Line),
+            "recovery_code" => Ok(Content::RecoveryCode),
+            "generated" => Ok(Content::Generated),
+            _ => Err(anyhow!("unknown clipboard content `{name}`")),
+        }
+    }
+
+    /// Returns true for secrets, which are cleared from the clipboard and
+    /// hidden from clipboard managers.
+    pub fn is_secret(self) -> bool {
+        self != Content::Path
+    }
+}
+
+/// Selection of the terminal, X11 terminals distinguish the clipboard from
+/// the primary selection that is pasted with the middle mouse button.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Selection {
+    #[default]
+    Clipboard,
+    Primary,
+    Both,
+}
+
+impl Selection {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "clipboard" => Ok(Selection::Clipboard),
+            "primary" => Ok(Selection::Primary),
+            "both" => Ok(Selection::Both),
+            _ => Err(anyhow!("unknown selection `{name}`")),
+        }
+    }
+
+    /// Returns the parameter of the OSC 52 escape sequence.
+    fn parameter(self) -> &'static str {
+        match self {
+            Selection::Clipboard => "c",
+            Selection::Primary => "p",
+            Selection::Both => "pc",
+        }
+    }
+}
+
+struct PendingClear {
+    expiry: Moment,
+    /// Options the secret was copied with, the provider is the one that was
+    /// used, either the system or the terminal
+    options: Options,
+    secret: String,
+    /// Contents of the clipboard before the first of the pending secrets
+    /// was copied, they are restored when clearing
+    previous: Option<String>,
+}
+
+/// Copies text to the system clipboard without clearing it later.
+pub fn copy(text: &str, options: &Options) -> Result<(), passepartout::Error> {
+    set_text(text, options, false)?;
+    *PENDING_CLEAR.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    Ok(())
+}
+
+/// Returns when the secret on the clipboard is cleared, if there is one.
+pub fn secret_expiry() -> Option<Moment> {
+    PENDING_CLEAR
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .as_ref()
+        .map(|pending| pending.expiry)
+        .filter(|expiry| expiry.remaining().is_some())
+}
+
+/// Blocks until the pending secret has been cleared from the clipboard,
+/// e.g. before exiting, which would leave it there.
+pub fn wait_for_clear() {
+    loop {
+        // The lock is held while clearing, so `None` means it is done
+        let expiry = match &*PENDING_CLEAR.lock().unwrap_or_else(PoisonError::into_inner) {
+            Some(pending) => pending.expiry,
+            None => return,
+        };
+        expiry.sleep_until();
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Returns why clearing the last secret from the clipboard failed, only
+/// once per failure.
+pub fn take_clear_failure() -> Option<String> {
+    CLEAR_FAILURE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+}
+
+/// Copies a secret to the system clipboard, after the interval the
+/// previous contents of the clipboard are restored like with `pass --clip`.
+/// A zero interval keeps the secret on the clipboard. Clipboard managers
+/// are asked not to record the secret in their history.
+pub fn copy_secret(
+    text: &str,
+    clear_after: Duration,
+    options: &Options,
+) -> Result<(), passepartout::Error> {
+    if clear_after.is_zero() {
+        set_text(text, options, true)?;
+        *PENDING_CLEAR.lock().unwrap_or_else(PoisonError::into_inner) = None;
+        return Ok(());
+    }
+    let mut pending = PENDING_CLEAR.lock().unwrap_or_else(PoisonError::into_inner);
+    // Copying another secret before the last one was cleared keeps what was
+    // on the clipboard before either of them
+    let previous = match pending.take() {
+        Some(pending) => pending.previous,
+        None => get_text(&options.provider),
+    };
+    let provider = set_text(text, options, true)?;
+    let expiry = Moment::now() + clear_after;
+    *pending = Some(PendingClear {
+        expiry,
+        options: Options {
+            provider,
+            ..options.clone()
+        },
+        secret: text.to_string(),
+        previous,
+    });
+    drop(pending);
+
+    thread::spawn(move || {
+        expiry.sleep_until();
+        let mut pending = PENDING_CLEAR.lock().unwrap_or_else(PoisonError::into_inner);
+        // Later copies replace the pending secret and clear it themselves
+        let Some(PendingClear {
+            options,
+            secret,
+            previous,
+            ..
+        }) = pending.take_if(|pending| pending.expiry == expiry)
+        else {
+            return;
+        };
+        // The terminal and commands cannot be asked what is on the
+        // clipboard, the secret is overwritten unconditionally
+        if options.provider.is_write_only() {
+            if let Err(e) = set_text(previous.as_deref().unwrap_or_default(), &options, false) {
+                report_clear_failure(format!("Clearing the clipboard failed: {e}"));
+            }
+            return;
+        }
+        let primary = options.provider == Provider::Primary;
+        let mut clipboard = CLIPBOARD.lock().unwrap_or_else(PoisonError::into_inner);
+        // Only touch the clipboard if it still contains the secret
+        let Some(clipboard_instance) = clipboard.as_mut() else {
+            return;
+        };
+        if !get_system_text(clipboard_instance, primary).is_ok_and(|current| current == secret) {
+            return;
+        }
+        let restores_secret = previous.as_ref() == Some(&secret);
+        let result = match previous {
+            Some(previous) => select(clipboard_instance.set(), primary).text(previous),
+            None => clear_system_text(clipboard_instance, primary),
+        };
+        drop(clipboard);
+        drop(pending);
+        if let Err(e) = result {
+            report_clear_failure(format!("Clearing the clipboard failed: {e}"));
+            return;
+        }
+        if restores_secret {
+            return;
+        }
+
+        thread::sleep(VERIFY_DELAY);
+        let copied_again = PENDING_CLEAR
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .is_some_and(|pending| pending.secret == secret);
+        if !copied_again && get_text(&options.provider) == Some(secret) {
+            report_clear_failure(
+                "The secret is still on the clipboard, e.g. restored by a clipboard manager"
+                    .to_string(),
+            );
+        }
+    });
+    Ok(())
+}
+
+fn report_clear_failure(message: String) {
+    *CLEAR_FAILURE.lock().unwrap_or_else(PoisonError::into_inner) = Some(message);
+}
+
+/// Returns the text on the clipboard, `None` if it is empty, holds
+/// something else, e.g. an image, or can't be read like the clipboard of
+/// the terminal.
+fn get_text(provider: &Provider) -> Option<String> {
+    if provider.is_write_only() {
+        return None;
+    }
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(PoisonError::into_inner);
+    let clipboard_instance = match &mut *clipboard {
+        Some(instance) => instance,
+        None => clipboard.insert(Clipboard::new().ok()?),
+    };
+    get_system_text(clipboard_instance, *provider == Provider::Primary).ok()
+}
+
+/// Copies text with the provider, returns the one that was used.
+fn set_text(text: &str, options: &Options, secret: bool) -> Result<Provider, passepartout::Error> {
+    match &options.provider {
+        Provider::System => set_system_text(text, secret, false).map(|()| Provider::System),
+        Provider::Primary => set_system_text(text, secret, true).map(|()| Provider::Primary),
+        Provider::Osc52 => set_terminal_text(text, options).map(|()| Provider::Osc52),
+        Provider::Command(args) => {
+            run_command(args, text).map(|()| Provider::Command(args.clone()))
+        }
+        Provider::Auto => set_system_text(text, secret, false)
+            .map(|()| Provider::System)
+            .or_else(|_| set_terminal_text(text, options).map(|()| Provider::Osc52)),
+    }
+}
+
+fn set_system_text(text: &str, secret: bool, primary: bool) -> Result<(), passepartout::Error> {
+    // The clipboard stays usable even if a thread panicked while holding it
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(PoisonError::into_inner);
+    let clipboard_instance = match &mut *clipboard {
+        Some(instance) => instance,
+        None => clipboard.insert(Clipboard::new()?),
+    };
+    let set = select(clipboard_instance.set(), primary);
+    if secret {
+        exclude_from_history(set).text(text)?;
+    } else {
+        set.text(text)?;
+    }
+    Ok(())
+}
+
+/// Runs the command with the text on stdin, e.g. `wl-copy`.
+fn run_command(args: &[String], text: &str) -> Result<(), passepartout::Error> {
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| passepartout::Error::Pass("empty clipboard command".to_string()))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(passepartout::Error::Pass(format!(
+            "clipboard command `{program}` failed ({status})"
+        )));
+    }
+    Ok(())
+}
+
+/// Chooses between the clipboard and the primary selection of X11 and
+/// Wayland.
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+))]
+fn select(set: Set<'_>, primary: bool) -> Set<'_> {
+    use arboard::{LinuxClipboardKind, SetExtLinux};
+    if primary {
+        set.clipboard(LinuxClipboardKind::Primary)
+    } else {
+        set
+    }
+}
+
+#[cfg(not(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+)))]
+fn select(set: Set<'_>, _primary: bool) -> Set<'_> {
+    set
+}
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+))]
+fn get_system_text(clipboard: &mut Clipboard, primary: bool) -> Result<String, arboard::Error> {
+    use arboard::{GetExtLinux, LinuxClipboardKind};
+    if primary {
+        clipboard
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+    } else {
+        clipboard.get_text()
+    }
+}
+
+#[cfg(not(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+)))]
+fn get_system_text(clipboard: &mut Clipboard, _primary: bool) -> Result<String, arboard::Error> {
+    clipboard.get_text()
+}
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+))]
+fn clear_system_text(clipboard: &mut Clipboard, primary: bool) -> Result<(), arboard::Error> {
+    use arboard::{ClearExtLinux, LinuxClipboardKind};
+    if primary {
+        clipboard
+            .clear_with()
+            .clipboard(LinuxClipboardKind::Primary)
+    } else {
+        clipboard.clear()
+    }
+}
+
+#[cfg(not(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+)))]
+fn clear_system_text(clipboard: &mut Clipboard, _primary: bool) -> Result<(), arboard::Error> {
+    clipboard.clear()
+}
+
+/// Marks the contents as a password with the `x-kde-passwordManagerHint`
+/// type that is respected by Klipper and other clipboard managers.
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "android", target_os = "emscripten"))
+))]
+fn exclude_from_history(set: Set<'_>) -> Set<'_> {
+    use arboard::SetExtLinux;
+    set.exclude_from_history()
+}
+
+/// Marks the contents with `org.nspasteboard.ConcealedType` for clipboard
+/// managers.
+#[cfg(target_os = "macos")]
+fn exclude_from_history(set: Set<'_>) -> Set<'_> {
+    use arboard::SetExtApple;
+    set.exclude_from_history()
+}
+
+/// Sets the `ExcludeClipboardContentFromMonitorProcessing`,
+/// `CanIncludeInClipboardHistory` and `CanUploadToCloudClipboard` formats.
+#[cfg(windows)]
+fn exclude_from_history(set: Set<'_>) -> Set<'_> {
+    use arboard::SetExtWindows;
+    set.exclude_from_monitoring()
+        .exclude_from_history()
+        .exclude_from_cloud()
+}
+
+#[cfg(not(any(
+    windows,
+    target_os = "macos",
+    all(unix, not(any(target_os = "android", target_os = "emscripten")))
+)))]
+fn exclude_from_history(set: Set<'_>) -> Set<'_> {
+    set
+}
+
+/// Asks the terminal to put the text on its clipboard, an empty text clears
+/// it. Terminals don't answer, so copying fails only if the text is too
+/// large for the configured limit or stdout is gone.
+fn set_terminal_text(text: &str, options: &Options) -> Result<(), passepartout::Error> {
+    let sequence = format!(
+        "\x1b]52;{};{}\x07",
+        options.selection.parameter(),
+        base64(text.as_bytes())
+    );
+    // Terminals silently drop sequences over their limit, which would leave
+    // the previous contents on the clipboard
+    if options.osc52_limit > 0 && sequence.len() > options.osc52_limit {
+        return Err(arboard::Error::Unknown {
+            description: format!(
+                "the escape sequence for the terminal needs {} bytes, more than the limit of {}",
+                sequence.len(),
+                options.osc52_limit
+            ),
+        }
+        .into());
+    }
+    // Inside tmux the escape sequence only reaches the terminal with
+    // `set-clipboard on` or `allow-passthrough on`, tmux itself can forward
+    // the text since 3.2
+    let in_tmux = env::var_os("TMUX").is_some();
+    if in_tmux && set_tmux_text(text).is_ok() {
+        return Ok(());
+    }
+    let sequence = if in_tmux {
+        tmux_passthrough(&sequence)
+    } else {
+        sequence
+    };
+    // Written in one piece so that it can't end up inside an escape sequence
+    // of a frame that is drawn at the same time
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Hands the text to tmux, which puts it on the clipboard of the terminal
+/// it runs in. The paste buffer is deleted again when the copy is cleared.
+fn set_tmux_text(text: &str) -> io::Result<()> {
+    // tmux ignores empty text, a space replaces the copy instead
+    let clearing = text.is_empty();
+    let mut child = Command::new("tmux")
+        .args(["load-buffer", "-w", "-b", TMUX_BUFFER, "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(if clearing { b" " } else { text.as_bytes() })?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "tmux load-buffer failed ({status})"
+        )));
+    }
+    if clearing {
+        let _ = Command::new("tmux")
+            .args(["delete-buffer", "-b", TMUX_BUFFER])
+            .stderr(Stdio::null())
+            .status();
+    }
+    Ok(())
+}
+
+/// Wraps an escape sequence for tmux to pass it on to the terminal it runs
+/// in, escape characters inside are doubled.
+fn tmux_passthrough(sequence: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+}
+
+/// Encodes bytes as base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | (u32::from(byte) << (16 - 8 * i))
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((group >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,166 @@
+//! The clock of the features that depend on time, i.e. one-time passwords,
+//! ages of entries, the action log, clearing the clipboard and forgetting
+//! decrypted files.
+//!
+//! There are two readings. The wall time is for everything related to
+//! dates, e.g. generating one-time passwords. Timers use a [`Moment`]
+//! instead, which is counted from boot and neither jumps when the wall time
+//! is corrected, e.g. by NTP, nor stops while the system is suspended like
+//! `std::time::Instant` does on Linux.
+//!
+//! `PASSEPARTUI_CLOCK` starts the wall time at a Unix timestamp, so that
+//! one-time passwords, countdowns and ages are reproducible in tests and
+//! screenshots.
+
+#[cfg(test)]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    env,
+    ops::Add,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Longest sleep of a timer before checking the time again, sleeping stops
+/// while the system is suspended
+const MAX_SLEEP: Duration = Duration::from_secs(1);
+
+/// Source of the wall time and of the moments of timers.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Clock {
+    /// Wall time at a moment, it runs on from there, `None` follows the
+    /// system
+    start: Option<(SystemTime, Moment)>,
+    /// Time a stopped clock was advanced by, shared by its copies
+    #[cfg(test)]
+    advanced: Option<&'static AtomicU64>,
+}
+
+impl Clock {
+    /// Returns the clock of the system unless `PASSEPARTUI_CLOCK` sets the
+    /// start of the wall time.
+    pub fn from_env() -> Result<Self, String> {
+        match env::var("PASSEPARTUI_CLOCK") {
+            Ok(seconds) => seconds
+                .trim()
+                .parse()
+                .map(|seconds| Self::starting_at(UNIX_EPOCH + Duration::from_secs(seconds)))
+                .map_err(|_| {
+                    format!(
+                        "PASSEPARTUI_CLOCK must be a Unix timestamp in seconds, not `{seconds}`"
+                    )
+                }),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Returns a clock whose wall time starts at the given time now.
+    pub fn starting_at(time: SystemTime) -> Self {
+        Self {
+            start: Some((time, Moment::now())),
+            #[cfg(test)]
+            advanced: None,
+        }
+    }
+
+    /// Returns a clock that stands still at the given time until it is
+    /// advanced, which moves all of its copies.
+    #[cfg(test)]
+    pub fn stopped_at(time: SystemTime) -> Self {
+        Self {
+            advanced: Some(Box::leak(Box::new(AtomicU64::new(0)))),
+            ..Self::starting_at(time)
+        }
+    }
+
+    #[cfg(test)]
+    pub fn advance(&self, duration: Duration) {
+        self.advanced
+            .expect("only a stopped clock can be advanced")
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the current wall time.
+    pub fn now(&self) -> SystemTime {
+        match self.start {
+            Some((time, moment)) => time + self.moment().saturating_duration_since(moment),
+            None => SystemTime::now(),
+        }
+    }
+
+    /// Returns the current moment for timers.
+    pub fn moment(&self) -> Moment {
+        #[cfg(test)]
+        if let (Some((_, start)), Some(advanced)) = (self.start, self.advanced) {
+            return start + Duration::from_nanos(advanced.load(Ordering::Relaxed));
+        }
+        Moment::now()
+    }
+}
+
+/// A point in time for timers, counted from boot including suspended time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Moment(Duration);
+
+impl Moment {
+    pub fn now() -> Self {
+        Self(since_boot())
+    }
+
+    pub fn elapsed(self) -> Duration {
+        Self::now().saturating_duration_since(self)
+    }
+
+    pub fn saturating_duration_since(self, earlier: Self) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Returns the time left until the moment, `None` if it has passed.
+    pub fn remaining(self) -> Option<Duration> {
+        self.remaining_at(Self::now())
+    }
+
+    /// Returns the time left from `now` until the moment, `None` if it has
+    /// passed by then.
+    pub fn remaining_at(self, now: Self) -> Option<Duration> {
+        self.0.checked_sub(now.0).filter(|left| !left.is_zero())
+    }
+
+    /// Blocks until the moment has passed, also if the system was suspended
+    /// in the meantime.
+    pub fn sleep_until(self) {
+        while let Some(left) = self.remaining() {
+            thread::sleep(left.min(MAX_SLEEP));
+        }
+    }
+}
+
+impl Add<Duration> for Moment {
+    type Output = Self;
+
+    fn add(self, duration: Duration) -> Self {
+        Self(self.0 + duration)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn since_boot() -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: the pointer is valid for writing the time, `CLOCK_BOOTTIME`
+    // exists since Linux 2.6.39
+    unsafe { libc::clock_gettime(libc::CLOCK_BOOTTIME, &mut time) };
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
+
+/// Other systems count from the start of the process with the monotonic
+/// clock of the standard library.
+#[cfg(not(target_os = "linux"))]
+fn since_boot() -> Duration {
+    use std::{sync::LazyLock, time::Instant};
+
+    static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+    START.elapsed()
+}
@@ -0,0 +1,228 @@
+use std::{
+    env,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+#[cfg(unix)]
+use std::{
+    fs::{self, DirBuilder, OpenOptions},
+    io, process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{config, entry};
+
+/// Returns the arguments of a command with the placeholders filled in from
+/// a password file.
+///
+/// `{pass_id}` is replaced with the ID of the entry, `{login}` with the second
+/// line of the file and any other `{key}` with the value of a `key: value`
+/// line.
+pub fn fill_args(
+    args: &[String],
+    pass_id: &str,
+    file_contents: &str,
+) -> Result<Vec<String>, passepartout::Error> {
+    args.iter()
+        .map(|arg| {
+            let mut filled = String::with_capacity(arg.len());
+            let mut rest = arg.as_str();
+            while let Some((before, after)) = rest.split_once('{') {
+                let Some((key, after)) = after.split_once('}') else {
+                    break;
+                };
+                let value = match key {
+                    "pass_id" => Some(pass_id.to_string()),
+                    "login" => file_contents.lines().nth(1).map(String::from),
+                    key => entry::field_value(file_contents, key),
+                };
+                let value = value
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| passepartout::Error::Pass(format!("no {key} found")))?;
+                filled.push_str(before);
+                filled.push_str(&value);
+                rest = after;
+            }
+            filled.push_str(rest);
+            Ok(filled)
+        })
+        .collect()
+}
+
+/// Runs a command with the input on stdin instead of passing it as argument
+/// or through the clipboard, e.g. `docker login --password-stdin`.
+///
+/// The error output of the command is returned as error message if it fails.
+pub fn run(args: &[String], input: &str) -> Result<(), passepartout::Error> {
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| passepartout::Error::Pass("empty command".to_string()))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    stdin.write_all(input.as_bytes())?;
+    // Close stdin so that the command stops reading
+    drop(stdin);
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(passepartout::Error::Pass(message))
+    }
+}
+
+/// Runs the command picking the password store when starting and sets
+/// `PASSWORD_STORE_DIR` to the path it prints, for `pass` as well.
+///
+/// Nothing printed keeps the store that would be opened otherwise.
+pub fn select_store(args: &[String]) -> Result<(), String> {
+    let (program, args) = args.split_first().ok_or("empty command")?;
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run {program}: {e}"))?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if message.is_empty() {
+            format!("{program} failed with {}", output.status)
+        } else {
+            format!("{program} failed: {message}")
+        });
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let Some(path) = stdout.lines().map(str::trim).find(|line| !line.is_empty()) else {
+        return Ok(());
+    };
+    let store_dir = PathBuf::from(config::expand_home(path));
+    if !store_dir.is_dir() {
+        return Err(format!("{program} picked {path}, which is not a directory"));
+    }
+    // Set before any other thread is started
+    env::set_var("PASSWORD_STORE_DIR", store_dir);
+    Ok(())
+}
+
+/// Directory only the user can access for the plaintext files passed to a
+/// difftool, the files are overwritten and removed when it is dropped.
+#[cfg(unix)]
+struct PlaintextDir {
+    path: PathBuf,
+    files: Vec<(PathBuf, usize)>,
+}
+
+#[cfg(unix)]
+impl PlaintextDir {
+    /// Creates the directory in `$XDG_RUNTIME_DIR`, which usually is not
+    /// backed by a disk, or in the temporary directory otherwise.
+    fn new() -> io::Result<Self> {
+        use std::os::unix::fs::DirBuilderExt;
+
+        let parent = env::var_os("XDG_RUNTIME_DIR")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_dir())
+            .unwrap_or_else(env::temp_dir);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.subsec_nanos());
+        let path = parent.join(format!("passepartui-diff-{}-{nanos}", process::id()));
+        DirBuilder::new().mode(0o700).create(&path)?;
+        Ok(Self {
+            path,
+            files: Vec::new(),
+        })
+    }
+
+    fn write(&mut self, name: &str, contents: &str) -> io::Result<PathBuf> {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let path = self.path.join(name);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)?;
+        self.files.push((path.clone(), contents.len()));
+        file.write_all(contents.as_bytes())?;
+        Ok(path)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for PlaintextDir {
+    fn drop(&mut self) {
+        for (path, len) in &self.files {
+            let _ = fs::write(path, vec![0; *len]);
+            let _ = fs::remove_file(path);
+        }
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Shows two versions of a password file with a difftool and waits until
+/// it exits.
+///
+/// `{old}` and `{new}` in the arguments are replaced with the paths of the
+/// versions, they are appended if the placeholders are missing. The files
+/// only exist while the tool runs, so it has to keep running until it is
+/// closed, e.g. `meld --newtab` returns immediately.
+///
+/// The tool runs in the background while the interface stays usable and
+/// gets no terminal, so only graphical tools are supported, not e.g.
+/// `vimdiff`.
+#[cfg(unix)]
+pub fn run_difftool(
+    args: &[String],
+    old: (&str, &str),
+    new: (&str, &str),
+) -> Result<(), passepartout::Error> {
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| passepartout::Error::Pass("no difftool configured".to_string()))?;
+    let mut dir = PlaintextDir::new()?;
+    let old_path = dir.write(&format!("old-{}", old.0), old.1)?;
+    let new_path = dir.write(&format!("new-{}", new.0), new.1)?;
+    let (old_path, new_path) = (
+        old_path.to_string_lossy().into_owned(),
+        new_path.to_string_lossy().into_owned(),
+    );
+    let mut args: Vec<String> = args
+        .iter()
+        .map(|arg| arg.replace("{old}", &old_path).replace("{new}", &new_path))
+        .collect();
+    if !args.iter().any(|arg| arg.contains(&old_path)) {
+        args.push(old_path);
+        args.push(new_path);
+    }
+    let output = Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()?;
+    // Diff tools exit with 1 if the files differ
+    if output.status.success() || output.status.code() == Some(1) {
+        Ok(())
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(passepartout::Error::Pass(message))
+    }
+}
+
+/// The plaintext files can only be restricted to the user on Unix.
+#[cfg(not(unix))]
+pub fn run_difftool(
+    _args: &[String],
+    _old: (&str, &str),
+    _new: (&str, &str),
+) -> Result<(), passepartout::Error> {
+    Err(passepartout::Error::Pass(
+        "comparing with a difftool is only supported on Unix".to_string(),
+    ))
+}
@@ -0,0 +1,53 @@
+//! Completion of paths in the password store, segment by segment like the
+//! completion of file names in a shell.
+//!
+//! The paths are the IDs of the store together with their folders, which end
+//! with a slash, e.g. "email/" and "email/work".
+
+use std::collections::BTreeSet;
+
+/// Returns the IDs together with their folders in sorted order.
+pub fn store_paths<'a>(ids: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut paths = BTreeSet::new();
+    for id in ids {
+        for (index, _) in id.match_indices('/') {
+            paths.insert(id[..=index].to_string());
+        }
+        paths.insert(id.to_string());
+    }
+    paths.into_iter().collect()
+}
+
+/// Returns the paths starting with the text up to the next slash, the
+/// paths have to be sorted.
+pub fn completions<'a>(paths: &'a [String], text: &str) -> Vec<&'a str> {
+    let mut completions: Vec<&str> = paths
+        .iter()
+        .filter_map(|path| {
+            let rest = path.strip_prefix(text)?;
+            let end = rest.find('/').map_or(path.len(), |i| text.len() + i + 1);
+            Some(&path[..end])
+        })
+        .collect();
+    completions.dedup();
+    completions
+}
+
+/// Returns the text extended with the part that all completions share,
+/// `None` if there is nothing to complete.
+pub fn complete(paths: &[String], text: &str) -> Option<String> {
+    let completions = completions(paths, text);
+    let (first, rest) = completions.split_first()?;
+    let mut shared = first.len();
+    for completion in rest {
+        let common = first
+            .char_indices()
+            .zip(completion.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0);
+        shared = shared.min(common);
+    }
+    (shared > text.len()).then(|| first[..shared].to_string())
+}
@@ -1,31 +1,118 @@
 use anyhow::Result;
 use ratatui::{crossterm::event::MouseEvent, layout::Rect};
 
+mod audit_popup;
+mod banner;
 mod button;
+mod commands_popup;
+mod conflicts_popup;
 mod dashboard;
+mod dialog_popup;
+mod duplicates_popup;
+mod error_screen;
 mod file_popup;
+mod generator_popup;
 mod help_popup;
+mod history_popup;
+mod insert_popup;
 mod menu;
+mod otp_audit_popup;
 mod password_details;
 mod password_table;
+mod prompt_popup;
+mod qr_popup;
+mod recovery_codes_popup;
 mod search_field;
+mod session_log_popup;
 mod status_bar;
+mod strength_popup;
+mod text_input;
 
 use crate::actions::Action;
+pub use audit_popup::{AuditPopup, StaleEntry};
+pub use banner::Banner;
 pub use button::Button;
+pub use commands_popup::CommandsPopup;
+pub use conflicts_popup::{Conflict, ConflictsPopup};
 pub use dashboard::Dashboard;
+pub use dialog_popup::{Dialog, DialogPopup};
+pub use duplicates_popup::DuplicatesPopup;
+pub use error_screen::ErrorScreen;
 pub use file_popup::FilePopup;
+pub use generator_popup::GeneratorPopup;
 pub use help_popup::HelpPopup;
+pub use history_popup::HistoryPopup;
+pub use insert_popup::InsertPopup;
 pub use menu::Menu;
+pub use otp_audit_popup::OtpAuditPopup;
 pub use password_details::PasswordDetails;
-pub use password_table::PasswordTable;
+pub use password_table::{DateFormat, PasswordTable, SortColumn};
+pub use prompt_popup::{PromptPopup, PromptPurpose};
+pub use qr_popup::QrPopup;
+pub use recovery_codes_popup::RecoveryCodesPopup;
 pub use search_field::SearchField;
-pub use status_bar::StatusBar;
+pub use session_log_popup::SessionLogPopup;
+pub use status_bar::{Segment, StatusBar};
+pub use strength_popup::StrengthPopup;
+pub use text_input::TextInput;
 
 pub trait Component {
     fn update(&mut self, action: Action) -> Result<Option<Action>>;
 }
 
+/// Keyboard focus over the buttons of a component, moved with (Tab) and
+/// (Shift-Tab) and pressed with (Enter) or (Space).
+pub trait FocusSupport<'a> {
+    /// Returns the buttons that are shown in focus order.
+    fn buttons(&mut self) -> Vec<&mut Button<'a>>;
+
+    fn focused_button(&mut self) -> &mut Option<usize>;
+
+    /// Focuses the next button, after the last one no button is focused.
+    fn focus_next(&mut self) {
+        let count = self.buttons().len();
+        let next = match *self.focused_button() {
+            Some(index) => index + 1,
+            None => 0,
+        };
+        self.set_focus(Some(next).filter(|&next| next < count));
+    }
+
+    /// Focuses the previous button, before the first one no button is
+    /// focused.
+    fn focus_previous(&mut self) {
+        let count = self.buttons().len();
+        let previous = match *self.focused_button() {
+            Some(index) => index.checked_sub(1),
+            None => count.checked_sub(1),
+        };
+        self.set_focus(previous);
+    }
+
+    fn clear_focus(&mut self) {
+        if self.focused_button().is_some() {
+            self.set_focus(None);
+        }
+    }
+
+    fn set_focus(&mut self, focus: Option<usize>) {
+        *self.focused_button() = focus;
+        for (index, button) in self.buttons().into_iter().enumerate() {
+            if Some(index) == focus {
+                button.select();
+            } else {
+                button.reset();
+            }
+        }
+    }
+
+    /// Returns the action of the focused button.
+    fn press_focused(&mut self) -> Option<Action> {
+        let focus = (*self.focused_button())?;
+        self.buttons().get(focus)?.press()
+    }
+}
+
 pub trait MouseSupport {
     fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action>;
 
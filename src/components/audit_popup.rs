@@ -0,0 +1,319 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, AuditAction, NavigationAction},
+    components::{Button, FocusSupport, MouseSupport},
+    store,
+    theme::Theme,
+};
+
+/// An entry that was neither used nor modified for a while.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleEntry {
+    pub pass_id: String,
+    /// Days since the last modification
+    pub modified_days: u64,
+    /// Days since the last use, `None` if it was never used
+    pub used_days: Option<u64>,
+}
+
+/// Lists stale entries for archiving or deleting them in one go, and warns
+/// about files and folders with more permissions than the umask of `pass`
+/// allows.
+#[derive(Debug, Default, Clone)]
+pub struct AuditPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    entries: Vec<StaleEntry>,
+    marked: Vec<bool>,
+    selected: usize,
+    offset: usize,
+    period_days: u64,
+    archive_folder: String,
+    /// Files and folders with permission bits the umask would remove
+    permissive: Vec<String>,
+    umask: u32,
+    archive_button: Button<'a>,
+    delete_button: Button<'a>,
+    fix_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl AuditPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        AuditPopup {
+            area: None,
+            list_area: None,
+            theme,
+            entries: Vec::new(),
+            marked: Vec::new(),
+            selected: 0,
+            offset: 0,
+            period_days: 0,
+            archive_folder: String::new(),
+            permissive: Vec::new(),
+            umask: 0,
+            archive_button: Button::new("Archive".fg(theme.button_label))
+                .keyboard_label("(A)".fg(theme.button_keyboard_label))
+                .dimensions(15, 3)
+                .padded()
+                .action_on_click(Action::Audit(AuditAction::Archive)),
+            delete_button: Button::new("Delete".fg(theme.button_label))
+                .keyboard_label("(D)".fg(theme.button_keyboard_label))
+                .dimensions(14, 3)
+                .padded()
+                .action_on_click(Action::Audit(AuditAction::Delete)),
+            fix_button: Button::new("Permissions".fg(theme.button_label))
+                .keyboard_label("(P)".fg(theme.button_keyboard_label))
+                .dimensions(19, 3)
+                .padded()
+                .action_on_click(Action::Audit(AuditAction::FixPermissions)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
+        }
+    }
+
+    pub fn set_entries(
+        &mut self,
+        entries: Vec<StaleEntry>,
+        period_days: u64,
+        archive_folder: &str,
+    ) {
+        self.marked = vec![false; entries.len()];
+        self.entries = entries;
+        self.selected = 0;
+        self.offset = 0;
+        self.period_days = period_days;
+        self.archive_folder = archive_folder.to_string();
+    }
+
+    /// Removes entries that were archived or deleted.
+    pub fn remove_entries(&mut self, pass_ids: &[String]) {
+        let (entries, marked) = self
+            .entries
+            .drain(..)
+            .zip(self.marked.drain(..))
+            .filter(|(entry, _)| !pass_ids.contains(&entry.pass_id))
+            .unzip();
+        self.entries = entries;
+        self.marked = marked;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn toggle_mark(&mut self) {
+        if let Some(marked) = self.marked.get_mut(self.selected) {
+            *marked = !*marked;
+        }
+    }
+
+    /// Marks all entries, or none if all are marked already.
+    pub fn toggle_mark_all(&mut self) {
+        let mark = !self.marked.iter().all(|marked| *marked);
+        self.marked.fill(mark);
+    }
+
+    /// Returns the marked entries, or the selected entry if none is marked.
+    pub fn marked_pass_ids(&self) -> Vec<String> {
+        let marked: Vec<String> = self
+            .entries
+            .iter()
+            .zip(&self.marked)
+            .filter(|(_, marked)| **marked)
+            .map(|(entry, _)| entry.pass_id.clone())
+            .collect();
+        if marked.is_empty() {
+            self.entries
+                .get(self.selected)
+                .map(|entry| vec![entry.pass_id.clone()])
+                .unwrap_or_default()
+        } else {
+            marked
+        }
+    }
+
+    pub fn archive_folder(&self) -> &str {
+        &self.archive_folder
+    }
+
+    pub fn set_permissive(&mut self, permissive: Vec<String>, umask: u32) {
+        self.permissive = permissive;
+        self.umask = umask;
+    }
+
+    pub fn permissive(&self) -> &[String] {
+        &self.permissive
+    }
+}
+
+impl Widget for &mut AuditPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(Line::from("Stale entries").fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let mut summary = vec![Line::from(format!(
+            "Not used and not modified for more than {} days ({} entries)",
+            self.period_days,
+            self.entries.len()
+        ))];
+        if let Some(first) = self.permissive.first() {
+            summary.push(
+                Line::from(format!(
+                    "⚠ {} more open than the umask {:03o} allows, e.g. {first}",
+                    store::count_paths(&self.permissive),
+                    self.umask
+                ))
+                .fg(theme.table_aged_marker_fg),
+            );
+        }
+        Paragraph::new(summary)
+            .style(Style::new().fg(theme.debug))
+            .render(layout[0], buf);
+
+        // Keep the selected entry visible
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        let lines: Vec<Line> = if self.entries.is_empty() {
+            vec![Line::from("No stale entries".fg(theme.details_hint_fg))]
+        } else {
+            self.entries
+                .iter()
+                .zip(&self.marked)
+                .enumerate()
+                .skip(self.offset)
+                .take(height)
+                .map(|(i, (entry, marked))| {
+                    let used = match entry.used_days {
+                        Some(days) => format!("used {days} days ago"),
+                        None => "never used".to_string(),
+                    };
+                    let line = Line::from(vec![
+                        if *marked { "[x] " } else { "[ ] " }.into(),
+                        entry.pass_id.clone().into(),
+                        format!("  modified {} days ago, {used}", entry.modified_days)
+                            .fg(theme.details_hint_fg),
+                    ]);
+                    if i == self.selected {
+                        line.reversed()
+                    } else {
+                        line
+                    }
+                })
+                .collect()
+        };
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        let hint = Line::from(format!(
+            "(␣) Mark  (*) Mark all  (A) Move to {}/  (D) Delete  (P) Fix permissions",
+            self.archive_folder
+        ));
+        Paragraph::new(hint)
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+
+        let [archive_area, delete_area, fix_area, close_area] = Layout::horizontal([
+            Constraint::Length(15),
+            Constraint::Length(14),
+            Constraint::Length(19),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[3]);
+        self.archive_button.render(archive_area, buf);
+        self.delete_button.render(delete_area, buf);
+        self.fix_button.render(fix_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for AuditPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.archive_button,
+            &mut self.delete_button,
+            &mut self.fix_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for AuditPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = self.offset + (position.y - area.y) as usize;
+                if index < self.entries.len() {
+                    self.selected = index;
+                    return Some(Action::Audit(AuditAction::ToggleMark));
+                }
+            }
+        }
+        self.archive_button
+            .handle_mouse_event(event)
+            .or_else(|| self.delete_button.handle_mouse_event(event))
+            .or_else(|| self.fix_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
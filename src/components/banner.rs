@@ -0,0 +1,69 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Position, Rect},
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, NavigationAction},
+    components::MouseSupport,
+    theme::Theme,
+};
+
+const DISMISS_LABEL: &str = " ✕ (Esc) ";
+
+/// A warning above the menu that stays until it is dismissed with (Esc) or
+/// by clicking it.
+#[derive(Debug, Default, Clone)]
+pub struct Banner {
+    area: Option<Rect>,
+    text: String,
+    theme: Theme,
+}
+
+impl Banner {
+    pub fn new(text: String) -> Self {
+        Self {
+            area: None,
+            text,
+            theme: Theme::new(),
+        }
+    }
+}
+
+impl Widget for &mut Banner {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let style = Style::new()
+            .fg(self.theme.banner_fg)
+            .bg(self.theme.banner_bg);
+        Paragraph::new(Line::from(format!(" ⚠ {}", self.text)))
+            .style(style)
+            .render(area, buf);
+        let width = (DISMISS_LABEL.chars().count() as u16).min(area.width);
+        let dismiss_area = Rect {
+            x: area.right() - width,
+            width,
+            ..area
+        };
+        Line::from(DISMISS_LABEL.bold())
+            .style(style)
+            .render(dismiss_area, buf);
+    }
+}
+
+impl MouseSupport for Banner {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let area = self.area?;
+        (event.kind == MouseEventKind::Down(MouseButton::Left)
+            && area.contains(Position::new(event.column, event.row)))
+        .then_some(Action::Navigation(NavigationAction::DismissBanner))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -118,6 +118,11 @@ impl<'a> Button<'a> {
         }
     }
 
+    /// Returns the action of the button when it is pressed with a key.
+    pub fn press(&self) -> Option<Action> {
+        self.mouse_action.clone()
+    }
+
     pub fn inner_area(&self) -> Option<Rect> {
         self.inner_area
     }
@@ -0,0 +1,178 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, NavigationAction, PasswordAction},
+    components::{Button, FocusSupport, MouseSupport},
+    config::EntryCommand,
+    theme::Theme,
+};
+
+/// Lists the configured commands that get the password of an entry on stdin.
+#[derive(Debug, Default, Clone)]
+pub struct CommandsPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    pass_id: Option<String>,
+    commands: Vec<EntryCommand>,
+    selected: usize,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl CommandsPopup<'_> {
+    pub fn new(commands: Vec<EntryCommand>) -> Self {
+        let theme = Theme::new();
+        CommandsPopup {
+            area: None,
+            list_area: None,
+            theme,
+            pass_id: None,
+            commands,
+            selected: 0,
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
+        }
+    }
+
+    pub fn set_pass_id(&mut self, pass_id: &str) {
+        self.pass_id = Some(pass_id.to_string());
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.commands.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.commands.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn selected_command(&self) -> Option<&EntryCommand> {
+        self.commands.get(self.selected)
+    }
+}
+
+impl Widget for &mut CommandsPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(Line::from("Run command").fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        if let Some(pass_id) = self.pass_id.clone() {
+            Paragraph::new(Line::from(vec![
+                "Password file ID: ".fg(theme.debug),
+                pass_id.into(),
+            ]))
+            .style(Style::new().fg(theme.standard_fg))
+            .render(layout[0], buf);
+        }
+
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let lines: Vec<Line> = if self.commands.is_empty() {
+            vec![Line::from(
+                "No commands configured".fg(theme.details_hint_fg),
+            )]
+        } else {
+            self.commands
+                .iter()
+                .enumerate()
+                .take(list_area.height as usize)
+                .map(|(i, command)| {
+                    let line = Line::from(vec![
+                        format!("{:>2}. {}  ", i + 1, command.name).into(),
+                        command.args.join(" ").fg(theme.details_hint_fg),
+                    ]);
+                    if i == self.selected {
+                        line.reversed()
+                    } else {
+                        line
+                    }
+                })
+                .collect()
+        };
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        Paragraph::new(Line::from(
+            "(↓) (↑) (1-9) Select  (↵) Run with the password on stdin",
+        ))
+        .style(Style::new().fg(theme.details_hint_fg))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+
+        let [button_area] = Layout::horizontal([Constraint::Length(13)])
+            .flex(Flex::Center)
+            .areas(layout[3]);
+        self.close_button.render(button_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for CommandsPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.close_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for CommandsPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = (position.y - area.y) as usize;
+                if index < self.commands.len() {
+                    self.selected = index;
+                    return Some(Action::Password(PasswordAction::RunCommand));
+                }
+            }
+        }
+        self.close_button
+            .handle_mouse_event(event)
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -0,0 +1,238 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, ConflictAction, NavigationAction},
+    components::{Button, FocusSupport, MouseSupport},
+    theme::Theme,
+};
+
+/// A file of the store that git could not merge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// Path relative to the store
+    pub path: String,
+    /// The file exists in the local version
+    pub local: bool,
+    /// The file exists in the version pulled from the remote
+    pub remote: bool,
+}
+
+impl Conflict {
+    /// Returns the ID of the entry, `None` for other files of the store
+    /// like `.gpg-id`.
+    pub fn pass_id(&self) -> Option<&str> {
+        self.path
+            .strip_suffix(".gpg")
+            .filter(|pass_id| !pass_id.is_empty() && !pass_id.ends_with('/'))
+    }
+}
+
+/// Lists the conflicts a pull left in the store for resolving them one by
+/// one.
+#[derive(Debug, Default, Clone)]
+pub struct ConflictsPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    conflicts: Vec<Conflict>,
+    selected: usize,
+    offset: usize,
+    local_button: Button<'a>,
+    remote_button: Button<'a>,
+    edit_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl ConflictsPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        ConflictsPopup {
+            theme,
+            local_button: Button::new("Keep local".fg(theme.button_label))
+                .keyboard_label("(l)".fg(theme.button_keyboard_label))
+                .dimensions(18, 3)
+                .padded()
+                .action_on_click(Action::Conflict(ConflictAction::KeepLocal)),
+            remote_button: Button::new("Keep remote".fg(theme.button_label))
+                .keyboard_label("(r)".fg(theme.button_keyboard_label))
+                .dimensions(19, 3)
+                .padded()
+                .action_on_click(Action::Conflict(ConflictAction::KeepRemote)),
+            edit_button: Button::new("Edit".fg(theme.button_label))
+                .keyboard_label("(e)".fg(theme.button_keyboard_label))
+                .dimensions(12, 3)
+                .padded()
+                .action_on_click(Action::Conflict(ConflictAction::Edit)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_conflicts(&mut self, conflicts: Vec<Conflict>) {
+        self.conflicts = conflicts;
+        self.selected = self.selected.min(self.conflicts.len().saturating_sub(1));
+    }
+
+    pub fn selected_conflict(&self) -> Option<&Conflict> {
+        self.conflicts.get(self.selected)
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.conflicts.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl Widget for &mut ConflictsPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from("Merge conflicts")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        Paragraph::new(Line::from(format!(
+            "Pulling changed these files both locally and on the remote ({} left)",
+            self.conflicts.len()
+        )))
+        .style(Style::new().fg(theme.debug))
+        .render(layout[0], buf);
+
+        // Keep the selected conflict visible
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        let lines: Vec<Line> = self
+            .conflicts
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(height)
+            .map(|(i, conflict)| {
+                let state = match (conflict.local, conflict.remote) {
+                    (true, true) => "changed on both sides",
+                    (false, _) => "deleted locally, changed on the remote",
+                    (_, false) => "changed locally, deleted on the remote",
+                };
+                let line = Line::from(vec![
+                    conflict
+                        .pass_id()
+                        .unwrap_or(&conflict.path)
+                        .to_string()
+                        .into(),
+                    format!("  {state}").fg(theme.details_hint_fg),
+                ]);
+                if i == self.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        let hint = Line::from("(e) Opens the local version with `pass edit`");
+        Paragraph::new(hint)
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+
+        let [local_area, remote_area, edit_area, close_area] = Layout::horizontal([
+            Constraint::Length(18),
+            Constraint::Length(19),
+            Constraint::Length(12),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[3]);
+        self.local_button.render(local_area, buf);
+        self.remote_button.render(remote_area, buf);
+        self.edit_button.render(edit_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for ConflictsPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.local_button,
+            &mut self.remote_button,
+            &mut self.edit_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for ConflictsPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = self.offset + (position.y - area.y) as usize;
+                if index < self.conflicts.len() {
+                    self.selected = index;
+                    return Some(Action::NoOp);
+                }
+            }
+        }
+        self.local_button
+            .handle_mouse_event(event)
+            .or_else(|| self.remote_button.handle_mouse_event(event))
+            .or_else(|| self.edit_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -4,25 +4,87 @@ use futures::{
     channel::oneshot,
     executor::{block_on, ThreadPool},
 };
+use notify::RecommendedWatcher;
 use passepartout::{PasswordInfo, PasswordStore};
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::MouseEvent,
-    layout::{Constraint, Direction, Layout, Margin, Rect},
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Constraint, Layout, Margin, Rect},
     widgets::Widget,
 };
-use std::sync::mpsc::Sender;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs::File,
+    future::Future,
+    hash::{DefaultHasher, Hash, Hasher},
+    mem,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender, TryRecvError},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use totp_rs::TOTP;
+use zeroize::Zeroize;
 
 use crate::{
-    actions::{Action, NavigationAction, PasswordAction, SearchAction},
-    app::{self, MainState, OverlayState, SearchState},
+    action_log::ActionLog,
+    actions::{
+        Action, Audit, AuditAction, ConflictAction, DialogAction, DuplicatesAction, FormAction,
+        HistoryAction, NavigationAction, OtpAuditAction, PasswordAction, ScanAction, SearchAction,
+        StoreAction, StrengthAction, TableAction,
+    },
+    alias,
+    app::{self, MainState, OverlayState, SearchState, StartupError},
+    backend::Backend,
+    browser,
+    cache::DetailsCache,
+    clipboard::Content,
+    clock::Moment,
+    command, completion,
     components::{
-        Component, FilePopup, HelpPopup, Menu, MouseSupport, PasswordDetails, PasswordTable,
-        SearchField, StatusBar,
+        AuditPopup, Banner, CommandsPopup, Component, Conflict, ConflictsPopup, DateFormat, Dialog,
+        DialogPopup, DuplicatesPopup, FilePopup, FocusSupport, GeneratorPopup, HelpPopup,
+        HistoryPopup, InsertPopup, Menu, MouseSupport, OtpAuditPopup, PasswordDetails,
+        PasswordTable, PromptPopup, PromptPurpose, QrPopup, RecoveryCodesPopup, SearchField,
+        Segment, SessionLogPopup, SortColumn, StaleEntry, StatusBar, StrengthPopup,
     },
-    event::PasswordEvent,
+    config::{Config, DetailsPosition, EntryCommand, OtpAuto},
+    data, diff,
+    entry::{self, Field},
+    event::{Finding, PasswordEvent},
+    generator, git, gpg, matcher, pass,
+    remembered::Remembered,
+    secret::Secret,
+    session, status,
+    store::{self, Excludes, StoreSnapshot},
+    strength,
+    usage::Usage,
+    watch,
 };
 
+const PINNED_FILE: &str = "pinned";
+const LISTING_EXTENSION: &str = "listing";
+const USAGE_EXTENSION: &str = "usage";
+const LOGINS_EXTENSION: &str = "logins";
+const HOSTS_EXTENSION: &str = "hosts";
+const ALIASES_EXTENSION: &str = "aliases";
+const ACTION_LOG_EXTENSION: &str = "log";
+const METADATA_TIMEOUT: Duration = Duration::from_secs(1);
+const STARTUP_SCAN_TIMEOUT: Duration = Duration::from_secs(2);
+const STALE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// Lines of the table, including the header, kept when the details pane
+/// is shown on a small terminal
+const MIN_TABLE_HEIGHT: u16 = 4;
+/// Columns of the table kept next to the details sidebar
+const MIN_TABLE_WIDTH: u16 = 40;
+/// Columns the sidebar grows or shrinks by at once
+const SIDEBAR_STEP: u16 = 4;
+
 #[derive(Default)]
 struct LastOperation {
     pass_id: String,
@@ -61,57 +123,1037 @@ impl LastOperation {
     }
 }
 
+/// Pulls changes from the git remote of the store with `pass git pull`.
+fn git_pull(backend: &dyn Backend, rebase: bool) -> Result<String, passepartout::Error> {
+    let output = if rebase {
+        backend.git(&["pull", "--rebase"])?
+    } else {
+        backend.git(&["pull"])?
+    };
+    Ok(
+        match output.lines().map(str::trim).rfind(|line| !line.is_empty()) {
+            Some(line) => format!("Pulled from remote: {line}"),
+            None => "Pulled from remote".to_string(),
+        },
+    )
+}
+
+/// Returns the number of local commits that are not on the upstream branch,
+/// zero if there is no upstream branch.
+fn unpushed_commits(backend: &dyn Backend) -> usize {
+    backend
+        .git(&["rev-list", "--count", "@{upstream}..HEAD"])
+        .ok()
+        .and_then(|output| output.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Returns true if git is rebasing the local commits of the store onto the
+/// remote ones, e.g. after `git pull --rebase`.
+fn rebasing(backend: &dyn Backend) -> bool {
+    backend
+        .git(&["rev-parse", "--quiet", "--verify", "REBASE_HEAD"])
+        .is_ok()
+}
+
+/// Returns the files of the store that are left to merge.
+fn list_conflicts(backend: &dyn Backend) -> Result<Vec<Conflict>, passepartout::Error> {
+    let output = backend.git(&["ls-files", "--unmerged", "-z"])?;
+    // While rebasing the remote version is "ours" and the local one "theirs"
+    let (local_stage, remote_stage) = if rebasing(backend) {
+        ("3", "2")
+    } else {
+        ("2", "3")
+    };
+    let mut conflicts: Vec<Conflict> = Vec::new();
+    // Records of the form `<mode> <object> <stage>\t<path>`, sorted by path
+    for record in output.split('\0') {
+        let Some((info, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let stage = info.split(' ').nth(2).unwrap_or_default();
+        if conflicts
+            .last()
+            .is_none_or(|conflict| conflict.path != path)
+        {
+            conflicts.push(Conflict {
+                path: path.to_string(),
+                local: false,
+                remote: false,
+            });
+        }
+        if let Some(conflict) = conflicts.last_mut() {
+            conflict.local |= stage == local_stage;
+            conflict.remote |= stage == remote_stage;
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Resolves a conflict by keeping the local or the remote version, the
+/// merge is completed once no conflicts are left.
+///
+/// Returns the conflicts that are left and a message if the merge was
+/// completed.
+fn resolve_conflict(
+    backend: &dyn Backend,
+    resolution: Option<(&Conflict, bool)>,
+) -> Result<(Vec<Conflict>, Option<String>), passepartout::Error> {
+    if let Some((conflict, keep_local)) = resolution {
+        let rebasing = rebasing(backend);
+        let kept = if keep_local {
+            conflict.local
+        } else {
+            conflict.remote
+        };
+        if kept {
+            let side = if keep_local != rebasing {
+                "--ours"
+            } else {
+                "--theirs"
+            };
+            backend.git(&["checkout", side, "--", &conflict.path])?;
+            backend.git(&["add", "--", &conflict.path])?;
+        } else {
+            backend.git(&["rm", "--quiet", "--", &conflict.path])?;
+        }
+    }
+    let conflicts = list_conflicts(backend)?;
+    if !conflicts.is_empty() {
+        return Ok((conflicts, None));
+    }
+    if rebasing(backend) {
+        if let Err(e) = backend.git(&["-c", "core.editor=true", "rebase", "--continue"]) {
+            // The next commit of the rebase may conflict as well
+            let conflicts = list_conflicts(backend)?;
+            return if conflicts.is_empty() {
+                Err(e)
+            } else {
+                Ok((conflicts, None))
+            };
+        }
+        Ok((
+            conflicts,
+            Some("Resolved the conflicts, rebase completed".to_string()),
+        ))
+    } else if backend
+        .git(&["rev-parse", "--quiet", "--verify", "MERGE_HEAD"])
+        .is_ok()
+    {
+        backend.git(&["commit", "--no-edit"])?;
+        Ok((
+            conflicts,
+            Some("Resolved the conflicts, merge completed".to_string()),
+        ))
+    } else {
+        // Editing the last conflicting entry committed the merge already
+        Ok((conflicts, Some("Resolved the conflicts".to_string())))
+    }
+}
+
+/// Returns the status message for a secret that was copied to the clipboard.
+fn copied_message(what: &str, clear_after: Duration) -> String {
+    if clear_after.is_zero() {
+        format!("{what} copied to clipboard")
+    } else {
+        format!(
+            "{what} copied to clipboard, clears after {} seconds",
+            clear_after.as_secs()
+        )
+    }
+}
+
+/// Returns the branch of the store and the number of uncommitted changes.
+fn git_status(backend: &dyn Backend) -> Result<String, passepartout::Error> {
+    let output = backend.git(&["status", "--porcelain", "--branch"])?;
+    let mut lines = output.lines();
+    let branch = lines
+        .next()
+        .and_then(|line| line.strip_prefix("## "))
+        .unwrap_or("no branch");
+    let changes = match lines.filter(|line| !line.is_empty()).count() {
+        0 => "no uncommitted changes".to_string(),
+        1 => "1 uncommitted change".to_string(),
+        count => format!("{count} uncommitted changes"),
+    };
+    Ok(format!("Git: {branch}, {changes}"))
+}
+
+/// Decryption of entries one after the other in the background, see
+/// [`scan_entries`].
+#[derive(Debug, Clone)]
+struct EntryScan {
+    id: u64,
+    /// Set to stop the background decryption
+    cancelled: Arc<AtomicBool>,
+}
+
+impl EntryScan {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Search for a pattern inside the decrypted password files, like
+/// `pass grep`.
+#[derive(Debug)]
+struct ContentSearch {
+    scan: EntryScan,
+    /// Entries whose contents matched so far
+    matches: Vec<String>,
+    searched: usize,
+    total: usize,
+}
+
+/// Decryption of the entries the OTP audit, the search for duplicates or
+/// the strength audit could not check from the cache.
+#[derive(Debug)]
+struct EntryCheck {
+    scan: EntryScan,
+    checked: usize,
+    total: usize,
+}
+
+/// Decrypts the password files one after the other and reports what
+/// `inspect` finds out about each, stops at the first error. Aliases are
+/// resolved to the contents of their target with `follow_aliases`.
+fn scan_entries(
+    backend: &dyn Backend,
+    store_dir: &Path,
+    scan: &EntryScan,
+    pass_ids: Vec<String>,
+    follow_aliases: bool,
+    event_tx: &Sender<PasswordEvent>,
+    inspect: impl Fn(&str) -> Finding,
+) {
+    let scan_id = scan.id;
+    for pass_id in pass_ids {
+        if scan.cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        let file_path = store_dir.join(format!("{}.gpg", pass_id));
+        let file_contents = if follow_aliases {
+            alias::decrypt(backend, &file_path)
+        } else {
+            backend.decrypt(&file_path)
+        };
+        let event = match file_contents {
+            Ok(mut file_contents) => {
+                let finding = inspect(&file_contents);
+                file_contents.zeroize();
+                PasswordEvent::EntryScanned {
+                    scan_id,
+                    pass_id,
+                    finding,
+                }
+            }
+            Err(error) => PasswordEvent::EntryScanFailed { scan_id, error },
+        };
+        let failed = matches!(event, PasswordEvent::EntryScanFailed { .. });
+        event_tx.send(event).expect("receiver deallocated");
+        if failed {
+            return;
+        }
+    }
+}
+
+/// Rates the password of a password file, `None` for strong passwords,
+/// aliases and files without a password.
+fn password_weakness(file_contents: &str) -> Option<strength::Weakness> {
+    if alias::target(file_contents).is_some() {
+        return None;
+    }
+    let password = file_contents
+        .lines()
+        .next()
+        .filter(|password| !password.is_empty())?;
+    strength::weakness(password)
+}
+
+/// Returns a hash of the password and the login of a password file for
+/// finding duplicates without keeping the secrets, `None` for aliases and
+/// files without a password.
+fn credential(file_contents: &str) -> Option<u64> {
+    if alias::target(file_contents).is_some() {
+        return None;
+    }
+    let mut lines = file_contents.lines();
+    let password = lines.next().filter(|password| !password.is_empty())?;
+    let login = lines.next().unwrap_or_default().trim();
+    let mut hasher = DefaultHasher::new();
+    (password, login).hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Replaces the password files with aliases of the target, returns the
+/// entries that were replaced before an error.
+fn alias_entries(
+    backend: &dyn Backend,
+    target: &str,
+    pass_ids: Vec<String>,
+) -> (Vec<String>, Option<passepartout::Error>) {
+    let mut aliased = Vec::new();
+    for pass_id in pass_ids {
+        if let Err(e) = backend.overwrite(&pass_id, &format!("alias: {target}\n")) {
+            return (aliased, Some(e));
+        }
+        aliased.push(pass_id);
+    }
+    (aliased, None)
+}
+
+/// Returns the name of a data file belonging to a password store, one per
+/// store directory.
+///
+/// The path is hashed with FNV-1a, unlike `DefaultHasher` it gives the same
+/// name with every build, otherwise the data would be lost after updating
+/// Rust and two builds wouldn't share the lock of a store.
+fn store_file_name(store_dir: &Path, extension: &str) -> String {
+    let hash = store_dir
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("store-{hash:016x}.{extension}")
+}
+
+/// Returns the listing cached by the last run, the metadata of the entries
+/// is a placeholder until the store has been scanned.
+fn cached_passwords(
+    store_dir: &Path,
+    data_dir: Option<&Path>,
+    excludes: &Excludes,
+) -> Option<Vec<PasswordInfo>> {
+    let mut ids = data::read_lines(data_dir, &store_file_name(store_dir, LISTING_EXTENSION));
+    // The excludes may have changed since the listing was written
+    ids.retain(|id| !excludes.matches(id));
+    if ids.is_empty() {
+        return None;
+    }
+    // Placeholder metadata for the entries
+    let dir = store_dir.to_path_buf();
+    let metadata = store::with_timeout(METADATA_TIMEOUT, move || dir.metadata())?.ok()?;
+    let passwords = ids
+        .into_iter()
+        .map(|id| PasswordInfo::new(id, metadata.clone()))
+        .collect();
+    Some(passwords)
+}
+
+/// Returns a line of a password file, the password is on the first line and
+/// the login on the second.
+fn file_line(
+    backend: &dyn Backend,
+    file_path: &Path,
+    index: usize,
+    name: &str,
+) -> Result<String, passepartout::Error> {
+    let file_contents = alias::decrypt(backend, file_path)?;
+    file_contents
+        .lines()
+        .nth(index)
+        .map(String::from)
+        .ok_or_else(|| passepartout::Error::Pass(format!("no {name} found")))
+}
+
+/// Opens the URL of a password file in the browser and optionally copies
+/// the login beforehand, returning a status message.
+fn open_entry_url(
+    backend: &dyn Backend,
+    file_path: &Path,
+    with_login: bool,
+) -> Result<String, passepartout::Error> {
+    let file_contents = alias::decrypt(backend, file_path)?;
+    let url = browser::find_url(&file_contents)
+        .ok_or_else(|| passepartout::Error::Pass("no URL found".to_string()))?;
+    if with_login {
+        let login = file_contents
+            .lines()
+            .nth(1)
+            .ok_or_else(|| passepartout::Error::Pass("no login found".to_string()))?;
+        backend.copy(login, Content::Login)?;
+    }
+    browser::open(&url)?;
+    if with_login {
+        Ok("Login on clipboard, password next with (y)".to_string())
+    } else {
+        Ok(format!("Opened {url}"))
+    }
+}
+
+/// Runs a configured command with the password of an entry on stdin,
+/// returning a status message.
+fn run_entry_command(
+    backend: &dyn Backend,
+    file_path: &Path,
+    pass_id: &str,
+    entry_command: &EntryCommand,
+) -> Result<String, passepartout::Error> {
+    let file_contents = alias::decrypt(backend, file_path)?;
+    let args = command::fill_args(&entry_command.args, pass_id, &file_contents)?;
+    let password = file_contents.lines().next().unwrap_or_default();
+    backend.run_command(&args, &format!("{password}\n"))?;
+    Ok(format!("Ran {} for {pass_id}", entry_command.name))
+}
+
+/// Runs the export command with the `otpauth://` URI with the given index of
+/// a password file on stdin, returning a status message.
+fn export_otp(
+    backend: &dyn Backend,
+    file_path: &Path,
+    pass_id: &str,
+    otp_index: usize,
+    args: &[String],
+) -> Result<String, passepartout::Error> {
+    let file_contents = alias::decrypt(backend, file_path)?;
+    let otp_uri = entry::otp_uris(&file_contents)
+        .into_iter()
+        .nth(otp_index)
+        .ok_or_else(|| passepartout::Error::Pass("one-time password not found".to_string()))?;
+    let args = command::fill_args(args, pass_id, &file_contents)?;
+    backend.run_command(&args, &format!("{}\n", otp_uri.uri))?;
+    Ok(format!(
+        "Exported the one-time password of {pass_id} with {}",
+        args[0]
+    ))
+}
+
+/// Decrypts two versions of a password file from the git history of the
+/// store, a missing older version is empty.
+fn decrypt_versions(
+    backend: &dyn Backend,
+    file_path: &Path,
+    old: Option<&str>,
+    new: &str,
+) -> Result<(String, String), passepartout::Error> {
+    let old = match old {
+        Some(revision) => backend.decrypt_revision(file_path, revision)?,
+        None => String::new(),
+    };
+    Ok((old, backend.decrypt_revision(file_path, new)?))
+}
+
+/// Generates a one-time password (OTP) from the `otpauth://` URI with the
+/// given index in a password file.
+fn generate_otp(
+    backend: &dyn Backend,
+    file_path: &Path,
+    index: usize,
+) -> Result<String, passepartout::Error> {
+    generate_otp_with_expiry(backend, file_path, index).map(|(otp, _, _)| otp)
+}
+
+/// Generates a one-time password, returns it together with the end of the
+/// time step it is valid for and the length of the step.
+fn generate_otp_with_expiry(
+    backend: &dyn Backend,
+    file_path: &Path,
+    index: usize,
+) -> Result<(String, SystemTime, Duration), passepartout::Error> {
+    let file_contents = alias::decrypt(backend, file_path)?;
+    let otp_uri = entry::otp_uris(&file_contents)
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| passepartout::Error::Pass("no OTP URL found".to_string()))?;
+    let totp = TOTP::from_url(&otp_uri.uri)?;
+    let time = backend
+        .clock()
+        .now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| passepartout::Error::Pass(format!("failed to generate OTP: {e}")))?;
+    let step = totp.step.max(1);
+    let valid_until = UNIX_EPOCH + Duration::from_secs((time.as_secs() / step + 1) * step);
+    Ok((
+        totp.generate(time.as_secs()),
+        valid_until,
+        Duration::from_secs(step),
+    ))
+}
+
+/// Copies the recovery code on the given line of a password file and marks
+/// it as used by rewriting the file, returning the new file contents and
+/// the entry an alias stands in for.
+fn use_recovery_code(
+    backend: &dyn Backend,
+    pass_id: &str,
+    file_path: &Path,
+    line: usize,
+) -> Result<(String, Option<String>), passepartout::Error> {
+    let (file_contents, target) = alias::resolve(backend, file_path)?;
+    let code = entry::recovery_codes(&file_contents)
+        .into_iter()
+        .find(|code| code.line == line)
+        .ok_or_else(|| passepartout::Error::Pass("recovery code not found".to_string()))?;
+    let marked = entry::mark_recovery_code_used(&file_contents, line)
+        .ok_or_else(|| passepartout::Error::Pass("recovery code already used".to_string()))?;
+    backend.copy(&code.code, Content::RecoveryCode)?;
+    // An alias has the code marked in the entry it stands in for
+    backend.overwrite(target.as_deref().unwrap_or(pass_id), &marked)?;
+    Ok((marked, target))
+}
+
+/// Returns why the path of a new entry is not valid.
+fn invalid_pass_id(pass_id: &str) -> Option<&'static str> {
+    if pass_id.is_empty() {
+        Some("Enter a path for the new entry")
+    } else if pass_id.starts_with('/') || pass_id.ends_with('/') {
+        Some("The path must not start or end with a slash")
+    } else if pass_id
+        .split('/')
+        .any(|part| part.is_empty() || part == "." || part == "..")
+    {
+        Some("The path contains an empty folder or a relative part")
+    } else {
+        None
+    }
+}
+
+/// Returns why the new path of an entry can not be used.
+fn validate_new_path(path: &str, paths: &[String]) -> Option<String> {
+    if let Some(message) = invalid_pass_id(path) {
+        Some(message.to_string())
+    } else if paths.iter().any(|existing| existing == path) {
+        Some(format!("{path} already exists"))
+    } else if paths.contains(&format!("{path}/")) {
+        // `pass mv` would move the entry into the folder
+        Some(format!("{path}/ is a folder"))
+    } else {
+        None
+    }
+}
+
+/// Returns why a folder can not be created at the path.
+fn validate_folder(path: &str, paths: &[String]) -> Option<String> {
+    let folder = path.strip_suffix('/').unwrap_or(path);
+    if folder.is_empty() {
+        Some("Enter a name for the folder".to_string())
+    } else if let Some(message) = invalid_pass_id(folder) {
+        Some(message.to_string())
+    } else if paths
+        .iter()
+        .any(|existing| *existing == format!("{folder}/"))
+    {
+        Some(format!("{folder}/ already exists"))
+    } else {
+        None
+    }
+}
+
+/// Returns why the entry for the URL can not be looked up.
+fn validate_url(url: &str, _paths: &[String]) -> Option<String> {
+    if url.is_empty() {
+        Some("Enter the URL of the site".to_string())
+    } else if browser::host(url).is_none() {
+        Some(format!("No host found in {url}"))
+    } else {
+        None
+    }
+}
+
+/// Returns why the session log can not be exported to the file.
+fn validate_export_path(path: &str, _paths: &[String]) -> Option<String> {
+    if path.is_empty() {
+        Some("Enter the path of the file".to_string())
+    } else if Path::new(path).is_dir() {
+        Some(format!("{path} is a directory"))
+    } else {
+        None
+    }
+}
+
+/// Inserts a new password file, an existing file is never replaced.
+fn insert_entry(
+    backend: &dyn Backend,
+    pass_id: &str,
+    file_path: &Path,
+    file_contents: &str,
+) -> Result<(), passepartout::Error> {
+    if backend.modified(file_path).is_some() {
+        return Err(passepartout::Error::Pass(format!(
+            "{pass_id} already exists"
+        )));
+    }
+    backend.insert(pass_id, file_contents)
+}
+
+/// Generates a new password file, an existing file is never replaced.
+fn generate_entry(
+    backend: &dyn Backend,
+    pass_id: &str,
+    file_path: &Path,
+    options: &generator::Options,
+) -> Result<(), passepartout::Error> {
+    if backend.modified(file_path).is_some() {
+        return Err(passepartout::Error::Pass(format!(
+            "{pass_id} already exists"
+        )));
+    }
+    backend.generate(pass_id, options)
+}
+
+/// Renames a password file, an existing file at the new path is never
+/// replaced and it is never moved into a folder of that name.
+fn move_entry(
+    backend: &dyn Backend,
+    from: &str,
+    to: &str,
+    file_path: &Path,
+) -> Result<(), passepartout::Error> {
+    if backend.modified(file_path).is_some() {
+        return Err(passepartout::Error::Pass(format!("{to} already exists")));
+    }
+    if file_path.with_extension("").is_dir() {
+        return Err(passepartout::Error::Pass(format!("{to}/ is a folder")));
+    }
+    backend.rename(from, to)
+}
+
+/// Moves entries to the archive folder or removes them if there is none,
+/// stops at the first error.
+fn clean_entries(
+    backend: &dyn Backend,
+    store_dir: &Path,
+    pass_ids: Vec<String>,
+    archive_folder: Option<&str>,
+) -> (
+    Vec<(String, String)>,
+    Vec<String>,
+    Option<passepartout::Error>,
+) {
+    let mut moved = Vec::new();
+    let mut removed = Vec::new();
+    for pass_id in pass_ids {
+        let result = match archive_folder {
+            Some(folder) => {
+                let to = format!("{folder}/{pass_id}");
+                let file_path = store_dir.join(format!("{}.gpg", to));
+                move_entry(backend, &pass_id, &to, &file_path).map(|()| moved.push((pass_id, to)))
+            }
+            None => backend.remove(&pass_id).map(|()| removed.push(pass_id)),
+        };
+        if let Err(e) = result {
+            return (moved, removed, Some(e));
+        }
+    }
+    (moved, removed, None)
+}
+
+/// Returns true for actions that use a secret of the selected entry.
+fn uses_secret(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::Navigation(NavigationAction::Secrets | NavigationAction::Qr)
+            | Action::History(HistoryAction::Diff | HistoryAction::Difftool)
+            | Action::Password(
+                PasswordAction::CopyPassword
+                    | PasswordAction::CopyDefault
+                    | PasswordAction::CopyLogin
+                    | PasswordAction::CopyOtp
+                    | PasswordAction::CopyFileLine
+                    | PasswordAction::CopyRecoveryCode
+                    | PasswordAction::UseRecoveryCode
+                    | PasswordAction::OpenUrlAndCopyLogin
+                    | PasswordAction::RunCommand
+                    | PasswordAction::ExportOtp
+            )
+    )
+}
+
+/// Returns what the action does with the selected entry for the action log,
+/// `None` if it is not logged.
+fn logged_action(action: &Action) -> Option<&'static str> {
+    let what = match action {
+        Action::Navigation(NavigationAction::Secrets) => "viewed",
+        Action::Navigation(NavigationAction::File) => "viewed file",
+        Action::Navigation(NavigationAction::Qr) => "showed QR codes",
+        Action::Navigation(NavigationAction::WifiQr) => "showed Wi-Fi QR code",
+        Action::Navigation(NavigationAction::RecoveryCodes) => "viewed recovery codes",
+        Action::History(HistoryAction::Diff | HistoryAction::Difftool) => "compared versions",
+        Action::Password(action) => match action {
+            PasswordAction::CopyPassword => "copied password",
+            PasswordAction::CopyDefault => "copied default field",
+            PasswordAction::CopyLogin => "copied login",
+            PasswordAction::CopyOtp => "copied one-time password",
+            PasswordAction::CopyField(_) => "copied field",
+            PasswordAction::CopyLine(_) => "copied line",
+            PasswordAction::CopyFileLine => "copied line",
+            PasswordAction::CopyRecoveryCode => "copied recovery code",
+            PasswordAction::UseRecoveryCode => "used recovery code",
+            PasswordAction::OpenUrl => "opened URL",
+            PasswordAction::OpenUrlAndCopyLogin => "opened URL and copied login",
+            PasswordAction::RunCommand => "ran command",
+            PasswordAction::ExportOtp => "exported one-time password",
+            PasswordAction::ShowOtpUri => "showed one-time password URI",
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(what)
+}
+
+/// Copies a field of a password file to the clipboard, the one-time
+/// password of the URI at `otp_index`.
+fn copy_field(
+    backend: &dyn Backend,
+    file_path: &Path,
+    field: &Field,
+    otp_index: usize,
+) -> Result<(), passepartout::Error> {
+    let (value, content) = match field {
+        Field::Password => (
+            file_line(backend, file_path, 0, "password")?,
+            Content::Password,
+        ),
+        Field::Login => (file_line(backend, file_path, 1, "login")?, Content::Login),
+        Field::Otp => (generate_otp(backend, file_path, otp_index)?, Content::Otp),
+        Field::Key(key) => {
+            let file_contents = alias::decrypt(backend, file_path)?;
+            let value = entry::field_value(&file_contents, key)
+                .ok_or_else(|| passepartout::Error::Pass(format!("no {key} found")))?;
+            (value, Content::Field)
+        }
+        Field::Line(number) => (
+            file_line(backend, file_path, number - 1, &format!("line {number}"))?,
+            Content::Line,
+        ),
+    };
+    backend.copy(&value, content)
+}
+
 pub struct Dashboard<'a> {
     tty_pinentry: bool,
+    config: Config,
+    backend: Arc<dyn Backend>,
+    data_dir: Option<PathBuf>,
     store: PasswordStore,
+    snapshot: StoreSnapshot,
+    /// Result of a background scan of the store
+    pending_scan: Option<Receiver<store::Scan>>,
+    /// Entry that is selected once the next scan has been applied and the
+    /// status shown then
+    select_after_scan: Option<(String, String)>,
+    /// Entry whose secrets are shown once the pending scan has been applied,
+    /// it was not listed before
+    open_after_scan: Option<String>,
+    /// URL looked up again once the pending scan has been applied, no
+    /// entry matched before
+    lookup_after_scan: Option<String>,
+    /// The pending scan was started because the store changed outside of
+    /// passepartui and leaves the status alone
+    quiet_scan: bool,
+    /// Another scan was requested while one was pending, quietly if set
+    rescan: Option<bool>,
+    /// Keeps watching the store for changes while it exists
+    _watcher: Option<RecommendedWatcher>,
+    details_cache: DetailsCache,
+    /// A check whether the displayed entry was modified is running
+    stale_check_pending: bool,
+    last_stale_check: Option<Moment>,
+    /// Entry whose one-time password is copied or typed once it has been
+    /// generated, see `auto` under [otp]
+    otp_auto_pending: Option<String>,
+    /// Counted up when locking, so that decryptions still running then
+    /// don't bring back the secrets
+    lock_generation: u64,
+    pinned: Vec<String>,
+    /// When secrets of the entries were last used
+    usage: Usage,
+    /// Hosts of the URLs of decrypted entries for looking them up by URL
+    hosts: Remembered,
+    /// Entries the aliases among the decrypted entries stand in for
+    aliases: Remembered,
+    /// Entries that were viewed, copied or changed, if enabled
+    action_log: ActionLog,
+    /// Entries marked with a letter, kept for the session
+    marks: HashMap<char, String>,
+    /// Entry that was selected before the last jump
+    previous_selection: Option<String>,
+    /// Folder the table and the search are restricted to
+    scope: Option<String>,
+    /// Entries in the archive folder are listed outside of it as well
+    show_archive: bool,
+    /// Replaces the search by pass ID while active
+    content_search: Option<ContentSearch>,
+    /// Decrypts the entries listed as unchecked by the OTP audit
+    otp_check: Option<EntryCheck>,
+    /// Decrypts the entries listed as unchecked by the search for
+    /// duplicates
+    duplicate_check: Option<EntryCheck>,
+    /// Decrypts the entries listed as unchecked by the strength audit
+    strength_check: Option<EntryCheck>,
+    /// Scans started so far, the IDs tell the scan results belong to
+    scan_count: u64,
     area: Option<Rect>,
     password_subset: Vec<usize>,
+    /// Warning shown above the menu until it is dismissed
+    banner: Option<Banner>,
     menu: Menu<'a>,
     password_table: PasswordTable<'a>,
     password_details: PasswordDetails<'a>,
     search_field: SearchField,
+    /// The search was started by typing in the table, leaving it shows all
+    /// entries again instead of keeping the filter
+    typed_search: bool,
     help_popup: HelpPopup<'a>,
     file_popup: FilePopup<'a>,
+    recovery_codes_popup: RecoveryCodesPopup<'a>,
+    qr_popup: QrPopup<'a>,
+    commands_popup: CommandsPopup<'a>,
+    audit_popup: AuditPopup<'a>,
+    otp_audit_popup: OtpAuditPopup<'a>,
+    duplicates_popup: DuplicatesPopup<'a>,
+    strength_popup: StrengthPopup<'a>,
+    session_log_popup: SessionLogPopup<'a>,
+    history_popup: HistoryPopup<'a>,
+    conflicts_popup: ConflictsPopup<'a>,
+    dialog_popup: DialogPopup<'a>,
+    insert_popup: InsertPopup<'a>,
+    prompt_popup: PromptPopup<'a>,
+    generator_popup: GeneratorPopup<'a>,
     status_bar: StatusBar,
     pub app_state: app::State,
+    /// State in which the keyboard focus was last checked, it is dropped
+    /// when the state changes
+    focus_state: app::State,
     render_details: bool,
+    /// The secrets of the selected entry are fetched then, moving in the
+    /// secrets view postpones it
+    follow_deadline: Option<Moment>,
+    /// A thread waits for the deadline
+    follow_timer_pending: bool,
+    details_position: DetailsPosition,
+    /// Lines of the details pane or columns of the sidebar unless the
+    /// terminal is too small for them
+    details_height: u16,
+    details_width: u16,
+    details_collapsed: bool,
+    /// Where the details pane was last rendered, empty if it was not
+    details_area: Rect,
+    /// The border between the table and the details pane is dragged
+    dragging_border: bool,
     pool: ThreadPool,
     last_op: LastOperation,
     event_tx: Sender<PasswordEvent>,
+    /// Lock on the password store held while the app is running
+    _instance_lock: Option<File>,
+    /// Set if another instance holds the lock on the store
+    read_only: bool,
+    /// Set until unpushed commits were pushed when quitting
+    push_on_quit: bool,
+    /// Full message of the last error, it is discarded after it was shown
+    last_error: Option<String>,
 }
 
-impl Dashboard<'_> {
-    pub fn new(tty_pinentry: bool, event_tx: Sender<PasswordEvent>) -> Self {
-        let store = PasswordStore::new();
+impl<'a> Dashboard<'a> {
+    pub fn new(
+        tty_pinentry: bool,
+        initial_scan: bool,
+        config: Config,
+        backend: Arc<dyn Backend>,
+        event_tx: Sender<PasswordEvent>,
+    ) -> Result<Self, StartupError> {
+        let store_dir = backend.store_dir()?;
+        let data_dir = backend.data_dir();
+
+        // Without the initial scan the table is populated from the cached
+        // listing, otherwise the startup waits a limited time for the scan
+        let cached = if initial_scan {
+            None
+        } else {
+            cached_passwords(&store_dir, data_dir.as_deref(), &config.excludes)
+        };
+        let dates_pending = cached.is_some();
+        let store = PasswordStore {
+            store_dir,
+            passwords: cached.unwrap_or_default(),
+        };
         let password_refs: Vec<&PasswordInfo> = store.passwords.iter().collect();
         let password_subset = (0..store.passwords.len()).collect();
         let search_field = SearchField::new();
         let help_popup = HelpPopup::new();
         let file_popup = FilePopup::new();
-        let pool = ThreadPool::builder()
-            .pool_size(2)
-            .create()
-            .expect("this should work");
+        let pool = ThreadPool::builder().pool_size(2).create().map_err(|e| {
+            StartupError::new(
+                format!("Failed to start worker threads: {e}"),
+                "Check the limit on the number of processes (ulimit -u) and the available memory.",
+            )
+        })?;
+        let mut password_table = PasswordTable::new(&password_refs);
+        password_table.index_strip = config.index_strip;
+        password_table.logins = config.login_column.then(|| {
+            let file_name = store_file_name(&store.store_dir, LOGINS_EXTENSION);
+            let mut logins = Remembered::load(data_dir.as_deref(), &file_name);
+            // Earlier versions also remembered second lines that are no logins
+            if logins.retain(entry::is_login) {
+                let _ = logins.save(data_dir.as_deref(), &file_name);
+            }
+            logins
+        });
+        password_table.date_format = DateFormat::new(config.date_locale.clone());
+        password_table.row_format = config.row_format.clone();
+        password_table.fuzzy = config.fuzzy_search;
+        password_table.dates_pending = dates_pending;
+        let details_cache = DetailsCache::new(config.cache_ttl, config.cache_size);
+        let commands_popup = CommandsPopup::new(config.commands.clone());
+        let show_archive = !config.archive_hidden;
+        let instance_lock = data::try_lock(
+            data_dir.as_deref(),
+            &store_file_name(&store.store_dir, "lock"),
+        );
+        let pinned = data::read_lines(data_dir.as_deref(), PINNED_FILE);
+        let usage = Usage::load(
+            data_dir.as_deref(),
+            &store_file_name(&store.store_dir, USAGE_EXTENSION),
+        );
+        let hosts = Remembered::load(
+            data_dir.as_deref(),
+            &store_file_name(&store.store_dir, HOSTS_EXTENSION),
+        );
+        let aliases = Remembered::load(
+            data_dir.as_deref(),
+            &store_file_name(&store.store_dir, ALIASES_EXTENSION),
+        );
+        password_table.markers.aliases = aliases.pass_ids();
+        let action_log = ActionLog::new(
+            config
+                .action_log
+                .then(|| store_file_name(&store.store_dir, ACTION_LOG_EXTENSION)),
+        );
+        let read_only = matches!(instance_lock, Ok(None));
+        let push_on_quit = config.push_on_exit;
+        let details_position = config.details_position;
+        let details_height = config.details_height;
+        let details_width = config.details_width;
+        let details_collapsed = config.details_collapsed;
+        let warnings: Vec<String> = [
+            session::warning(&config.clipboard.provider),
+            gpg::cache_policy().ok().and_then(|policy| policy.warning()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let banner = (!warnings.is_empty()).then(|| Banner::new(warnings.join(" · ")));
         let mut dashboard = Self {
             tty_pinentry,
+            config,
+            backend,
+            data_dir,
             area: None,
-            password_table: PasswordTable::new(&password_refs),
+            password_table,
             store,
+            snapshot: StoreSnapshot::default(),
+            pending_scan: None,
+            select_after_scan: None,
+            open_after_scan: None,
+            lookup_after_scan: None,
+            quiet_scan: false,
+            rescan: None,
+            _watcher: None,
+            details_cache,
+            stale_check_pending: false,
+            otp_auto_pending: None,
+            lock_generation: 0,
+            last_stale_check: None,
+            pinned,
+            usage,
+            hosts,
+            aliases,
+            action_log,
+            marks: HashMap::new(),
+            previous_selection: None,
+            scope: None,
+            show_archive,
+            content_search: None,
+            otp_check: None,
+            duplicate_check: None,
+            strength_check: None,
+            scan_count: 0,
             password_details: PasswordDetails::new(),
             password_subset,
+            banner,
             menu: Menu::new(),
             search_field,
+            typed_search: false,
             help_popup,
             file_popup,
+            recovery_codes_popup: RecoveryCodesPopup::new(),
+            qr_popup: QrPopup::new(),
+            commands_popup,
+            audit_popup: AuditPopup::new(),
+            otp_audit_popup: OtpAuditPopup::new(),
+            duplicates_popup: DuplicatesPopup::new(),
+            strength_popup: StrengthPopup::new(),
+            session_log_popup: SessionLogPopup::new(),
+            history_popup: HistoryPopup::new(),
+            conflicts_popup: ConflictsPopup::new(),
+            dialog_popup: DialogPopup::new(),
+            last_error: None,
+            insert_popup: InsertPopup::new(),
+            prompt_popup: PromptPopup::new(),
+            generator_popup: GeneratorPopup::new(),
             status_bar: StatusBar::new(),
             app_state: app::State::default(),
+            focus_state: app::State::default(),
             render_details: true,
+            follow_deadline: None,
+            follow_timer_pending: false,
+            details_position,
+            details_height,
+            details_width,
+            details_collapsed,
+            details_area: Rect::default(),
+            dragging_border: false,
             pool,
             last_op: LastOperation::default(),
             event_tx,
+            _instance_lock: instance_lock.ok().flatten(),
+            read_only,
+            push_on_quit,
         };
-        dashboard.select_entry(0);
-        dashboard
+        dashboard.rebuild_table();
+        dashboard.scan_store();
+        if dashboard.config.watch_store {
+            // Without watching, the store is still refreshed with (F5)
+            dashboard._watcher =
+                watch::watch(&dashboard.store.store_dir, dashboard.event_tx.clone()).ok();
+        }
+        if initial_scan {
+            let scan = dashboard
+                .pending_scan
+                .as_ref()
+                .and_then(|receiver| receiver.recv_timeout(STARTUP_SCAN_TIMEOUT).ok());
+            match scan {
+                Some(scan) => {
+                    dashboard.apply_scan(scan);
+                }
+                None => dashboard
+                    .status_bar
+                    .set_status("⧗ Password store is being scanned...".to_string()),
+            }
+        }
+        #[cfg(target_os = "linux")]
+        if dashboard.config.lock_with_session {
+            if let Err(e) = crate::logind::watch(dashboard.event_tx.clone()) {
+                dashboard
+                    .status_bar
+                    .set_status(format!("✗ Locking with the session is not available: {e}"));
+            }
+        }
+        dashboard.status_bar.segments = dashboard.config.status_segments.clone();
+        dashboard.status_bar.read_only = read_only;
+        if read_only {
+            dashboard.status_bar.set_status(
+                "⚠ Another passepartui instance uses this store, opened read-only".to_string(),
+            );
+        } else if dashboard.config.pull_on_start {
+            if let Some(Action::SetStatus(progress)) = dashboard.run_git(StoreAction::GitPullRebase)
+            {
+                dashboard.status_bar.set_status(progress);
+            }
+        }
+        Ok(dashboard)
     }
 
     pub fn next(&mut self, step: usize) {
@@ -131,13 +1173,75 @@ impl Dashboard<'_> {
     }
 
     pub fn top_row(&mut self) {
+        let from = self.selected_pass_id();
         let i = 0;
         self.select_entry(i);
+        self.record_jump(from);
     }
 
     pub fn bottom_row(&mut self) {
+        let from = self.selected_pass_id();
         let i = self.password_subset.len() - 1;
         self.select_entry(i);
+        self.record_jump(from);
+    }
+
+    fn selected_pass_id(&self) -> Option<String> {
+        self.get_selected_info().map(|info| info.id.clone())
+    }
+
+    /// Remembers the entry selected before a jump for jumping back to it,
+    /// unless the jump stayed on it.
+    fn record_jump(&mut self, from: Option<String>) {
+        if from.is_some() && from != self.selected_pass_id() {
+            self.previous_selection = from;
+        }
+    }
+
+    fn set_mark(&mut self, letter: char) -> Option<Action> {
+        if !letter.is_ascii_alphabetic() {
+            return Some(Action::SetStatus(format!(
+                "✗ Marks are letters, not \"{letter}\""
+            )));
+        }
+        let pass_id = self.selected_pass_id()?;
+        let status = format!("Mark {letter} set on {pass_id}");
+        self.marks.insert(letter, pass_id);
+        Some(Action::SetStatus(status))
+    }
+
+    /// Selects the entry of the mark, (') and (`) as the letter jump back
+    /// like in Vim.
+    fn jump_to_mark(&mut self, letter: char) -> Option<Action> {
+        if matches!(letter, '\'' | '`') {
+            return self.jump_back();
+        }
+        match self.marks.get(&letter).cloned() {
+            Some(pass_id) => self.jump_to(&pass_id),
+            None => Some(Action::SetStatus(format!("✗ Mark {letter} is not set"))),
+        }
+    }
+
+    fn jump_back(&mut self) -> Option<Action> {
+        match self.previous_selection.clone() {
+            Some(pass_id) => self.jump_to(&pass_id),
+            None => Some(Action::SetStatus(
+                "✗ No previous selection to jump back to".to_string(),
+            )),
+        }
+    }
+
+    /// Selects an entry like going to it by path, the secrets view of the
+    /// entry switches to the preview of the new one.
+    fn jump_to(&mut self, pass_id: &str) -> Option<Action> {
+        let status = self.goto_entry(pass_id);
+        if self.app_state.main == MainState::Secrets
+            && self.selected_pass_id().as_deref() == Some(pass_id)
+        {
+            self.follow_selection()
+        } else {
+            status
+        }
     }
 
     fn select_entry(&mut self, index: usize) {
@@ -152,76 +1256,1460 @@ impl Dashboard<'_> {
                         return;
                     }
                 }
-                self.status_bar.reset_status();
-                self.file_popup.reset_content();
-                self.password_details.reset();
-                self.password_details.pass_id = Some(pass_id);
-            }
-            None => {
-                self.status_bar.reset_status();
-                self.file_popup.reset_content();
-                self.password_details.reset();
+                self.status_bar.reset_status();
+                self.file_popup.reset_content();
+                self.recovery_codes_popup.reset_content();
+                self.qr_popup.reset_content();
+                self.password_details.reset();
+                self.password_details.copy_default = match self.config.copy_field(&pass_id) {
+                    Field::Password => None,
+                    field => Some(field.label()),
+                };
+                self.password_details.pass_id = Some(pass_id);
+            }
+            None => {
+                self.status_bar.reset_status();
+                self.file_popup.reset_content();
+                self.recovery_codes_popup.reset_content();
+                self.qr_popup.reset_content();
+                self.password_details.reset();
+            }
+        }
+    }
+
+    pub fn get_selected_info(&self) -> Option<&PasswordInfo> {
+        if !self.password_subset.is_empty() {
+            return match self.password_table.selected() {
+                Some(index) => self.store.passwords.get(self.password_subset[index]),
+                None => None,
+            };
+        }
+        None
+    }
+
+    /// Updates the vector of indices for passwords matching the pattern,
+    /// pinned entries are placed first.
+    fn update_subset(&mut self, pattern: &str) {
+        let fuzzy = self.config.fuzzy_search;
+        // Going to an entry lists the paths starting with the pattern in the
+        // whole store
+        let goto = self.app_state.search == SearchState::Goto;
+        let prefix = match &self.scope {
+            Some(folder) if !goto => Some(format!("{folder}/")),
+            _ => None,
+        };
+        // Archived entries are listed when the archive folder is entered
+        let archive_visible = self.show_archive
+            || (goto && self.config.is_archived(pattern))
+            || self.scope.as_ref().is_some_and(|folder| {
+                *folder == self.config.archive_folder || self.config.is_archived(folder)
+            });
+        let (mut subset, unpinned): (Vec<_>, Vec<_>) = self
+            .store
+            .passwords
+            .iter()
+            .enumerate()
+            .filter(|(_, info)| {
+                prefix
+                    .as_ref()
+                    .is_none_or(|prefix| info.id.starts_with(prefix))
+            })
+            .filter(|(_, info)| archive_visible || !self.config.is_archived(&info.id))
+            .filter_map(|(index, info)| match &self.content_search {
+                _ if goto => info.id.starts_with(pattern).then_some((index, 0)),
+                Some(search) => search.matches.contains(&info.id).then_some((index, 0)),
+                None => matcher::find(pattern, &info.id, fuzzy).map(|found| (index, found.score)),
+            })
+            .partition(|&(index, _)| self.pinned.contains(&self.store.passwords[index].id));
+        self.password_table.pinned_rows = subset.len();
+        subset.extend(unpinned);
+        self.sort_subset(&mut subset);
+        self.password_subset = subset.into_iter().map(|(index, _)| index).collect();
+    }
+
+    /// Sorts the pinned entries and the other entries separately, pinned
+    /// entries stay on top. Fuzzy matches are ranked by their score first.
+    fn sort_subset(&self, subset: &mut [(usize, i64)]) {
+        let sort = self.password_table.sort;
+        let passwords = &self.store.passwords;
+        let (pinned, unpinned) = subset.split_at_mut(self.password_table.pinned_rows);
+        for part in [pinned, unpinned] {
+            part.sort_by(|&(a, a_score), &(b, b_score)| {
+                let (a, b) = (&passwords[a], &passwords[b]);
+                let ordering = match sort.column {
+                    None => std::cmp::Ordering::Equal,
+                    Some(SortColumn::PassId) => a.id.cmp(&b.id),
+                    Some(SortColumn::Modified) => a
+                        .metadata
+                        .modified()
+                        .ok()
+                        .cmp(&b.metadata.modified().ok())
+                        .then_with(|| a.id.cmp(&b.id)),
+                };
+                let ordering = if sort.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                };
+                b_score.cmp(&a_score).then(ordering)
+            });
+        }
+    }
+
+    /// Runs `pass git pull`, `push` or `status` in the background, the store
+    /// is scanned again after pulling.
+    fn run_git(&mut self, action: StoreAction) -> Option<Action> {
+        let (class, progress) = match action {
+            StoreAction::GitPull | StoreAction::GitPullRebase if self.read_only => {
+                return Some(Action::SetStatus(
+                    "✗ Read-only, another passepartui instance uses this store".to_string(),
+                ));
+            }
+            StoreAction::GitPull | StoreAction::GitPullRebase => {
+                ("git pull", "⧗ Pulling from remote...")
+            }
+            StoreAction::GitPush => ("git push", "⧗ Pushing to remote..."),
+            _ => ("git status", "⧗ Checking git status..."),
+        };
+        let completion_beacon = self.last_op.allows("", class)?;
+        let store_dir = self.store.store_dir.clone();
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            let event = match action {
+                StoreAction::GitPull | StoreAction::GitPullRebase => {
+                    match git_pull(&*backend, action == StoreAction::GitPullRebase) {
+                        Ok(message) => PasswordEvent::StorePulled { message },
+                        Err(e) => match list_conflicts(&*backend) {
+                            Ok(conflicts) if !conflicts.is_empty() => {
+                                PasswordEvent::Conflicts { conflicts }
+                            }
+                            _ => PasswordEvent::Status(Err(e)),
+                        },
+                    }
+                }
+                StoreAction::GitPush => PasswordEvent::Status(
+                    backend
+                        .git(&["push"])
+                        .map(|_| Some("Pushed to remote".to_string())),
+                ),
+                _ => PasswordEvent::Status(git_status(&*backend).map(Some)),
+            };
+            event_tx.send(event).expect("receiver deallocated");
+            let summary = git::status(&store_dir).unwrap_or(None);
+            event_tx
+                .send(PasswordEvent::GitStatus { summary })
+                .expect("receiver deallocated");
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus(progress.to_string()))
+        }
+    }
+
+    /// Returns true if quitting has to wait for unpushed commits to be
+    /// pushed first.
+    pub fn pushes_before_quit(&self) -> bool {
+        self.push_on_quit
+    }
+
+    /// Pushes unpushed commits in the background before quitting, the app
+    /// quits when it succeeded. Quitting again doesn't wait for the push.
+    fn push_before_quit(&mut self) -> Option<Action> {
+        self.push_on_quit = false;
+        let completion_beacon = self.last_op.allows("", "quit")?;
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            let event = match unpushed_commits(&*backend) {
+                0 => PasswordEvent::QuitReady,
+                _ => match backend.git(&["push"]) {
+                    Ok(_) => PasswordEvent::QuitReady,
+                    Err(e) => PasswordEvent::Status(Err(e)),
+                },
+            };
+            event_tx.send(event).expect("receiver deallocated");
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus(
+                "⧗ Pushing to remote before quitting, (q) quits right away...".to_string(),
+            ))
+        }
+    }
+
+    /// Resolves the selected conflict in the background, `None` only looks
+    /// up the conflicts that are left, e.g. after an entry was edited.
+    fn resolve_selected_conflict(&mut self, keep_local: Option<bool>) -> Option<Action> {
+        let conflict = self.conflicts_popup.selected_conflict()?.clone();
+        let completion_beacon = self.last_op.allows(&conflict.path, "resolve")?;
+        let store_dir = self.store.store_dir.clone();
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            let resolution = keep_local.map(|keep_local| (&conflict, keep_local));
+            match resolve_conflict(&*backend, resolution) {
+                Ok((conflicts, message)) => {
+                    event_tx
+                        .send(PasswordEvent::Conflicts { conflicts })
+                        .expect("receiver deallocated");
+                    if let Some(message) = message {
+                        event_tx
+                            .send(PasswordEvent::StorePulled { message })
+                            .expect("receiver deallocated");
+                    }
+                }
+                Err(e) => event_tx
+                    .send(PasswordEvent::Status(Err(e)))
+                    .expect("receiver deallocated"),
+            }
+            let summary = git::status(&store_dir).unwrap_or(None);
+            event_tx
+                .send(PasswordEvent::GitStatus { summary })
+                .expect("receiver deallocated");
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus("⧗ Resolving conflict...".to_string()))
+        }
+    }
+
+    /// Starts searching the decrypted password files of the listed entries
+    /// for the pattern in the search field, matches are added to the table
+    /// as they are found.
+    fn start_content_search(&mut self) -> Option<Action> {
+        let pattern = self.search_field.get_content();
+        if pattern.is_empty() {
+            return Some(Action::SetStatus(
+                "✗ Type the text to search the password files for".to_string(),
+            ));
+        }
+        self.stop_content_search();
+        self.update_subset("");
+        let pass_ids: Vec<String> = self
+            .password_subset
+            .iter()
+            .map(|&index| self.store.passwords[index].id.clone())
+            .collect();
+
+        let total = pass_ids.len();
+        let pattern = pattern.to_lowercase();
+        let (scan, action) = self.start_scan(
+            pass_ids,
+            true,
+            move |file_contents| {
+                Finding::Matched(
+                    file_contents
+                        .lines()
+                        .any(|line| line.to_lowercase().contains(&pattern)),
+                )
+            },
+            "⧗ Searching password files...",
+        );
+        self.content_search = Some(ContentSearch {
+            scan,
+            matches: Vec::new(),
+            searched: 0,
+            total,
+        });
+        self.search_field.contents = true;
+        self.password_table.highlight_pattern = None;
+        self.rebuild_table();
+        action
+    }
+
+    /// Decrypts the entries one after the other in the background with
+    /// [`scan_entries`], the results arrive as `ScanAction::Scanned`.
+    /// Returns the scan and the status while it runs.
+    fn start_scan(
+        &mut self,
+        pass_ids: Vec<String>,
+        follow_aliases: bool,
+        inspect: impl Fn(&str) -> Finding + Send + 'static,
+        status: &str,
+    ) -> (EntryScan, Option<Action>) {
+        self.scan_count += 1;
+        let scan = EntryScan {
+            id: self.scan_count,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+
+        let running = scan.clone();
+        let store_dir = self.store.store_dir.clone();
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+        let future = async move {
+            scan_entries(
+                &*backend,
+                &store_dir,
+                &running,
+                pass_ids,
+                follow_aliases,
+                &event_tx,
+                inspect,
+            );
+        };
+
+        let action = if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus(status.to_string()))
+        };
+        (scan, action)
+    }
+
+    /// Picks up the result for a password file of the running scan with the
+    /// ID, results of a stopped scan are ignored.
+    fn entry_scanned(&mut self, scan_id: u64, pass_id: String, finding: Finding) -> Option<Action> {
+        match finding {
+            Finding::Matched(matched) => self.content_searched(scan_id, pass_id, matched),
+            Finding::HasOtp(has_otp) => self.otp_checked(scan_id, pass_id, has_otp),
+            Finding::Credential(credential) => self.duplicate_checked(scan_id, pass_id, credential),
+            Finding::Weakness(weakness) => self.strength_checked(scan_id, pass_id, weakness),
+        }
+    }
+
+    /// Ends the running scan with the ID after an error, the results so far
+    /// are kept.
+    fn entry_scan_failed(&mut self, scan_id: u64, message: String) -> Option<Action> {
+        if let Some(search) = self
+            .content_search
+            .as_mut()
+            .filter(|search| search.scan.id == scan_id)
+        {
+            search.total = search.searched;
+            return Some(Action::ReportError(message));
+        }
+        let checks = [
+            &mut self.otp_check,
+            &mut self.duplicate_check,
+            &mut self.strength_check,
+        ];
+        let check = checks
+            .into_iter()
+            .find(|check| check.as_ref().is_some_and(|check| check.scan.id == scan_id))?;
+        *check = None;
+        Some(Action::ReportError(message))
+    }
+
+    /// Asks before decrypting the entries the audit could not check from
+    /// the cache.
+    fn confirm_scan(&mut self, audit: Audit) -> Option<Action> {
+        let (running, count, title, question, hint) = match audit {
+            Audit::Otp => (
+                self.otp_check.is_some(),
+                self.otp_audit_popup.unchecked().len(),
+                "Check for one-time passwords",
+                "check for one-time passwords",
+                "The contents are only checked for otpauth:// lines and not kept",
+            ),
+            Audit::Duplicates => (
+                self.duplicate_check.is_some(),
+                self.duplicates_popup.unchecked().len(),
+                "Find duplicates",
+                "compare their passwords and logins",
+                "Only a hash of the password and the login is kept",
+            ),
+            Audit::Strength => (
+                self.strength_check.is_some(),
+                self.strength_popup.unchecked().len(),
+                "Check password strength",
+                "rate their passwords",
+                "Only the ratings of weak passwords are kept, not the passwords",
+            ),
+        };
+        if running {
+            return Some(Action::SetStatus(
+                "⧗ Entries are being decrypted...".to_string(),
+            ));
+        }
+        if count == 0 {
+            return Some(Action::SetStatus("All entries were checked".to_string()));
+        }
+        self.open_dialog(
+            Dialog::new(title, format!("Decrypt {count} entries to {question}?"))
+                .hint(hint)
+                .button(
+                    "Decrypt",
+                    'y',
+                    Action::Scan(ScanAction::DecryptConfirmed(audit)),
+                )
+                .cancel_button("Cancel"),
+        );
+        None
+    }
+
+    /// Stops a running content search and goes back to the search by pass
+    /// ID.
+    fn stop_content_search(&mut self) {
+        if let Some(search) = self.content_search.take() {
+            search.scan.cancel();
+        }
+        self.search_field.contents = false;
+    }
+
+    /// Picks up the result for a password file of the running content
+    /// search, results of a stopped search are ignored.
+    fn content_searched(
+        &mut self,
+        search_id: u64,
+        pass_id: String,
+        matched: bool,
+    ) -> Option<Action> {
+        let search = self
+            .content_search
+            .as_mut()
+            .filter(|search| search.scan.id == search_id)?;
+        search.searched += 1;
+        if matched {
+            search.matches.push(pass_id);
+            self.rebuild_table();
+        }
+
+        let search = self.content_search.as_ref()?;
+        let found = search.matches.len();
+        let message = if search.searched < search.total {
+            format!(
+                "⧗ Searching password files {}/{}, {found} found",
+                search.searched, search.total
+            )
+        } else {
+            format!(
+                "{found} of {} password files contain the text",
+                search.total
+            )
+        };
+        Some(Action::SetStatus(message))
+    }
+
+    /// Remembers the host of the URL of a decrypted password file for
+    /// looking it up by URL, the entry an alias stands in for to mark it in
+    /// the table and its login for the login column, if it is shown.
+    fn remember(&mut self, pass_id: &str, file_contents: &str, target: Option<&str>) {
+        let host = browser::find_url(file_contents)
+            .and_then(|url| browser::host(&url))
+            .unwrap_or_default();
+        let mut changed = self.hosts.record(pass_id, &host);
+        if self.aliases.record(pass_id, target.unwrap_or_default()) {
+            changed = true;
+            self.password_table.markers.aliases = self.aliases.pass_ids();
+            self.rebuild_table();
+        }
+        if let Some(logins) = self.password_table.logins.as_mut() {
+            let login = entry::login(file_contents).unwrap_or_default();
+            if logins.record(pass_id, login) {
+                changed = true;
+                self.rebuild_table();
+            }
+        }
+        if changed {
+            self.save_remembered();
+        }
+    }
+
+    /// Keeps the remembered values and the marks of an entry that was
+    /// renamed or moved, they are forgotten for a removed entry without `to`.
+    fn move_remembered(&mut self, from: &str, to: Option<&str>) {
+        let marked = self
+            .marks
+            .values_mut()
+            .chain(self.previous_selection.as_mut());
+        for pass_id in marked.filter(|pass_id| *pass_id == from) {
+            *pass_id = to.unwrap_or_default().to_string();
+        }
+        self.marks.retain(|_, pass_id| !pass_id.is_empty());
+        self.previous_selection
+            .take_if(|pass_id| pass_id.is_empty());
+        let logins = self.password_table.logins.as_mut();
+        let remembered = [Some(&mut self.hosts), Some(&mut self.aliases), logins];
+        for remembered in remembered.into_iter().flatten() {
+            match to {
+                Some(to) => remembered.rename(from, to),
+                None => remembered.remove(from),
+            }
+        }
+        self.password_table.markers.aliases = self.aliases.pass_ids();
+    }
+
+    fn save_remembered(&self) {
+        let data_dir = self.data_dir.as_deref();
+        let file_name = store_file_name(&self.store.store_dir, HOSTS_EXTENSION);
+        let _ = self.hosts.save(data_dir, &file_name);
+        let file_name = store_file_name(&self.store.store_dir, ALIASES_EXTENSION);
+        let _ = self.aliases.save(data_dir, &file_name);
+        if let Some(logins) = &self.password_table.logins {
+            let file_name = store_file_name(&self.store.store_dir, LOGINS_EXTENSION);
+            let _ = logins.save(data_dir, &file_name);
+        }
+    }
+
+    /// Selects the entry that matches the host of the URL best and starts
+    /// decrypting it, see [`Dashboard::best_match`]. Without a match the
+    /// lookup is repeated once a pending scan has been applied.
+    pub fn lookup_url(&mut self, url: &str) -> Option<Action> {
+        let Some(host) = browser::host(url) else {
+            return Some(Action::SetStatus(format!("✗ No host found in {url}")));
+        };
+        match self.best_match(&host) {
+            Some(pass_id) => self.open_entry(&pass_id),
+            None if self.pending_scan.is_some() => {
+                self.lookup_after_scan = Some(url.to_string());
+                Some(Action::SetStatus(
+                    "⧗ Password store is being scanned...".to_string(),
+                ))
+            }
+            None => Some(Action::SetStatus(format!("✗ No entry matches {host}"))),
+        }
+    }
+
+    /// Returns the entry whose URL or a part of whose pass ID like
+    /// `github.com` is the host or the closest parent domain of it.
+    ///
+    /// URLs are only known for entries that were decrypted before and win
+    /// over the pass ID, then the entry used last and then the first by
+    /// name wins. Archived entries are left out.
+    fn best_match(&self, host: &str) -> Option<String> {
+        let from_urls = self.hosts.iter().filter_map(|(pass_id, domain)| {
+            Some((browser::match_labels(domain, host)?, true, pass_id))
+        });
+        let from_pass_ids = self.store.passwords.iter().filter_map(|info| {
+            let labels = info
+                .id
+                .split('/')
+                .filter(|part| part.contains('.'))
+                .filter_map(browser::host)
+                .filter_map(|domain| browser::match_labels(&domain, host))
+                .max()?;
+            Some((labels, false, info.id.as_str()))
+        });
+        from_urls
+            .chain(from_pass_ids)
+            .filter(|(_, _, pass_id)| {
+                !self.config.is_archived(pass_id)
+                    && self.store.passwords.iter().any(|info| info.id == *pass_id)
+            })
+            .max_by_key(|&(labels, from_url, pass_id)| {
+                (
+                    labels,
+                    from_url,
+                    self.usage.last_used(pass_id),
+                    Reverse(pass_id),
+                )
+            })
+            .map(|(_, _, pass_id)| pass_id.to_string())
+    }
+
+    /// Lists the entries without a one-time password, entries are checked
+    /// from the decrypted files in the cache and the others are listed as
+    /// unchecked. Entries in the archive folder are left out.
+    fn open_otp_audit(&mut self) {
+        self.stop_otp_check();
+        let mut missing = Vec::new();
+        let mut with_otp = 0;
+        let mut unchecked = Vec::new();
+        for info in &self.store.passwords {
+            if self.config.is_archived(&info.id) {
+                continue;
+            }
+            match self.details_cache.peek(&info.id) {
+                Some(file_contents) if entry::otp_uris(file_contents).is_empty() => {
+                    missing.push(info.id.clone())
+                }
+                Some(_) => with_otp += 1,
+                None => unchecked.push(info.id.clone()),
+            }
+        }
+        missing.sort();
+        self.otp_audit_popup
+            .set_entries(missing, with_otp, unchecked);
+        self.app_state.overlay = OverlayState::OtpAudit;
+    }
+
+    /// Decrypts the entries the OTP audit could not check from the cache,
+    /// results are added to the popup as they come in.
+    fn start_otp_check(&mut self) -> Option<Action> {
+        let pass_ids = self.otp_audit_popup.unchecked().to_vec();
+        let total = pass_ids.len();
+        let (scan, action) = self.start_scan(
+            pass_ids,
+            true,
+            |file_contents| Finding::HasOtp(!entry::otp_uris(file_contents).is_empty()),
+            "⧗ Decrypting password files...",
+        );
+        self.otp_check = Some(EntryCheck {
+            scan,
+            checked: 0,
+            total,
+        });
+        action
+    }
+
+    /// Stops decrypting entries for the OTP audit, e.g. when its popup is
+    /// closed.
+    fn stop_otp_check(&mut self) {
+        if let Some(check) = self.otp_check.take() {
+            check.scan.cancel();
+        }
+    }
+
+    /// Picks up the result for a password file of the running OTP check,
+    /// results of a stopped check are ignored.
+    fn otp_checked(&mut self, check_id: u64, pass_id: String, has_otp: bool) -> Option<Action> {
+        let check = self
+            .otp_check
+            .as_mut()
+            .filter(|check| check.scan.id == check_id)?;
+        check.checked += 1;
+        let (checked, total) = (check.checked, check.total);
+        self.otp_audit_popup.checked(pass_id, has_otp);
+
+        let message = if checked < total {
+            format!("⧗ Checking password files {checked}/{total}")
+        } else {
+            self.otp_check = None;
+            format!(
+                "{} entries have no one-time password",
+                self.otp_audit_popup.missing_count()
+            )
+        };
+        Some(Action::SetStatus(message))
+    }
+
+    /// Lists the entries with weak passwords, entries are rated from the
+    /// decrypted files in the cache and the others are listed as unchecked.
+    /// Entries in the archive folder are left out, as are known aliases
+    /// whose cached contents are those of their target.
+    fn open_strength_audit(&mut self) {
+        self.stop_strength_check();
+        let mut weak = Vec::new();
+        let mut strong = 0;
+        let mut unchecked = Vec::new();
+        for info in &self.store.passwords {
+            if self.config.is_archived(&info.id) || self.aliases.get(&info.id).is_some() {
+                continue;
+            }
+            match self.details_cache.peek(&info.id) {
+                Some(file_contents) => match password_weakness(file_contents) {
+                    Some(weakness) => weak.push((info.id.clone(), weakness)),
+                    None => strong += 1,
+                },
+                None => unchecked.push(info.id.clone()),
+            }
+        }
+        weak.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.strength_popup.set_entries(weak, strong, unchecked);
+        self.app_state.overlay = OverlayState::Strength;
+    }
+
+    /// Decrypts the entries the strength audit could not check from the
+    /// cache, results are added to the popup as they come in.
+    fn start_strength_check(&mut self) -> Option<Action> {
+        let pass_ids = self.strength_popup.unchecked().to_vec();
+        let total = pass_ids.len();
+        // Aliases are not followed, the password of their target is rated
+        // with the target
+        let (scan, action) = self.start_scan(
+            pass_ids,
+            false,
+            |file_contents| Finding::Weakness(password_weakness(file_contents)),
+            "⧗ Decrypting password files...",
+        );
+        self.strength_check = Some(EntryCheck {
+            scan,
+            checked: 0,
+            total,
+        });
+        action
+    }
+
+    /// Stops decrypting entries for the strength audit, e.g. when its popup
+    /// is closed.
+    fn stop_strength_check(&mut self) {
+        if let Some(check) = self.strength_check.take() {
+            check.scan.cancel();
+        }
+    }
+
+    /// Picks up the rating of a password file of the running strength
+    /// check, results of a stopped check are ignored.
+    fn strength_checked(
+        &mut self,
+        check_id: u64,
+        pass_id: String,
+        weakness: Option<strength::Weakness>,
+    ) -> Option<Action> {
+        let check = self
+            .strength_check
+            .as_mut()
+            .filter(|check| check.scan.id == check_id)?;
+        check.checked += 1;
+        let (checked, total) = (check.checked, check.total);
+        self.strength_popup.checked(pass_id, weakness);
+
+        let message = if checked < total {
+            format!("⧗ Rating passwords {checked}/{total}")
+        } else {
+            self.strength_check = None;
+            format!(
+                "{} entries have a weak password",
+                self.strength_popup.weak_count()
+            )
+        };
+        Some(Action::SetStatus(message))
+    }
+
+    /// Lists the entries sharing a password and login, entries are compared
+    /// from the decrypted files in the cache and the others are listed as
+    /// unchecked. Entries in the archive folder are left out, as are known
+    /// aliases whose cached contents are those of their target.
+    fn open_duplicates(&mut self) {
+        self.stop_duplicate_check();
+        let mut credentials: HashMap<u64, Vec<String>> = HashMap::new();
+        let mut checked = 0;
+        let mut unchecked = Vec::new();
+        for info in &self.store.passwords {
+            if self.config.is_archived(&info.id) || self.aliases.get(&info.id).is_some() {
+                continue;
+            }
+            match self.details_cache.peek(&info.id) {
+                Some(file_contents) => {
+                    checked += 1;
+                    if let Some(credential) = credential(file_contents) {
+                        credentials
+                            .entry(credential)
+                            .or_default()
+                            .push(info.id.clone());
+                    }
+                }
+                None => unchecked.push(info.id.clone()),
+            }
+        }
+        self.duplicates_popup
+            .set_entries(credentials, checked, unchecked);
+        self.app_state.overlay = OverlayState::Duplicates;
+    }
+
+    /// Decrypts the entries the search for duplicates could not check from
+    /// the cache, results are added to the popup as they come in.
+    fn start_duplicate_check(&mut self) -> Option<Action> {
+        let pass_ids = self.duplicates_popup.unchecked().to_vec();
+        let total = pass_ids.len();
+        // Aliases are not followed, they are no duplicates of their target
+        let (scan, action) = self.start_scan(
+            pass_ids,
+            false,
+            |file_contents| Finding::Credential(credential(file_contents)),
+            "⧗ Decrypting password files...",
+        );
+        self.duplicate_check = Some(EntryCheck {
+            scan,
+            checked: 0,
+            total,
+        });
+        action
+    }
+
+    fn stop_duplicate_check(&mut self) {
+        if let Some(check) = self.duplicate_check.take() {
+            check.scan.cancel();
+        }
+    }
+
+    /// Picks up the result for a password file of the running check for
+    /// duplicates, results of a stopped check are ignored.
+    fn duplicate_checked(
+        &mut self,
+        check_id: u64,
+        pass_id: String,
+        credential: Option<u64>,
+    ) -> Option<Action> {
+        let check = self
+            .duplicate_check
+            .as_mut()
+            .filter(|check| check.scan.id == check_id)?;
+        check.checked += 1;
+        let (checked, total) = (check.checked, check.total);
+        self.duplicates_popup.checked(pass_id, credential);
+
+        let message = if checked < total {
+            format!("⧗ Comparing password files {checked}/{total}")
+        } else {
+            self.duplicate_check = None;
+            format!(
+                "{} entries share their password and login with another one",
+                self.duplicates_popup.duplicate_count()
+            )
+        };
+        Some(Action::SetStatus(message))
+    }
+
+    /// Asks how to merge the group of the selected duplicate into it.
+    fn confirm_merge(&mut self) -> Option<Action> {
+        let (target, others) = self.duplicates_popup.selected_group()?;
+        let count = others.len();
+        self.open_dialog(
+            Dialog::new(
+                "Merge duplicates",
+                format!(
+                    "Keep {target} and merge the {count} other entries with the same password \
+                     and login into it?"
+                ),
+            )
+            .hint("Aliases keep the other pass IDs working with the kept entry")
+            .button(
+                "Aliases",
+                'a',
+                Action::Duplicates(DuplicatesAction::MergeAliases),
+            )
+            .button(
+                "Archive",
+                'A',
+                Action::Duplicates(DuplicatesAction::MergeArchive),
+            )
+            .button(
+                "Delete",
+                'D',
+                Action::Duplicates(DuplicatesAction::MergeDelete),
+            )
+            .cancel_button("Cancel"),
+        );
+        None
+    }
+
+    /// Merges the other entries of the group of the selected duplicate into
+    /// it by replacing them with aliases of it, archiving or deleting them.
+    fn merge_duplicates(&mut self, action: DuplicatesAction) -> Option<Action> {
+        let (target, pass_ids) = self.duplicates_popup.selected_group()?;
+        let completion_beacon = self.last_op.allows(&target, "merge")?;
+        let store_dir = self.store.store_dir.clone();
+        let archive_folder = self.config.archive_folder.clone();
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            let error = if action == DuplicatesAction::MergeAliases {
+                let (pass_ids, error) = alias_entries(&*backend, &target, pass_ids);
+                event_tx
+                    .send(PasswordEvent::EntriesAliased { target, pass_ids })
+                    .expect("receiver deallocated");
+                error
+            } else {
+                let archive_folder =
+                    (action == DuplicatesAction::MergeArchive).then_some(archive_folder);
+                let (moved, removed, error) =
+                    clean_entries(&*backend, &store_dir, pass_ids, archive_folder.as_deref());
+                event_tx
+                    .send(PasswordEvent::EntriesCleaned { moved, removed })
+                    .expect("receiver deallocated");
+                error
+            };
+            if let Some(e) = error {
+                event_tx
+                    .send(PasswordEvent::Status(Err(e)))
+                    .expect("receiver deallocated");
+            }
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus("⧗ Merging entries...".to_string()))
+        }
+    }
+
+    fn position_of(&self, pass_id: &str) -> Option<usize> {
+        self.password_subset
+            .iter()
+            .position(|&idx| self.store.passwords[idx].id == pass_id)
+    }
+
+    fn filter_passwords(&mut self) {
+        let pattern = self.search_field.get_content();
+        self.update_subset(&pattern);
+
+        // Reference vector for password table
+        let filtered_passwords: Vec<&PasswordInfo> = self
+            .password_subset
+            .iter()
+            .filter_map(|&idx| self.store.passwords.get(idx))
+            .collect();
+        self.password_table.highlight_pattern = Some(pattern);
+        self.password_table.update_passwords(&filtered_passwords);
+
+        // Select the first entry
+        self.select_entry(0);
+    }
+
+    /// Opens the search field with the pattern and filters the table by it.
+    pub fn search(&mut self, pattern: &str) {
+        self.search_field.reset();
+        self.search_field.set_content(pattern);
+        self.app_state.search = SearchState::Active;
+        self.filter_passwords();
+    }
+
+    fn reset_password_filter(&mut self) {
+        let selected_id = self.get_selected_info().map(|info| info.id.clone());
+        self.update_subset("");
+        let password_refs: Vec<&PasswordInfo> = self
+            .password_subset
+            .iter()
+            .filter_map(|&idx| self.store.passwords.get(idx))
+            .collect();
+        self.password_table.highlight_pattern = None;
+        self.password_table.update_passwords(&password_refs);
+        let index = selected_id
+            .and_then(|id| self.position_of(&id))
+            .unwrap_or(0);
+        self.select_entry(index);
+    }
+
+    /// Restricts the table and the search to a folder of the store,
+    /// `None` widens the scope to the whole store.
+    fn set_scope(&mut self, scope: Option<String>) -> Option<Action> {
+        let message = match &scope {
+            Some(folder) => format!("Showing entries in {folder}/"),
+            None => "Showing all entries".to_string(),
+        };
+        self.scope = scope;
+        self.search_field.scope = self.scope.clone();
+        self.password_table.scope = self.scope.clone();
+        self.rebuild_table();
+        Some(Action::SetStatus(message))
+    }
+
+    /// Renames an entry in the background.
+    /// Returns the entry of a dragged row and its path in the folder of the
+    /// entry it is dropped on, `None` if it is in that folder already.
+    fn drop_path(&self, from: usize, to: usize) -> Option<(String, String)> {
+        let pass_id = |index: usize| {
+            self.password_subset
+                .get(index)
+                .map(|&idx| &self.store.passwords[idx].id)
+        };
+        let (from, target) = (pass_id(from)?, pass_id(to)?);
+        let name = from.rsplit('/').next().unwrap_or(from);
+        let to = match target.rsplit_once('/') {
+            Some((folder, _)) => format!("{folder}/{name}"),
+            None => name.to_string(),
+        };
+        (to != *from).then(|| (from.clone(), to))
+    }
+
+    /// Asks before moving the entry of a dragged row to the folder of the
+    /// entry it was dropped on.
+    fn confirm_drop(&mut self, from: usize, to: usize) -> Option<Action> {
+        if self.app_state.overlay != OverlayState::Inactive {
+            return None;
+        }
+        let Some((from, to)) = self.drop_path(from, to) else {
+            return Some(Action::ResetStatus);
+        };
+        if let Some(message) = validate_new_path(&to, &self.store_paths()) {
+            return Some(Action::SetStatus(format!("✗ {message}")));
+        }
+        self.open_dialog(
+            Dialog::new("Move entry", format!("Move {from} to {to}?"))
+                .button(
+                    "Move",
+                    'y',
+                    Action::Table(TableAction::MoveConfirmed { from, to }),
+                )
+                .cancel_button("Cancel"),
+        );
+        Some(Action::ResetStatus)
+    }
+
+    fn spawn_move(&mut self, from: String, to: String) -> Option<Action> {
+        let completion_beacon = self.last_op.allows(&from, "move")?;
+        let file_path = self.store.store_dir.join(format!("{}.gpg", to));
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            match move_entry(&*backend, &from, &to, &file_path) {
+                Ok(()) => event_tx
+                    .send(PasswordEvent::EntryMoved { from, to })
+                    .expect("receiver deallocated"),
+                Err(e) => event_tx
+                    .send(PasswordEvent::Status(Err(e)))
+                    .expect("receiver deallocated"),
+            }
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus("⧗ Moving entry...".to_string()))
+        }
+    }
+
+    fn store_paths(&self) -> Vec<String> {
+        completion::store_paths(self.store.passwords.iter().map(|info| info.id.as_str()))
+    }
+
+    /// Starts the search for going to an entry by its path, beginning with
+    /// the current folder.
+    fn start_goto(&mut self) {
+        self.stop_content_search();
+        self.search_field.reset();
+        self.search_field.goto = true;
+        if let Some(folder) = &self.scope {
+            self.search_field.set_content(&format!("{folder}/"));
+        }
+        self.app_state.search = SearchState::Goto;
+        self.filter_passwords();
+    }
+
+    /// Leaves the search for going to an entry, the filter is removed.
+    fn stop_goto(&mut self) {
+        self.search_field.reset();
+        self.search_field.goto = false;
+        self.app_state.search = SearchState::Inactive;
+        self.reset_password_filter();
+    }
+
+    /// Extends the path in the search with the part that all paths of the
+    /// store starting with it share.
+    fn complete_goto(&mut self) {
+        let paths = self.store_paths();
+        if let Some(text) = completion::complete(&paths, &self.search_field.get_content()) {
+            self.search_field.set_content(&text);
+            self.filter_passwords();
+        }
+    }
+
+    /// Selects and fetches the entry with the path typed in the search.
+    fn confirm_goto(&mut self) -> Option<Action> {
+        let path = self.search_field.get_content();
+        let path = path.trim();
+        if !self.store.passwords.iter().any(|info| info.id == path) {
+            return Some(Action::SetStatus(format!(
+                "✗ No entry {path}, (⇥) completes the path"
+            )));
+        }
+        self.stop_goto();
+        self.goto_entry(path);
+        let index = self.position_of(path)?;
+        Some(Action::Navigation(NavigationAction::SelectAndFetch(index)))
+    }
+
+    /// Selects the entry and shows its secrets, e.g. for an entry given on
+    /// the command line. An entry that is not listed yet is opened after the
+    /// pending scan.
+    pub fn open_entry(&mut self, pass_id: &str) -> Option<Action> {
+        if self.pending_scan.is_some()
+            && !self.store.passwords.iter().any(|info| info.id == pass_id)
+        {
+            self.open_after_scan = Some(pass_id.to_string());
+            return Some(Action::SetStatus(
+                "⧗ Password store is being scanned...".to_string(),
+            ));
+        }
+        let status = self.goto_entry(pass_id);
+        match self.position_of(pass_id) {
+            Some(index) => Some(Action::Navigation(NavigationAction::SelectAndFetch(index))),
+            None => status,
+        }
+    }
+
+    /// Hands the one-time password shown in the QR codes popup to the
+    /// configured export command in the background.
+    fn spawn_otp_export(&mut self) -> Option<Action> {
+        let Some(otp_index) = self
+            .qr_popup
+            .otp_index()
+            .filter(|_| self.app_state.overlay == OverlayState::Qr)
+        else {
+            return Some(Action::SetStatus(
+                "✗ Show the QR code of a one-time password to export it".to_string(),
+            ));
+        };
+        if self.config.otp_export.is_empty() {
+            return Some(Action::SetStatus(
+                "✗ No export command configured, set `export` under [otp]".to_string(),
+            ));
+        }
+        let pass_id = self.get_selected_info()?.id.clone();
+        let completion_beacon = self.last_op.allows(&pass_id, "export_otp")?;
+        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+        let args = self.config.otp_export.clone();
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            let event = match export_otp(&*backend, &file_path, &pass_id, otp_index, &args) {
+                Ok(message) => PasswordEvent::Status(Ok(Some(message))),
+                Err(e) => PasswordEvent::Status(Err(e)),
+            };
+            event_tx.send(event).expect("receiver deallocated");
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            Some(Action::SetStatus(
+                "⧗ Exporting one-time password...".to_string(),
+            ))
+        }
+    }
+
+    /// Uses the path entered in the prompt.
+    fn submit_prompt(&mut self) -> Option<Action> {
+        if let Some(message) = self.prompt_popup.validate() {
+            return Some(Action::SetStatus(format!("✗ {message}")));
+        }
+        let path = self.prompt_popup.text();
+        match self.prompt_popup.purpose().clone() {
+            PromptPurpose::Move(from) if path == from => {
+                self.app_state.overlay = OverlayState::Inactive;
+                None
+            }
+            PromptPurpose::Move(_) if self.read_only => Some(Action::SetStatus(
+                "✗ Read-only, another passepartui instance uses this store".to_string(),
+            )),
+            PromptPurpose::Move(from) => self.spawn_move(from, path),
+            // Continue with the form for the first entry of the folder
+            PromptPurpose::NewFolder => {
+                let folder = path.trim_end_matches('/');
+                self.insert_popup.reset(Some(folder));
+                let template = self.config.template(&self.insert_popup.pass_id());
+                self.insert_popup.set_template(template);
+                self.app_state.overlay = OverlayState::Insert;
+                Some(Action::ResetStatus)
+            }
+            PromptPurpose::LookupUrl => {
+                self.app_state.overlay = OverlayState::Inactive;
+                self.lookup_url(&path)
+            }
+            PromptPurpose::ExportSessionLog => {
+                self.app_state.overlay = OverlayState::Inactive;
+                let message = match self.action_log.export(Path::new(&path)) {
+                    Ok(()) => format!("Exported the session log to {path}"),
+                    Err(e) => format!("✗ Failed to export the session log: {e}"),
+                };
+                Some(Action::SetStatus(message))
+            }
+        }
+    }
+
+    /// Selects the entry, the search, the folder and hiding the archive are
+    /// left if they keep it out of the table.
+    fn goto_entry(&mut self, pass_id: &str) -> Option<Action> {
+        let from = self.selected_pass_id();
+        if self.position_of(pass_id).is_none() {
+            self.search_field.reset();
+            self.stop_content_search();
+            self.app_state.search = SearchState::Inactive;
+            self.scope = None;
+            self.search_field.scope = None;
+            self.password_table.scope = None;
+            if self.config.is_archived(pass_id) {
+                self.show_archive = true;
+            }
+            self.reset_password_filter();
+        }
+        match self.position_of(pass_id) {
+            Some(index) => {
+                self.select_entry(index);
+                self.record_jump(from);
+                Some(Action::SetStatus(format!("Selected {pass_id}")))
+            }
+            None => Some(Action::SetStatus(format!("✗ {pass_id} not found"))),
+        }
+    }
+
+    /// Moves the selected entry into the archive folder or back out of it.
+    fn archive_entry(&mut self) -> Option<Action> {
+        let from = self.get_selected_info()?.id.clone();
+        let to = match from
+            .strip_prefix(self.config.archive_folder.as_str())
+            .and_then(|rest| rest.strip_prefix('/'))
+        {
+            Some(rest) => rest.to_string(),
+            None => format!("{}/{from}", self.config.archive_folder),
+        };
+        if self.store.passwords.iter().any(|info| info.id == to) {
+            Some(Action::SetStatus(format!("✗ {to} already exists")))
+        } else {
+            self.spawn_move(from, to)
+        }
+    }
+
+    /// Pins the selected entry to the top of the table or unpins it.
+    fn toggle_pin(&mut self) -> Option<Action> {
+        let pass_id = self.get_selected_info()?.id.clone();
+        let message = if let Some(position) = self.pinned.iter().position(|id| *id == pass_id) {
+            self.pinned.remove(position);
+            format!("Unpinned {pass_id}")
+        } else {
+            self.pinned.push(pass_id.clone());
+            format!("Pinned {pass_id} to the top")
+        };
+        self.rebuild_table();
+        match data::write_lines(self.data_dir.as_deref(), PINNED_FILE, &self.pinned) {
+            Ok(()) => Some(Action::SetStatus(message)),
+            Err(e) => Some(Action::SetStatus(format!(
+                "✗ Failed to save pinned entries: {e}"
+            ))),
+        }
+    }
+
+    /// Re-scans the directories of the store that changed since the last
+    /// scan in the background.
+    fn refresh_store(&mut self) -> Option<Action> {
+        if self.pending_scan.is_some() {
+            self.rescan = Some(false);
+            return Some(Action::SetStatus(
+                "⧗ Password store is being scanned...".to_string(),
+            ));
+        }
+        self.scan_store();
+        Some(Action::SetStatus(
+            "⧗ Refreshing password store...".to_string(),
+        ))
+    }
+
+    /// Scans the store again after it was changed outside of passepartui,
+    /// the selection and the search filter are kept.
+    fn store_changed(&mut self) -> Option<Action> {
+        if self.pending_scan.is_some() {
+            // The pending scan might have missed the change
+            self.rescan.get_or_insert(true);
+        } else {
+            self.quiet_scan = true;
+            self.scan_store();
+        }
+        None
+    }
+
+    /// Scans the store on a separate thread so that a slow filesystem does
+    /// not block the interface or the decryption of entries, the result is
+    /// picked up with `StoreAction::Reconcile`.
+    fn scan_store(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        self.pending_scan = Some(receiver);
+        let store_dir = self.store.store_dir.clone();
+        let passwords = self.store.passwords.clone();
+        let snapshot = mem::take(&mut self.snapshot);
+        let excludes = self.config.excludes.clone();
+        let check_git = self.status_bar.segments.contains(&Segment::Git);
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+        thread::spawn(move || {
+            let _ = sender.send(backend.scan(&store_dir, passwords, snapshot, &excludes));
+            event_tx
+                .send(PasswordEvent::StoreScanned)
+                .expect("receiver deallocated");
+            if check_git {
+                let summary = git::status(&store_dir).unwrap_or(None);
+                event_tx
+                    .send(PasswordEvent::GitStatus { summary })
+                    .expect("receiver deallocated");
+            }
+        });
+    }
+
+    /// Picks up the result of the background scan.
+    fn reconcile_store(&mut self) -> Option<Action> {
+        match self.pending_scan.as_ref()?.try_recv() {
+            // The store changed during the scan, the result is applied
+            // quietly and the status is shown after the next scan
+            Ok(scan) => match self.rescan.take() {
+                Some(quiet) => {
+                    self.quiet_scan = true;
+                    self.apply_scan(scan);
+                    self.quiet_scan = quiet;
+                    self.scan_store();
+                    None
+                }
+                None => {
+                    let action = self.apply_scan(scan);
+                    match (self.open_after_scan.take(), self.lookup_after_scan.take()) {
+                        (Some(pass_id), _) => self.open_entry(&pass_id),
+                        (None, Some(url)) => self.lookup_url(&url),
+                        (None, None) => action,
+                    }
+                }
+            },
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.pending_scan = None;
+                Some(Action::SetStatus(
+                    "✗ Failed to scan password store".to_string(),
+                ))
             }
         }
     }
 
-    pub fn get_selected_info(&self) -> Option<&PasswordInfo> {
-        if !self.password_subset.is_empty() {
-            return match self.password_table.selected() {
-                Some(index) => self.store.passwords.get(self.password_subset[index]),
-                None => None,
+    /// Updates the table with the result of a scan while keeping the
+    /// selected entry.
+    fn apply_scan(&mut self, scan: store::Scan) -> Option<Action> {
+        self.pending_scan = None;
+        self.snapshot = scan.snapshot;
+        self.password_table.markers.links = self.snapshot.links().to_vec();
+        // Changing permissions leaves the modification times alone, the
+        // table is rebuilt for greying out the entries
+        let unreadable = self.snapshot.unreadable().to_vec();
+        let permissions_changed = unreadable != self.password_table.markers.unreadable;
+        self.password_table.markers.unreadable = unreadable;
+        self.audit_popup
+            .set_permissive(self.snapshot.permissive().to_vec(), pass::umask());
+        let quiet = mem::take(&mut self.quiet_scan);
+        if !scan.changed && !permissions_changed && !self.password_table.dates_pending {
+            if quiet {
+                return None;
+            }
+            let message = match self.select_after_scan.take() {
+                Some((_, message)) => message,
+                None => format!("Password store is up to date{}", self.permissions_note()),
             };
+            return Some(Action::SetStatus(message));
         }
-        None
+        self.store.passwords = scan.passwords;
+        self.password_table.dates_pending = false;
+
+        // Drop decrypted files that were changed or removed
+        let passwords = &self.store.passwords;
+        self.details_cache.retain(|pass_id, modified| {
+            passwords
+                .binary_search_by(|info| info.id.as_str().cmp(pass_id))
+                .is_ok_and(|index| passwords[index].metadata.modified().ok() == modified)
+        });
+        self.rebuild_table();
+        self.save_listing();
+        if quiet {
+            return None;
+        }
+        if let Some((pass_id, message)) = self.select_after_scan.take() {
+            if let Some(index) = self.position_of(&pass_id) {
+                self.select_entry(index);
+            }
+            return Some(Action::SetStatus(message));
+        }
+        Some(Action::SetStatus(format!(
+            "Password store refreshed, {} entries{}",
+            self.store.passwords.len(),
+            self.permissions_note()
+        )))
     }
 
-    fn filter_passwords(&mut self) {
-        let pattern = self.search_field.get_content();
+    /// Returns notes on the files and folders of the store that can't be
+    /// read or are more open than the umask allows for the status after
+    /// scanning, empty if there are none.
+    fn permissions_note(&self) -> String {
+        let mut note = String::new();
+        let unreadable = self.snapshot.unreadable();
+        if let Some(first) = unreadable.first() {
+            note += &format!(
+                " · ⊘ {} can't be read, e.g. {first}, check their owner",
+                store::count_paths(unreadable)
+            );
+        }
+        let permissive = self.snapshot.permissive();
+        if !permissive.is_empty() {
+            note += &format!(
+                " · ⚠ {} more open than PASSWORD_STORE_UMASK, see (S)",
+                store::count_paths(permissive)
+            );
+        }
+        note
+    }
 
-        // Vector of indices for matching passwords
-        self.password_subset = self
+    /// Caches the IDs of the store for a start without the initial scan.
+    fn save_listing(&self) {
+        let ids: Vec<String> = self
             .store
             .passwords
             .iter()
-            .enumerate()
-            .filter(|(_, info)| info.id.to_lowercase().contains(&pattern.to_lowercase()))
-            .map(|(index, _)| index)
+            .map(|info| info.id.clone())
             .collect();
+        let file_name = store_file_name(&self.store.store_dir, LISTING_EXTENSION);
+        let _ = data::write_lines(self.data_dir.as_deref(), &file_name, &ids);
+    }
 
-        // Reference vector for password table
+    /// Rebuilds the table after the store vector changed.
+    ///
+    /// The search filter, the selected entry and the scroll position are
+    /// preserved. If the selected entry was removed, the entry that took its
+    /// place is selected instead.
+    fn rebuild_table(&mut self) {
+        self.update_changed();
+        self.update_aged();
+        let selected_id = self.get_selected_info().map(|info| info.id.clone());
+        let selected_index = self.password_table.selected().unwrap_or(0);
+
+        let pattern = self.search_field.get_content();
+        self.update_subset(&pattern);
         let filtered_passwords: Vec<&PasswordInfo> = self
             .password_subset
             .iter()
             .filter_map(|&idx| self.store.passwords.get(idx))
             .collect();
+        self.password_table.replace_passwords(&filtered_passwords);
 
-        self.password_table.highlight_pattern = Some(pattern);
-        self.password_table.update_passwords(&filtered_passwords);
-
-        // Select the first entry
-        self.select_entry(0);
-    }
-
-    fn reset_password_filter(&mut self) {
-        let index = if let Some(index) = self.password_table.selected() {
-            self.password_subset[index]
-        } else {
-            0
-        };
-        let password_refs: Vec<&PasswordInfo> = self.store.passwords.iter().collect();
-        self.password_subset = (0..self.store.passwords.len()).collect();
-        self.password_table.highlight_pattern = None;
-        self.password_table.update_passwords(&password_refs);
+        let index = selected_id
+            .and_then(|id| self.position_of(&id))
+            .unwrap_or(selected_index);
         self.select_entry(index);
     }
 
-    fn update_pass_details(&mut self, pass_id: String, message: String) -> Option<Action> {
+    fn update_pass_details(
+        &mut self,
+        pass_id: String,
+        message: &str,
+        modified: Option<SystemTime>,
+    ) -> Option<Action> {
         match self.get_selected_info() {
             Some(info) if pass_id == info.id => (),
             _ => return None,
         }
 
-        self.file_popup.set_content(&pass_id, &message.clone());
+        self.password_details.wipe_secrets();
+        self.password_details.fetch_error = None;
+        self.password_details.fetched_modified = modified;
+        self.password_details.stale = false;
+
+        self.file_popup.set_content(&pass_id, message);
+        self.recovery_codes_popup
+            .set_content(&pass_id, entry::recovery_codes(message));
+        match self.app_state.overlay {
+            OverlayState::WifiQr => self.qr_popup.set_wifi(&pass_id, message),
+            OverlayState::Qr => {
+                let otp_index = self.password_details.otp_index;
+                self.qr_popup.set_secrets(&pass_id, message, otp_index);
+            }
+            _ => (),
+        }
         let mut lines = message.lines();
         let mut count = 0;
         if let Some(password) = lines.next() {
@@ -233,16 +2721,29 @@ impl Dashboard<'_> {
             count += 1;
         }
 
-        let mut next_line = lines.next();
-        let mut has_otp = false;
-        while let Some(line) = next_line {
-            // One-time password (OTP)
-            if line.starts_with("otpauth://") {
-                has_otp = true;
-            }
-            count += 1;
-            next_line = lines.next();
-        }
+        count += lines.count();
+        // The URL has a field of its own with a button for opening it
+        let url = browser::find_url(message);
+        let fields = entry::key_values(message)
+            .into_iter()
+            .filter(|(key, value)| {
+                !(key.eq_ignore_ascii_case("url") && url.as_ref() == Some(value))
+            })
+            .collect();
+        self.password_details.set_fields(fields);
+        self.password_details.url = url;
+
+        // One-time password (OTP)
+        let otp_uris = entry::otp_uris(message);
+        let has_otp = !otp_uris.is_empty();
+        self.password_details.otp_labels = otp_uris
+            .iter()
+            .map(|uri| uri.label().unwrap_or("Unnamed".to_string()))
+            .collect();
+        self.password_details.otp_index = self
+            .password_details
+            .otp_index
+            .min(otp_uris.len().saturating_sub(1));
 
         // let remainder = lines.fold(String::default(), |a, b| a + b);
         // if !remainder.is_empty() {}
@@ -251,36 +2752,694 @@ impl Dashboard<'_> {
 
         if has_otp {
             self.password_details.one_time_password = Some("*".repeat(6));
+            self.password_details.otp_window = None;
+            if self.config.otp_auto != OtpAuto::Off {
+                self.otp_auto_pending = Some(pass_id);
+            }
             Some(Action::Password(PasswordAction::FetchOtp))
         } else {
             None
         }
     }
 
+    /// Looks up who last changed the selected entry in the git history
+    /// of the store in the background.
+    fn fetch_last_change(&mut self) {
+        if let Some(info) = self.get_selected_info() {
+            let pass_id = info.id.clone();
+            let store_dir = self.store.store_dir.clone();
+            let file_path = store_dir.join(format!("{}.gpg", pass_id));
+            let event_tx = self.event_tx.clone();
+            self.pool.spawn_ok(async move {
+                let last_change = git::last_change(&store_dir, &file_path).unwrap_or(None);
+                event_tx
+                    .send(PasswordEvent::LastChange {
+                        pass_id,
+                        last_change,
+                    })
+                    .expect("receiver deallocated");
+            });
+        }
+    }
+
+    /// Looks up the commits that changed an entry in the background.
+    fn fetch_history(&mut self, pass_id: String) {
+        let store_dir = self.store.store_dir.clone();
+        let file_path = store_dir.join(format!("{}.gpg", pass_id));
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+        self.pool.spawn_ok(async move {
+            let event = match backend.history(&store_dir, &file_path) {
+                Ok(revisions) => PasswordEvent::History { pass_id, revisions },
+                Err(e) => PasswordEvent::Status(Err(e)),
+            };
+            event_tx.send(event).expect("receiver deallocated");
+        });
+    }
+
+    /// Decrypts the versions compared in the history and shows the changes
+    /// or opens them with the difftool.
+    fn compare_versions(&mut self, difftool: bool) -> Option<Action> {
+        let (old, new) = self.history_popup.compared()?;
+        if difftool && self.config.difftool.is_empty() {
+            return Some(Action::SetStatus(
+                "No difftool configured, see [history] in the configuration".to_string(),
+            ));
+        }
+        let pass_id = self.history_popup.pass_id().to_string();
+        let class = if difftool { "difftool" } else { "diff" };
+        let completion_beacon = self.last_op.allows(&pass_id, class)?;
+        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+        let args = self.config.difftool.clone();
+        let generation = self.lock_generation;
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        let future = async move {
+            let old_label = old.clone().unwrap_or_else(|| "nothing".to_string());
+            let event = match decrypt_versions(&*backend, &file_path, old.as_deref(), &new) {
+                Ok((old_contents, new_contents)) if difftool => {
+                    // Named like the entry so that the tool shows which one it is
+                    let name = pass_id.rsplit('/').next().unwrap_or_default();
+                    let old_file = (&*format!("{old_label}-{name}"), &*old_contents);
+                    let new_file = (&*format!("{new}-{name}"), &*new_contents);
+                    match backend.run_difftool(&args, old_file, new_file) {
+                        Ok(()) => PasswordEvent::Status(Ok(None)),
+                        Err(e) => PasswordEvent::Status(Err(e)),
+                    }
+                }
+                Ok((old_contents, new_contents)) => PasswordEvent::Diff {
+                    lines: diff::lines(&old_contents, &new_contents),
+                    pass_id,
+                    generation,
+                    old: old_label,
+                    new,
+                },
+                Err(e) => PasswordEvent::Status(Err(e)),
+            };
+            event_tx.send(event).expect("receiver deallocated");
+            let _ = completion_beacon.send(1);
+        };
+
+        if self.tty_pinentry {
+            block_on(future);
+            Some(Action::Redraw)
+        } else {
+            self.pool.spawn_ok(future);
+            let status = if difftool {
+                "⧗ Waiting for the difftool..."
+            } else {
+                "⧗ Decrypting versions..."
+            };
+            Some(Action::SetStatus(status.to_string()))
+        }
+    }
+
+    /// Returns the job generating the shown one-time password of the entry,
+    /// the result arrives as `DisplayOneTimePassword`.
+    fn otp_future(&mut self, pass_id: String) -> Option<impl Future<Output = ()> + Send + 'static> {
+        let completion_beacon = self.last_op.allows(&pass_id, "copy_password")?;
+        let otp_index = self.password_details.otp_index;
+        let generation = self.lock_generation;
+        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+
+        Some(async move {
+            let event = match generate_otp_with_expiry(&*backend, &file_path, otp_index) {
+                Ok((otp, valid_until, period)) => PasswordEvent::OneTimePassword {
+                    pass_id,
+                    generation,
+                    otp,
+                    valid_until,
+                    period,
+                },
+                Err(e) => PasswordEvent::Status(Err(e)),
+            };
+            event_tx.send(event).expect("receiver deallocated");
+            let _ = completion_beacon.send(1);
+        })
+    }
+
+    /// Copies or types a generated one-time password as configured with
+    /// `auto` under [otp].
+    fn deliver_otp(&mut self, pass_id: String, otp: String) -> Option<Action> {
+        match &self.config.otp_auto {
+            OtpAuto::Off => None,
+            OtpAuto::Copy => match self.backend.copy(&otp, Content::Otp) {
+                Ok(()) => Some(Action::SetStatus(copied_message(
+                    "One-time password",
+                    self.config.clear_after,
+                ))),
+                Err(e) => Some(Action::ReportError(e.to_string())),
+            },
+            OtpAuto::Type(args) => {
+                let args = args.clone();
+                let backend = Arc::clone(&self.backend);
+                let event_tx = self.event_tx.clone();
+                self.pool.spawn_ok(async move {
+                    let event = match backend.run_command(&args, &otp) {
+                        Ok(()) => PasswordEvent::Status(Ok(Some(format!(
+                            "Typed the one-time password of {pass_id} with {}",
+                            args[0]
+                        )))),
+                        Err(e) => PasswordEvent::Status(Err(e)),
+                    };
+                    event_tx.send(event).expect("receiver deallocated");
+                });
+                Some(Action::SetStatus(
+                    "⧗ Typing one-time password...".to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Returns the most lines the details pane can take from the terminal
+    /// height while the table keeps a few rows.
+    fn max_details_height(&self, height: u16) -> u16 {
+        // The menu and the status bar take a line each
+        height.saturating_sub(2 + MIN_TABLE_HEIGHT)
+    }
+
+    /// Returns the lines of the details pane for the terminal height, 0 if
+    /// it is collapsed or there is no room for it.
+    fn details_area_height(&self, height: u16) -> u16 {
+        let details_height = self.details_height.min(self.max_details_height(height));
+        if self.details_collapsed || details_height < PasswordDetails::MIN_HEIGHT {
+            0
+        } else {
+            details_height
+        }
+    }
+
+    /// Returns the columns of the details sidebar for the terminal width, 0
+    /// if it is collapsed or there is no room for it.
+    fn details_area_width(&self, width: u16) -> u16 {
+        let details_width = self
+            .details_width
+            .min(width.saturating_sub(MIN_TABLE_WIDTH));
+        if self.details_collapsed || details_width < PasswordDetails::MIN_WIDTH {
+            0
+        } else {
+            details_width
+        }
+    }
+
+    /// Grows or shrinks the details pane by a step and shows it if it was
+    /// collapsed, it stops where the table would get too small.
+    fn resize_details(&mut self, grow: bool) {
+        self.details_collapsed = false;
+        if self.details_position == DetailsPosition::Right {
+            let most = self
+                .area
+                .map_or(u16::MAX, |area| area.width.saturating_sub(MIN_TABLE_WIDTH))
+                .max(PasswordDetails::MIN_WIDTH);
+            let width = self.details_width.min(most);
+            self.details_width = if grow {
+                width.saturating_add(SIDEBAR_STEP)
+            } else {
+                width.saturating_sub(SIDEBAR_STEP)
+            }
+            .clamp(PasswordDetails::MIN_WIDTH, most);
+            return;
+        }
+        let most = self
+            .area
+            .map_or(u16::MAX, |area| self.max_details_height(area.height))
+            .max(PasswordDetails::MIN_HEIGHT);
+        let height = self.details_height.min(most);
+        self.details_height = if grow {
+            height.saturating_add(1)
+        } else {
+            height.saturating_sub(1)
+        }
+        .clamp(PasswordDetails::MIN_HEIGHT, most);
+    }
+
+    /// Resizes the details pane by dragging the border between it and the
+    /// table, the size is kept for the rest of the session. Returns true if
+    /// the event belonged to dragging.
+    fn drag_details_border(&mut self, event: MouseEvent) -> bool {
+        let details = self.details_area;
+        let (Some(area), false) = (self.area, details.is_empty()) else {
+            self.dragging_border = false;
+            return false;
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left)
+                if self.app_state.overlay == OverlayState::Inactive =>
+            {
+                self.dragging_border = match self.details_position {
+                    DetailsPosition::Bottom => {
+                        event.row == details.y
+                            && (details.left()..details.right()).contains(&event.column)
+                    }
+                    DetailsPosition::Right => {
+                        event.column == details.x
+                            && (details.top()..details.bottom()).contains(&event.row)
+                    }
+                };
+                self.dragging_border
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging_border => {
+                // The pane stays anchored at the bottom or on the right
+                match self.details_position {
+                    DetailsPosition::Bottom => {
+                        self.details_height = details
+                            .bottom()
+                            .saturating_sub(event.row)
+                            .min(self.max_details_height(area.height))
+                            .max(PasswordDetails::MIN_HEIGHT);
+                    }
+                    DetailsPosition::Right => {
+                        self.details_width = details
+                            .right()
+                            .saturating_sub(event.column)
+                            .min(area.width.saturating_sub(MIN_TABLE_WIDTH))
+                            .max(PasswordDetails::MIN_WIDTH);
+                    }
+                }
+                true
+            }
+            MouseEventKind::Up(_) if self.dragging_border => {
+                self.dragging_border = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Generates the next one-time password in the background once the
+    /// shown one expired and updates the time left until then.
+    fn refresh_expired_otp(&mut self) {
+        let now = self.backend.clock().now();
+        let details = &mut self.password_details;
+        let Some((valid_until, period)) = details.otp_window else {
+            return;
+        };
+        let remaining = valid_until.duration_since(now).unwrap_or_default();
+        // A clock set back leaves more than a time step, the password is
+        // then generated again for the new time
+        let expired = now >= valid_until || remaining > period;
+        details.otp_remaining = Some(remaining.min(period));
+        if !expired || !details.show_secrets {
+            return;
+        }
+        details.otp_window = None;
+        details.otp_remaining = None;
+        details.one_time_password = Some("*".repeat(6));
+        // Decrypting might ask for the passphrase on the terminal, which
+        // is left to (r) then
+        if self.tty_pinentry {
+            return;
+        }
+        let Some(pass_id) = details.pass_id.clone() else {
+            return;
+        };
+        if let Some(future) = self.otp_future(pass_id) {
+            self.pool.spawn_ok(future);
+        }
+    }
+
+    /// Checks on a separate thread whether the password file was modified
+    /// since it was decrypted, the result arrives as `DisplayFileModified`.
+    fn check_stale(&mut self) {
+        let details = &self.password_details;
+        let (Some(pass_id), Some(_)) = (&details.pass_id, details.fetched_modified) else {
+            return;
+        };
+        let due = self
+            .last_stale_check
+            .is_none_or(|last| last.elapsed() >= STALE_CHECK_INTERVAL);
+        if self.stale_check_pending || !due {
+            return;
+        }
+        self.stale_check_pending = true;
+        self.last_stale_check = Some(Moment::now());
+        let pass_id = pass_id.clone();
+        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+        thread::spawn(move || {
+            let modified = backend.modified(&file_path);
+            event_tx
+                .send(PasswordEvent::FileModified { pass_id, modified })
+                .expect("receiver deallocated");
+        });
+    }
+
+    /// Continues the secrets view with the newly selected entry once the
+    /// selection rests, so that holding (j) doesn't decrypt every entry on
+    /// the way. Moving switches to the preview unless `follow_selection`
+    /// is set.
+    fn follow_selection(&mut self) -> Option<Action> {
+        if !self.config.follow_selection {
+            return Some(Action::Navigation(NavigationAction::Preview));
+        }
+        // The selection didn't change, e.g. at the end of the list
+        if self.password_details.show_secrets {
+            return None;
+        }
+        self.show_pass_secrets();
+        self.follow_deadline = Some(Moment::now() + self.config.follow_delay);
+        self.start_follow_timer();
+        None
+    }
+
+    /// Waits for the deadline of following the selection in the
+    /// background, one thread at a time.
+    fn start_follow_timer(&mut self) {
+        let Some(deadline) = self.follow_deadline else {
+            return;
+        };
+        if self.follow_timer_pending {
+            return;
+        }
+        self.follow_timer_pending = true;
+        let event_tx = self.event_tx.clone();
+        thread::spawn(move || {
+            deadline.sleep_until();
+            event_tx
+                .send(PasswordEvent::SelectionRested)
+                .expect("receiver deallocated");
+        });
+    }
+
     fn show_pass_secrets(&mut self) {
         self.password_details.show_secrets = true;
     }
 
+    /// Records that a secret of the selected entry was used, a read-only
+    /// instance keeps it in memory only.
+    fn record_use(&mut self) {
+        let Some(pass_id) = self.get_selected_info().map(|info| info.id.clone()) else {
+            return;
+        };
+        self.record_use_of(&pass_id);
+    }
+
+    fn record_use_of(&mut self, pass_id: &str) {
+        self.usage.record(pass_id, self.backend.clock().now());
+        if !self.read_only {
+            let file_name = store_file_name(&self.store.store_dir, USAGE_EXTENSION);
+            let _ = self.usage.save(self.data_dir.as_deref(), &file_name);
+        }
+        // Using the entry acknowledges the change
+        if self
+            .password_table
+            .markers
+            .changed
+            .iter()
+            .any(|changed| changed == pass_id)
+        {
+            self.rebuild_table();
+        }
+    }
+
+    /// Finds the entries that were changed since their secrets were last
+    /// used, entries that were never used are left out.
+    fn update_changed(&mut self) {
+        if !self.config.mark_changed {
+            return;
+        }
+        self.password_table.markers.changed = self
+            .store
+            .passwords
+            .iter()
+            .filter(|info| {
+                let last_used = self.usage.last_used(&info.id);
+                let modified = info.metadata.modified().ok();
+                // Uses are recorded in whole seconds
+                last_used
+                    .zip(modified)
+                    .is_some_and(|(last_used, modified)| {
+                        modified
+                            .duration_since(last_used)
+                            .is_ok_and(|since| since >= Duration::from_secs(1))
+                    })
+            })
+            .map(|info| info.id.clone())
+            .collect();
+    }
+
+    /// Copies a field of the selected entry in the background.
+    fn copy_selected_field(&mut self, field: Field) -> Option<Action> {
+        if let Some(info) = self.get_selected_info() {
+            let pass_id = info.id.clone();
+            // The one-time password that is shown, like with `CopyOtp`
+            let otp_index = self.password_details.otp_index;
+            if let Some(completion_beacon) = self.last_op.allows(&pass_id, "copy_password") {
+                let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                let backend = Arc::clone(&self.backend);
+                let event_tx = self.event_tx.clone();
+                let label = field.label();
+                let clear_after = self.config.clear_after;
+
+                let future = async move {
+                    let event = match copy_field(&*backend, &file_path, &field, otp_index) {
+                        Ok(_) => {
+                            let status_message = copied_message(&label, clear_after);
+                            PasswordEvent::Status(Ok(Some(status_message)))
+                        }
+                        Err(e) => PasswordEvent::Status(Err(e)),
+                    };
+                    event_tx.send(event).expect("receiver deallocated");
+                    let _ = completion_beacon.send(1);
+                };
+
+                if self.tty_pinentry {
+                    block_on(future);
+                    Some(Action::Redraw)
+                } else {
+                    self.pool.spawn_ok(future);
+                    let status_message = "⧗ Copying...".to_string();
+                    Some(Action::SetStatus(status_message))
+                }
+            } else {
+                None
+            }
+        } else {
+            let status_message = "No entry selected".to_string();
+            Some(Action::SetStatus(status_message))
+        }
+    }
+
+    /// Counts the ages of entries up to now and marks the entries that are
+    /// older than the configured age.
+    fn update_aged(&mut self) {
+        let now = self.backend.clock().now();
+        self.password_table.ages_at = self.config.age_column.then_some(now);
+        let Some(max_age) = self.config.max_age else {
+            return;
+        };
+        self.password_table.markers.aged = self
+            .store
+            .passwords
+            .iter()
+            .filter(|info| {
+                info.metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .is_some_and(|age| age > max_age)
+            })
+            .map(|info| info.id.clone())
+            .collect();
+    }
+
+    /// Appends to the action log if it is enabled, a read-only instance logs
+    /// as well since lines are only appended.
+    fn log_action(&mut self, pass_id: &str, what: &str) {
+        let _ = self.action_log.record(
+            self.data_dir.as_deref(),
+            self.backend.clock().now(),
+            pass_id,
+            what,
+        );
+    }
+
+    /// Returns the entries that were neither used nor modified within the
+    /// configured period, entries in the archive folder are left out.
+    fn stale_entries(&self) -> Vec<StaleEntry> {
+        let now = self.backend.clock().now();
+        let period = self.config.audit_period;
+        let archive_prefix = format!("{}/", self.config.archive_folder);
+        let days = |time: SystemTime| {
+            now.duration_since(time)
+                .map(|age| age.as_secs() / (24 * 60 * 60))
+                .unwrap_or_default()
+        };
+        let stale = |time: SystemTime| now.duration_since(time).is_ok_and(|age| age > period);
+        self.store
+            .passwords
+            .iter()
+            .filter(|info| !info.id.starts_with(&archive_prefix))
+            .filter_map(|info| {
+                let modified = info.metadata.modified().ok()?;
+                let last_used = self.usage.last_used(&info.id);
+                (stale(modified) && last_used.is_none_or(stale)).then(|| StaleEntry {
+                    pass_id: info.id.clone(),
+                    modified_days: days(modified),
+                    used_days: last_used.map(days),
+                })
+            })
+            .collect()
+    }
+
+    /// Forgets everything that was decrypted and has the agent forget the
+    /// passphrases, showing secrets again requires the passphrase.
+    fn lock(&mut self) -> Option<Action> {
+        self.lock_generation += 1;
+        self.details_cache.clear();
+        self.otp_auto_pending = None;
+        // A running content search would ask for the passphrase again, and
+        // its matches tell about the decrypted contents
+        if self.content_search.is_some() {
+            self.stop_content_search();
+            self.search_field.reset();
+            self.reset_password_filter();
+            self.app_state.search = SearchState::Inactive;
+        }
+        self.stop_otp_check();
+        self.stop_duplicate_check();
+        self.stop_strength_check();
+        self.hide_secrets();
+        if self.app_state.main == MainState::Secrets {
+            self.app_state.main = MainState::Preview;
+        }
+        // These popups show decrypted contents
+        if matches!(
+            self.app_state.overlay,
+            OverlayState::File
+                | OverlayState::RecoveryCodes
+                | OverlayState::WifiQr
+                | OverlayState::Qr
+                | OverlayState::History
+        ) {
+            self.app_state.overlay = OverlayState::Inactive;
+        }
+
+        let backend = Arc::clone(&self.backend);
+        let event_tx = self.event_tx.clone();
+        self.pool.spawn_ok(async move {
+            let status = backend.forget_passphrases().map(|()| {
+                Some("Locked, decrypted files and cached passphrases were forgotten".to_string())
+            });
+            event_tx
+                .send(PasswordEvent::Status(status))
+                .expect("receiver deallocated");
+        });
+        Some(Action::SetStatus("⧗ Locking...".to_string()))
+    }
+
     fn hide_secrets(&mut self) {
         self.password_details.clear_secrets();
         self.file_popup.reset_content();
+        self.recovery_codes_popup.reset_content();
+        self.qr_popup.reset_content();
+        self.history_popup.close_diff();
+    }
+
+    /// Shows a dialog on top of the current overlay.
+    fn open_dialog(&mut self, dialog: Dialog) {
+        let previous_overlay = self.app_state.overlay;
+        self.app_state.overlay = OverlayState::Dialog;
+        // The dialog focuses a button itself
+        self.sync_focus();
+        self.dialog_popup.open(dialog, previous_overlay);
+    }
+
+    /// Returns the component whose buttons are focused with (Tab) in the
+    /// current state.
+    fn focus_target(&mut self) -> Option<&mut dyn FocusSupport<'a>> {
+        self.sync_focus();
+        let target: &mut dyn FocusSupport<'a> = match self.app_state.overlay {
+            OverlayState::Help => &mut self.help_popup,
+            OverlayState::File => &mut self.file_popup,
+            OverlayState::RecoveryCodes => &mut self.recovery_codes_popup,
+            OverlayState::WifiQr | OverlayState::Qr => &mut self.qr_popup,
+            OverlayState::Commands => &mut self.commands_popup,
+            OverlayState::Audit => &mut self.audit_popup,
+            OverlayState::OtpAudit => &mut self.otp_audit_popup,
+            OverlayState::Duplicates => &mut self.duplicates_popup,
+            OverlayState::Strength => &mut self.strength_popup,
+            OverlayState::SessionLog => &mut self.session_log_popup,
+            OverlayState::History => &mut self.history_popup,
+            OverlayState::Conflicts => &mut self.conflicts_popup,
+            OverlayState::Dialog => &mut self.dialog_popup,
+            OverlayState::Generator => &mut self.generator_popup,
+            OverlayState::Prompt => &mut self.prompt_popup,
+            OverlayState::Insert => &mut self.insert_popup,
+            OverlayState::Inactive => match (self.app_state.main, self.app_state.search) {
+                (_, SearchState::Active | SearchState::Goto) | (MainState::Table, _) => {
+                    return None
+                }
+                (MainState::Preview | MainState::Secrets, _) => &mut self.password_details,
+            },
+        };
+        Some(target)
+    }
+
+    /// Drops the keyboard focus after the state changed, e.g. when a popup
+    /// was closed.
+    fn sync_focus(&mut self) {
+        if self.focus_state == self.app_state {
+            return;
+        }
+        self.focus_state = self.app_state;
+        let targets: [&mut dyn FocusSupport<'a>; 17] = [
+            &mut self.password_details,
+            &mut self.help_popup,
+            &mut self.file_popup,
+            &mut self.recovery_codes_popup,
+            &mut self.qr_popup,
+            &mut self.commands_popup,
+            &mut self.audit_popup,
+            &mut self.otp_audit_popup,
+            &mut self.duplicates_popup,
+            &mut self.strength_popup,
+            &mut self.session_log_popup,
+            &mut self.history_popup,
+            &mut self.conflicts_popup,
+            &mut self.dialog_popup,
+            &mut self.generator_popup,
+            &mut self.prompt_popup,
+            &mut self.insert_popup,
+        ];
+        for target in targets {
+            target.clear_focus();
+        }
+    }
+
+    /// Returns true if a button has the keyboard focus, (Enter) and (Space)
+    /// press it instead of running their actions.
+    pub fn has_focused_button(&mut self) -> bool {
+        self.focus_target()
+            .is_some_and(|target| target.focused_button().is_some())
     }
 }
 
 impl Component for Dashboard<'_> {
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if uses_secret(&action) {
+            self.record_use();
+        }
+        if let Some(what) = logged_action(&action) {
+            if let Some(pass_id) = self.selected_pass_id() {
+                self.log_action(&pass_id, what);
+            }
+        }
         let action = match action {
             Action::Password(action) => match action {
                 PasswordAction::CopyPassId => {
                     if let Some(info) = self.get_selected_info() {
-                        match passepartout::copy_id(info.id.clone()) {
+                        match self.backend.copy(&info.id, Content::Path) {
                             Ok(()) => {
                                 let message = "Password file ID copied to clipboard".to_string();
                                 Some(Action::SetStatus(message))
                             }
                             Err(passepartout::Error::Clipboard(e)) => {
-                                let message = format!("✗ Clipboard error: {e:?}");
-                                Some(Action::SetStatus(message))
+                                Some(Action::ReportError(format!("Clipboard error: {e}")))
                             }
                             Err(_) => None,
                         }
@@ -295,14 +3454,17 @@ impl Component for Dashboard<'_> {
                             self.last_op.allows(&pass_id, "copy_password")
                         {
                             let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                            let backend = Arc::clone(&self.backend);
                             let event_tx = self.event_tx.clone();
+                            let clear_after = self.config.clear_after;
 
                             let future = async move {
-                                let event = match passepartout::copy_password(&file_path) {
+                                let event = match file_line(&*backend, &file_path, 0, "password")
+                                    .and_then(|password| backend.copy(&password, Content::Password))
+                                {
                                     Ok(_) => {
                                         let status_message =
-                                            "Password copied to clipboard, clears after 45 seconds"
-                                                .to_string();
+                                            copied_message("Password", clear_after);
                                         PasswordEvent::Status(Ok(Some(status_message)))
                                     }
                                     Err(e) => PasswordEvent::Status(Err(e)),
@@ -327,6 +3489,15 @@ impl Component for Dashboard<'_> {
                         Some(Action::SetStatus(status_message))
                     }
                 }
+                PasswordAction::CopyDefault => {
+                    let field = self
+                        .get_selected_info()
+                        .map(|info| self.config.copy_field(&info.id).clone())
+                        .unwrap_or_default();
+                    self.copy_selected_field(field)
+                }
+                PasswordAction::CopyField(key) => self.copy_selected_field(Field::Key(key.clone())),
+                PasswordAction::CopyLine(number) => self.copy_selected_field(Field::Line(number)),
                 PasswordAction::CopyLogin => {
                     if let Some(info) = self.get_selected_info() {
                         let pass_id = info.id.clone();
@@ -334,14 +3505,16 @@ impl Component for Dashboard<'_> {
                             self.last_op.allows(&pass_id, "copy_password")
                         {
                             let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                            let backend = Arc::clone(&self.backend);
                             let event_tx = self.event_tx.clone();
+                            let clear_after = self.config.clear_after;
 
                             let future = async move {
-                                let event = match passepartout::copy_login(&file_path) {
+                                let event = match file_line(&*backend, &file_path, 1, "login")
+                                    .and_then(|login| backend.copy(&login, Content::Login))
+                                {
                                     Ok(_) => {
-                                        let status_message =
-                                            "Login copied to clipboard, clears after 45 seconds"
-                                                .to_string();
+                                        let status_message = copied_message("Login", clear_after);
                                         PasswordEvent::Status(Ok(Some(status_message)))
                                     }
                                     Err(e) => PasswordEvent::Status(Err(e)),
@@ -369,18 +3542,22 @@ impl Component for Dashboard<'_> {
                 PasswordAction::CopyOtp => {
                     if let Some(info) = self.get_selected_info() {
                         let pass_id = info.id.clone();
+                        let otp_index = self.password_details.otp_index;
                         if let Some(completion_beacon) =
                             self.last_op.allows(&pass_id, "copy_password")
                         {
                             let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                            let backend = Arc::clone(&self.backend);
                             let event_tx = self.event_tx.clone();
+                            let clear_after = self.config.clear_after;
 
                             let future = async move {
-                                let event = match passepartout::copy_otp(&file_path) {
+                                let event = match generate_otp(&*backend, &file_path, otp_index)
+                                    .and_then(|otp| backend.copy(&otp, Content::Otp))
+                                {
                                     Ok(_) => {
                                         let status_message =
-                                        "One-time password copied to clipboard, clears after 45 seconds"
-                                            .to_string();
+                                            copied_message("One-time password", clear_after);
                                         PasswordEvent::Status(Ok(Some(status_message)))
                                     }
                                     Err(e) => PasswordEvent::Status(Err(e)),
@@ -394,7 +3571,93 @@ impl Component for Dashboard<'_> {
                                 Some(Action::Redraw)
                             } else {
                                 self.pool.spawn_ok(future);
-                                let status_message = "⧗ Copying one-time password...".to_string();
+                                let status_message = "⧗ Copying one-time password...".to_string();
+                                Some(Action::SetStatus(status_message))
+                            }
+                        } else {
+                            None
+                        }
+                    } else {
+                        let status_message = "No entry selected".to_string();
+                        Some(Action::SetStatus(status_message))
+                    }
+                }
+                PasswordAction::Fetch => {
+                    if let Some(info) = self.get_selected_info() {
+                        let pass_id = info.id.clone();
+                        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                        if !store::is_readable(&file_path) {
+                            // Asking gpg would only report a failed read
+                            Some(Action::DisplayFetchError {
+                                message: "Permission denied, the file may be owned by root, \
+                                          check it with ls -l"
+                                    .to_string(),
+                                pass_id,
+                            })
+                        } else if let Some((file_contents, modified)) =
+                            self.details_cache.get(&pass_id)
+                        {
+                            let target = self.aliases.get(&pass_id).map(String::from);
+                            Some(Action::DisplaySecrets {
+                                pass_id,
+                                generation: self.lock_generation,
+                                file_contents,
+                                modified,
+                                target,
+                            })
+                        } else if let Some(completion_beacon) =
+                            self.last_op.allows(&pass_id, "decrypt_password_file")
+                        {
+                            let generation = self.lock_generation;
+                            let backend = Arc::clone(&self.backend);
+                            let event_tx = self.event_tx.clone();
+
+                            let future = async move {
+                                let modified = backend.modified(&file_path);
+                                let event = match alias::resolve(&*backend, &file_path) {
+                                    Ok((file_contents, target)) => PasswordEvent::PasswordFile {
+                                        pass_id,
+                                        generation,
+                                        file_contents: Arc::new(Secret::new(file_contents)),
+                                        modified,
+                                        target,
+                                    },
+                                    Err(error) => PasswordEvent::FetchFailed { pass_id, error },
+                                };
+                                event_tx.send(event).expect("receiver deallocated");
+                                let _ = completion_beacon.send(1);
+                            };
+
+                            if self.tty_pinentry {
+                                block_on(future);
+                                Some(Action::Redraw)
+                            } else {
+                                self.pool.spawn_ok(future);
+                                let status_message = "⧗ Fetching password entry...".to_string();
+                                Some(Action::SetStatus(status_message))
+                            }
+                        } else {
+                            None
+                        }
+                    } else {
+                        let status_message = "No entry selected".to_string();
+                        Some(Action::SetStatus(status_message))
+                    }
+                }
+                // Retry decrypting the file if that failed before
+                PasswordAction::FetchOtp if self.password_details.fetch_error.is_some() => {
+                    Some(Action::Password(PasswordAction::Fetch))
+                }
+                PasswordAction::FetchOtp => {
+                    if let Some(info) = self.get_selected_info() {
+                        let pass_id = info.id.clone();
+                        if let Some(future) = self.otp_future(pass_id) {
+                            if self.tty_pinentry {
+                                block_on(future);
+                                Some(Action::Redraw)
+                            } else {
+                                self.pool.spawn_ok(future);
+                                let status_message = "⧗ Fetching one-time password...".to_string();
                                 Some(Action::SetStatus(status_message))
                             }
                         } else {
@@ -405,24 +3668,108 @@ impl Component for Dashboard<'_> {
                         Some(Action::SetStatus(status_message))
                     }
                 }
-                PasswordAction::Fetch => {
-                    if let Some(info) = self.get_selected_info() {
+                PasswordAction::NextOtp if self.app_state.overlay == OverlayState::Qr => {
+                    self.qr_popup.next();
+                    None
+                }
+                PasswordAction::ShowOtpUri => {
+                    if self.qr_popup.toggle_uri() {
+                        None
+                    } else {
+                        Some(Action::SetStatus(
+                            "Only one-time passwords have a URI".to_string(),
+                        ))
+                    }
+                }
+                PasswordAction::ExportOtp => self.spawn_otp_export(),
+                PasswordAction::NextOtp => {
+                    let count = self.password_details.otp_labels.len();
+                    if count > 1 {
+                        let details = &mut self.password_details;
+                        details.otp_index = (details.otp_index + 1) % count;
+                        details.one_time_password = Some("*".repeat(6));
+                        details.otp_window = None;
+                        if self.config.otp_auto != OtpAuto::Off {
+                            self.otp_auto_pending = details.pass_id.clone();
+                        }
+                        Some(Action::Password(PasswordAction::FetchOtp))
+                    } else {
+                        None
+                    }
+                }
+                PasswordAction::CopyFileLine => match self.file_popup.selected_line() {
+                    Some((number, line)) => match self.backend.copy(line, Content::Line) {
+                        Ok(()) => {
+                            let message =
+                                copied_message(&format!("Line {number}"), self.config.clear_after);
+                            Some(Action::SetStatus(message))
+                        }
+                        Err(e) => Some(Action::ReportError(e.to_string())),
+                    },
+                    None => {
+                        let status_message = "No line selected".to_string();
+                        Some(Action::SetStatus(status_message))
+                    }
+                },
+                PasswordAction::CopyRecoveryCode => {
+                    match self.recovery_codes_popup.selected_code() {
+                        Some(code) => match self.backend.copy(&code.code, Content::RecoveryCode) {
+                            Ok(()) => {
+                                let message =
+                                    copied_message("Recovery code", self.config.clear_after);
+                                Some(Action::SetStatus(message))
+                            }
+                            Err(e) => Some(Action::ReportError(e.to_string())),
+                        },
+                        None => {
+                            let status_message = "No recovery code selected".to_string();
+                            Some(Action::SetStatus(status_message))
+                        }
+                    }
+                }
+                PasswordAction::UseRecoveryCode if self.read_only => Some(Action::SetStatus(
+                    "✗ Read-only, another passepartui instance uses this store".to_string(),
+                )),
+                PasswordAction::UseRecoveryCode => {
+                    let line = self
+                        .recovery_codes_popup
+                        .selected_code()
+                        .map(|code| code.line);
+                    if let (Some(info), Some(line)) = (self.get_selected_info(), line) {
                         let pass_id = info.id.clone();
                         if let Some(completion_beacon) =
-                            self.last_op.allows(&pass_id, "decrypt_password_file")
+                            self.last_op.allows(&pass_id, "use_recovery_code")
                         {
                             let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                            let generation = self.lock_generation;
+                            let backend = Arc::clone(&self.backend);
                             let event_tx = self.event_tx.clone();
 
                             let future = async move {
-                                let event = match passepartout::decrypt_password_file(&file_path) {
-                                    Ok(file_contents) => PasswordEvent::PasswordFile {
-                                        pass_id,
-                                        file_contents,
-                                    },
-                                    Err(e) => PasswordEvent::Status(Err(e)),
-                                };
-                                event_tx.send(event).expect("receiver deallocated");
+                                let result =
+                                    use_recovery_code(&*backend, &pass_id, &file_path, line);
+                                match result {
+                                    Ok((file_contents, target)) => {
+                                        event_tx
+                                            .send(PasswordEvent::PasswordFile {
+                                                pass_id,
+                                                generation,
+                                                file_contents: Arc::new(Secret::new(file_contents)),
+                                                modified: backend.modified(&file_path),
+                                                target,
+                                            })
+                                            .expect("receiver deallocated");
+                                        let status_message =
+                                            "Recovery code copied to clipboard and marked as used"
+                                                .to_string();
+                                        event_tx
+                                            .send(PasswordEvent::Status(Ok(Some(status_message))))
+                                            .expect("receiver deallocated");
+                                    }
+                                    Err(e) => event_tx
+                                        .send(PasswordEvent::Status(Err(e)))
+                                        .expect("receiver deallocated"),
+                                }
                                 let _ = completion_beacon.send(1);
                             };
 
@@ -431,29 +3778,193 @@ impl Component for Dashboard<'_> {
                                 Some(Action::Redraw)
                             } else {
                                 self.pool.spawn_ok(future);
-                                let status_message = "⧗ Fetching password entry...".to_string();
+                                let status_message =
+                                    "⧗ Marking recovery code as used...".to_string();
                                 Some(Action::SetStatus(status_message))
                             }
                         } else {
                             None
                         }
                     } else {
-                        let status_message = "No entry selected".to_string();
+                        let status_message = "No recovery code selected".to_string();
                         Some(Action::SetStatus(status_message))
                     }
                 }
-                PasswordAction::FetchOtp => {
+                PasswordAction::Insert | PasswordAction::Overwrite if self.read_only => {
+                    Some(Action::SetStatus(
+                        "✗ Read-only, another passepartui instance uses this store".to_string(),
+                    ))
+                }
+                PasswordAction::Insert | PasswordAction::Overwrite => {
+                    let pass_id = self.insert_popup.pass_id();
+                    let overwrite = action == PasswordAction::Overwrite;
+                    if let Some(message) = invalid_pass_id(&pass_id) {
+                        Some(Action::SetStatus(format!("✗ {message}")))
+                    } else if !overwrite
+                        && self.store.passwords.iter().any(|info| info.id == pass_id)
+                    {
+                        self.open_dialog(
+                            Dialog::new(
+                                "Overwrite entry",
+                                format!("{pass_id} already exists, overwrite it?"),
+                            )
+                            .button(
+                                "Overwrite",
+                                'y',
+                                Action::Password(PasswordAction::Overwrite),
+                            )
+                            .cancel_button("Cancel"),
+                        );
+                        None
+                    } else if let Some(completion_beacon) = self.last_op.allows(&pass_id, "insert")
+                    {
+                        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                        let file_contents = self.insert_popup.file_contents();
+                        let backend = Arc::clone(&self.backend);
+                        let event_tx = self.event_tx.clone();
+
+                        let future = async move {
+                            let result = if overwrite {
+                                backend.overwrite(&pass_id, &file_contents)
+                            } else {
+                                insert_entry(&*backend, &pass_id, &file_path, &file_contents)
+                            };
+                            match result {
+                                Ok(()) => event_tx
+                                    .send(PasswordEvent::EntryCreated { pass_id })
+                                    .expect("receiver deallocated"),
+                                Err(e) => event_tx
+                                    .send(PasswordEvent::Status(Err(e)))
+                                    .expect("receiver deallocated"),
+                            }
+                            let _ = completion_beacon.send(1);
+                        };
+
+                        if self.tty_pinentry {
+                            block_on(future);
+                            Some(Action::Redraw)
+                        } else {
+                            self.pool.spawn_ok(future);
+                            Some(Action::SetStatus("⧗ Creating entry...".to_string()))
+                        }
+                    } else {
+                        None
+                    }
+                }
+                PasswordAction::CopyGenerated => {
+                    match self
+                        .backend
+                        .copy(self.generator_popup.preview(), Content::Generated)
+                    {
+                        Ok(()) => Some(Action::SetStatus(copied_message(
+                            "Generated password",
+                            self.config.clear_after,
+                        ))),
+                        Err(e) => Some(Action::ReportError(e.to_string())),
+                    }
+                }
+                PasswordAction::Generate if self.read_only => Some(Action::SetStatus(
+                    "✗ Read-only, another passepartui instance uses this store".to_string(),
+                )),
+                PasswordAction::Generate => {
+                    let pass_id = self.generator_popup.pass_id();
+                    if let Some(message) = invalid_pass_id(&pass_id) {
+                        Some(Action::SetStatus(format!("✗ {message}")))
+                    } else if self.store.passwords.iter().any(|info| info.id == pass_id) {
+                        Some(Action::SetStatus(format!("✗ {pass_id} already exists")))
+                    } else if let Some(completion_beacon) =
+                        self.last_op.allows(&pass_id, "generate")
+                    {
+                        let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                        let options = *self.generator_popup.options();
+                        let backend = Arc::clone(&self.backend);
+                        let event_tx = self.event_tx.clone();
+
+                        let future = async move {
+                            match generate_entry(&*backend, &pass_id, &file_path, &options) {
+                                Ok(()) => event_tx
+                                    .send(PasswordEvent::EntryCreated { pass_id })
+                                    .expect("receiver deallocated"),
+                                Err(e) => event_tx
+                                    .send(PasswordEvent::Status(Err(e)))
+                                    .expect("receiver deallocated"),
+                            }
+                            let _ = completion_beacon.send(1);
+                        };
+
+                        if self.tty_pinentry {
+                            block_on(future);
+                            Some(Action::Redraw)
+                        } else {
+                            self.pool.spawn_ok(future);
+                            Some(Action::SetStatus("⧗ Generating entry...".to_string()))
+                        }
+                    } else {
+                        None
+                    }
+                }
+                PasswordAction::Archive if self.read_only => Some(Action::SetStatus(
+                    "✗ Read-only, another passepartui instance uses this store".to_string(),
+                )),
+                PasswordAction::Archive => self.archive_entry(),
+                PasswordAction::Lock => self.lock(),
+                PasswordAction::RunCommand => {
+                    self.app_state.overlay = OverlayState::Inactive;
+                    let entry_command = self.commands_popup.selected_command().cloned();
+                    match (self.get_selected_info(), entry_command) {
+                        (Some(info), Some(entry_command)) => {
+                            let pass_id = info.id.clone();
+                            if let Some(completion_beacon) =
+                                self.last_op.allows(&pass_id, "command")
+                            {
+                                let file_path =
+                                    self.store.store_dir.join(format!("{}.gpg", pass_id));
+                                let backend = Arc::clone(&self.backend);
+                                let event_tx = self.event_tx.clone();
+                                let status_message = format!("⧗ Running {}...", entry_command.name);
+
+                                let future = async move {
+                                    let event = match run_entry_command(
+                                        &*backend,
+                                        &file_path,
+                                        &pass_id,
+                                        &entry_command,
+                                    ) {
+                                        Ok(message) => PasswordEvent::Status(Ok(Some(message))),
+                                        Err(e) => PasswordEvent::Status(Err(e)),
+                                    };
+                                    event_tx.send(event).expect("receiver deallocated");
+                                    let _ = completion_beacon.send(1);
+                                };
+
+                                if self.tty_pinentry {
+                                    block_on(future);
+                                    Some(Action::Redraw)
+                                } else {
+                                    self.pool.spawn_ok(future);
+                                    Some(Action::SetStatus(status_message))
+                                }
+                            } else {
+                                None
+                            }
+                        }
+                        (None, _) => Some(Action::SetStatus("No entry selected".to_string())),
+                        (_, None) => Some(Action::SetStatus("No commands configured".to_string())),
+                    }
+                }
+                PasswordAction::OpenUrl | PasswordAction::OpenUrlAndCopyLogin => {
                     if let Some(info) = self.get_selected_info() {
                         let pass_id = info.id.clone();
-                        if let Some(completion_beacon) =
-                            self.last_op.allows(&pass_id, "copy_password")
-                        {
+                        let with_login = action == PasswordAction::OpenUrlAndCopyLogin;
+                        if let Some(completion_beacon) = self.last_op.allows(&pass_id, "open_url") {
                             let file_path = self.store.store_dir.join(format!("{}.gpg", pass_id));
+                            let backend = Arc::clone(&self.backend);
                             let event_tx = self.event_tx.clone();
 
                             let future = async move {
-                                let event = match passepartout::generate_otp(&file_path) {
-                                    Ok(otp) => PasswordEvent::OneTimePassword { pass_id, otp },
+                                let event = match open_entry_url(&*backend, &file_path, with_login)
+                                {
+                                    Ok(message) => PasswordEvent::Status(Ok(Some(message))),
                                     Err(e) => PasswordEvent::Status(Err(e)),
                                 };
                                 event_tx.send(event).expect("receiver deallocated");
@@ -465,7 +3976,7 @@ impl Component for Dashboard<'_> {
                                 Some(Action::Redraw)
                             } else {
                                 self.pool.spawn_ok(future);
-                                let status_message = "⧗ Fetching one-time password...".to_string();
+                                let status_message = "⧗ Opening URL...".to_string();
                                 Some(Action::SetStatus(status_message))
                             }
                         } else {
@@ -479,10 +3990,144 @@ impl Component for Dashboard<'_> {
             },
             Action::Navigation(action) => {
                 match action {
+                    NavigationAction::FocusNext => {
+                        if let Some(target) = self.focus_target() {
+                            target.focus_next();
+                        }
+                        None
+                    }
+                    NavigationAction::FocusPrevious => {
+                        if let Some(target) = self.focus_target() {
+                            target.focus_previous();
+                        }
+                        None
+                    }
+                    NavigationAction::PressFocused => self
+                        .focus_target()
+                        .and_then(|target| target.press_focused()),
+                    NavigationAction::Quit if self.push_on_quit => self.push_before_quit(),
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::File => {
+                        self.file_popup.next_line();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::File => {
+                        self.file_popup.previous_line();
+                        None
+                    }
+                    NavigationAction::Down
+                        if self.app_state.overlay == OverlayState::RecoveryCodes =>
+                    {
+                        self.recovery_codes_popup.next();
+                        None
+                    }
+                    NavigationAction::Up
+                        if self.app_state.overlay == OverlayState::RecoveryCodes =>
+                    {
+                        self.recovery_codes_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::Audit => {
+                        self.audit_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::Audit => {
+                        self.audit_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::OtpAudit => {
+                        self.otp_audit_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::OtpAudit => {
+                        self.otp_audit_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down
+                        if self.app_state.overlay == OverlayState::Duplicates =>
+                    {
+                        self.duplicates_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::Duplicates => {
+                        self.duplicates_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::Strength => {
+                        self.strength_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::Strength => {
+                        self.strength_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::Help => {
+                        self.help_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::Help => {
+                        self.help_popup.previous();
+                        None
+                    }
+                    NavigationAction::PageDown if self.app_state.overlay == OverlayState::Help => {
+                        self.help_popup.next_page();
+                        None
+                    }
+                    NavigationAction::PageUp if self.app_state.overlay == OverlayState::Help => {
+                        self.help_popup.previous_page();
+                        None
+                    }
+                    NavigationAction::Top if self.app_state.overlay == OverlayState::Help => {
+                        self.help_popup.top();
+                        None
+                    }
+                    NavigationAction::Bottom if self.app_state.overlay == OverlayState::Help => {
+                        self.help_popup.bottom();
+                        None
+                    }
+                    NavigationAction::Down
+                        if self.app_state.overlay == OverlayState::SessionLog =>
+                    {
+                        self.session_log_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::SessionLog => {
+                        self.session_log_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::History => {
+                        self.history_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::History => {
+                        self.history_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::Conflicts => {
+                        self.conflicts_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::Conflicts => {
+                        self.conflicts_popup.previous();
+                        None
+                    }
+                    NavigationAction::Down if self.app_state.overlay == OverlayState::Commands => {
+                        self.commands_popup.next();
+                        None
+                    }
+                    NavigationAction::Up if self.app_state.overlay == OverlayState::Commands => {
+                        self.commands_popup.previous();
+                        None
+                    }
+                    NavigationAction::Select(index)
+                        if self.app_state.overlay == OverlayState::Commands =>
+                    {
+                        self.commands_popup.select(index);
+                        None
+                    }
                     NavigationAction::Down => match self.app_state.main {
                         MainState::Secrets => {
                             self.next(1);
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.next(1);
@@ -492,7 +4137,7 @@ impl Component for Dashboard<'_> {
                     NavigationAction::Up => match self.app_state.main {
                         MainState::Secrets => {
                             self.previous(1);
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.previous(1);
@@ -502,7 +4147,7 @@ impl Component for Dashboard<'_> {
                     NavigationAction::PageDown => match self.app_state.main {
                         MainState::Secrets => {
                             self.next(10);
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.next(10);
@@ -512,7 +4157,7 @@ impl Component for Dashboard<'_> {
                     NavigationAction::PageUp => match self.app_state.main {
                         MainState::Secrets => {
                             self.previous(10);
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.previous(10);
@@ -522,7 +4167,7 @@ impl Component for Dashboard<'_> {
                     NavigationAction::Top => match self.app_state.main {
                         MainState::Secrets => {
                             self.top_row();
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.top_row();
@@ -532,7 +4177,7 @@ impl Component for Dashboard<'_> {
                     NavigationAction::Bottom => match self.app_state.main {
                         MainState::Secrets => {
                             self.bottom_row();
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.bottom_row();
@@ -542,13 +4187,40 @@ impl Component for Dashboard<'_> {
                     NavigationAction::Select(i) => match self.app_state.main {
                         MainState::Secrets => {
                             self.select_entry(i);
-                            Some(Action::Navigation(NavigationAction::Preview))
+                            self.follow_selection()
                         }
                         _ => {
                             self.select_entry(i);
                             None
                         }
                     },
+                    NavigationAction::JumpToLetter(letter) => {
+                        let letter = letter.to_lowercase().to_string();
+                        let position = self.password_subset.iter().position(|&idx| {
+                            self.store.passwords[idx]
+                                .id
+                                .to_lowercase()
+                                .starts_with(&letter)
+                        });
+                        match position {
+                            Some(i) => {
+                                let from = self.selected_pass_id();
+                                self.select_entry(i);
+                                self.record_jump(from);
+                                if self.app_state.main == MainState::Secrets {
+                                    self.follow_selection()
+                                } else {
+                                    Some(Action::ResetStatus)
+                                }
+                            }
+                            None => Some(Action::SetStatus(format!(
+                                "No entry starting with \"{letter}\""
+                            ))),
+                        }
+                    }
+                    NavigationAction::SetMark(letter) => self.set_mark(letter),
+                    NavigationAction::JumpToMark(letter) => self.jump_to_mark(letter),
+                    NavigationAction::JumpBack => self.jump_back(),
                     NavigationAction::SelectAndFetch(i) => {
                         self.app_state.main = MainState::Secrets;
                         self.show_pass_secrets();
@@ -566,19 +4238,241 @@ impl Component for Dashboard<'_> {
                         Some(Action::Password(PasswordAction::Fetch))
                     }
                     // Open search popup
+                    NavigationAction::Search if self.app_state.search == SearchState::Goto => None,
                     NavigationAction::Search => {
                         self.app_state.search = SearchState::Active;
+                        self.typed_search = false;
                         self.search_field.resume();
                         None
                     }
+                    NavigationAction::SelectionRested => {
+                        self.follow_timer_pending = false;
+                        match self.follow_deadline {
+                            // Moved again in the meantime
+                            Some(deadline) if deadline.remaining().is_some() => {
+                                self.start_follow_timer();
+                                None
+                            }
+                            Some(_) => {
+                                self.follow_deadline = None;
+                                (self.app_state.main == MainState::Secrets)
+                                    .then_some(Action::Password(PasswordAction::Fetch))
+                            }
+                            None => None,
+                        }
+                    }
+                    NavigationAction::GrowDetails => {
+                        self.resize_details(true);
+                        None
+                    }
+                    NavigationAction::ShrinkDetails => {
+                        self.resize_details(false);
+                        None
+                    }
+                    NavigationAction::SwitchLayout => {
+                        let status = match self.details_position {
+                            DetailsPosition::Bottom => {
+                                self.details_position = DetailsPosition::Right;
+                                "Details pane on the right of the table"
+                            }
+                            DetailsPosition::Right => {
+                                self.details_position = DetailsPosition::Bottom;
+                                "Details pane below the table"
+                            }
+                        };
+                        Some(Action::SetStatus(status.to_string()))
+                    }
+                    NavigationAction::ToggleDetails => {
+                        self.details_collapsed = !self.details_collapsed;
+                        let status = if self.details_collapsed {
+                            "Details pane collapsed, (z) shows it again"
+                        } else {
+                            "Details pane shown"
+                        };
+                        Some(Action::SetStatus(status.to_string()))
+                    }
                     // Open help popup
                     NavigationAction::Help => {
                         self.app_state.overlay = OverlayState::Help;
+                        self.help_popup.top();
                         None
                     }
                     // Open file popup and fetch details
                     NavigationAction::File => {
                         self.app_state.overlay = OverlayState::File;
+                        self.fetch_last_change();
+                        Some(Action::Password(PasswordAction::Fetch))
+                    }
+                    NavigationAction::Insert if self.read_only => Some(Action::SetStatus(
+                        "✗ Read-only, another passepartui instance uses this store".to_string(),
+                    )),
+                    NavigationAction::ErrorDetails if self.last_error.is_some() => {
+                        let message = self.last_error.take().unwrap_or_default();
+                        self.open_dialog(
+                            Dialog::new("Error details", message)
+                                .hint("The message is discarded when the dialog is closed")
+                                .cancel_button("Close"),
+                        );
+                        Some(Action::ResetStatus)
+                    }
+                    NavigationAction::ErrorDetails => {
+                        Some(Action::SetStatus("No error to show".to_string()))
+                    }
+                    NavigationAction::DismissBanner => {
+                        self.banner = None;
+                        None
+                    }
+                    NavigationAction::Move if self.read_only => Some(Action::SetStatus(
+                        "✗ Read-only, another passepartui instance uses this store".to_string(),
+                    )),
+                    // Prompt for the new path of the selected entry
+                    NavigationAction::Move => {
+                        if let Some(info) = self.get_selected_info() {
+                            let pass_id = info.id.clone();
+                            let paths = self.store_paths();
+                            self.prompt_popup.open(
+                                PromptPurpose::Move(pass_id.clone()),
+                                &pass_id,
+                                validate_new_path,
+                                paths,
+                            );
+                            self.app_state.overlay = OverlayState::Prompt;
+                        }
+                        None
+                    }
+                    NavigationAction::Goto => {
+                        self.start_goto();
+                        None
+                    }
+                    NavigationAction::NewFolder if self.read_only => Some(Action::SetStatus(
+                        "✗ Read-only, another passepartui instance uses this store".to_string(),
+                    )),
+                    NavigationAction::NewFolder => {
+                        let text = match &self.scope {
+                            Some(folder) => format!("{folder}/"),
+                            None => String::new(),
+                        };
+                        let paths = self.store_paths();
+                        self.prompt_popup.open(
+                            PromptPurpose::NewFolder,
+                            &text,
+                            validate_folder,
+                            paths,
+                        );
+                        self.app_state.overlay = OverlayState::Prompt;
+                        None
+                    }
+                    NavigationAction::LookupUrl => {
+                        self.prompt_popup.open(
+                            PromptPurpose::LookupUrl,
+                            "",
+                            validate_url,
+                            Vec::new(),
+                        );
+                        self.app_state.overlay = OverlayState::Prompt;
+                        None
+                    }
+                    NavigationAction::Generator => {
+                        self.generator_popup.reset(self.scope.as_deref());
+                        self.app_state.overlay = OverlayState::Generator;
+                        None
+                    }
+                    // Open form for a new entry in the current folder
+                    NavigationAction::Insert => {
+                        self.insert_popup.reset(self.scope.as_deref());
+                        let template = self.config.template(&self.insert_popup.pass_id());
+                        self.insert_popup.set_template(template);
+                        self.app_state.overlay = OverlayState::Insert;
+                        None
+                    }
+                    // Open Wi-Fi QR code popup and fetch details
+                    NavigationAction::WifiQr => {
+                        self.app_state.overlay = OverlayState::WifiQr;
+                        Some(Action::Password(PasswordAction::Fetch))
+                    }
+                    // Open QR code popup for the one-time passwords and the password
+                    NavigationAction::Qr => {
+                        self.app_state.overlay = OverlayState::Qr;
+                        Some(Action::Password(PasswordAction::Fetch))
+                    }
+                    NavigationAction::Audit if self.password_table.dates_pending => Some(
+                        Action::SetStatus("⧗ Password store is being scanned...".to_string()),
+                    ),
+                    NavigationAction::Audit => {
+                        let entries = self.stale_entries();
+                        let period_days = self.config.audit_period.as_secs() / (24 * 60 * 60);
+                        self.audit_popup.set_entries(
+                            entries,
+                            period_days,
+                            &self.config.archive_folder,
+                        );
+                        self.app_state.overlay = OverlayState::Audit;
+                        None
+                    }
+                    NavigationAction::OtpAudit => {
+                        self.open_otp_audit();
+                        None
+                    }
+                    NavigationAction::Duplicates => {
+                        self.open_duplicates();
+                        None
+                    }
+                    NavigationAction::Strength => {
+                        self.open_strength_audit();
+                        None
+                    }
+                    NavigationAction::SessionLog if !self.action_log.is_enabled() => {
+                        Some(Action::SetStatus(
+                            "✗ The action log is off, set `action_log = true` under [security]"
+                                .to_string(),
+                        ))
+                    }
+                    NavigationAction::SessionLog => {
+                        self.session_log_popup.set_lines(self.action_log.session());
+                        self.app_state.overlay = OverlayState::SessionLog;
+                        None
+                    }
+                    NavigationAction::ExportSessionLog => {
+                        let text = dirs::home_dir()
+                            .map(|home| {
+                                home.join("passepartui-session.log")
+                                    .to_string_lossy()
+                                    .into_owned()
+                            })
+                            .unwrap_or_default();
+                        self.prompt_popup.open(
+                            PromptPurpose::ExportSessionLog,
+                            &text,
+                            validate_export_path,
+                            Vec::new(),
+                        );
+                        self.app_state.overlay = OverlayState::Prompt;
+                        None
+                    }
+                    NavigationAction::History => {
+                        if let Some(info) = self.get_selected_info() {
+                            let pass_id = info.id.clone();
+                            self.history_popup.open(&pass_id);
+                            self.app_state.overlay = OverlayState::History;
+                            self.fetch_history(pass_id);
+                            None
+                        } else {
+                            Some(Action::SetStatus("No entry selected".to_string()))
+                        }
+                    }
+                    NavigationAction::Commands => {
+                        if let Some(info) = self.get_selected_info() {
+                            let pass_id = info.id.clone();
+                            self.commands_popup.set_pass_id(&pass_id);
+                            self.app_state.overlay = OverlayState::Commands;
+                            None
+                        } else {
+                            Some(Action::SetStatus("No entry selected".to_string()))
+                        }
+                    }
+                    // Open recovery codes popup and fetch details
+                    NavigationAction::RecoveryCodes => {
+                        self.app_state.overlay = OverlayState::RecoveryCodes;
                         Some(Action::Password(PasswordAction::Fetch))
                     }
                     NavigationAction::Leave => match self.app_state {
@@ -589,6 +4483,11 @@ impl Component for Dashboard<'_> {
                         } => {
                             if self.search_field.is_empty() {
                                 self.app_state.search = SearchState::Inactive;
+                            } else if self.typed_search {
+                                self.search_field.reset();
+                                self.stop_content_search();
+                                self.reset_password_filter();
+                                self.app_state.search = SearchState::Inactive;
                             } else {
                                 self.search_field.suspend();
                                 self.app_state.search = SearchState::Suspended;
@@ -597,42 +4496,126 @@ impl Component for Dashboard<'_> {
                         }
                         app::State {
                             main: _,
-                            search: SearchState::Suspended,
-                            overlay: OverlayState::Inactive,
+                            search: SearchState::Goto,
+                            overlay: OverlayState::Inactive,
+                        } => {
+                            self.stop_goto();
+                            None
+                        }
+                        app::State {
+                            main: _,
+                            search: SearchState::Suspended,
+                            overlay: OverlayState::Inactive,
+                        } => {
+                            self.search_field.reset();
+                            self.stop_content_search();
+                            self.reset_password_filter();
+                            self.app_state.search = SearchState::Inactive;
+                            None
+                        }
+                        app::State {
+                            overlay: OverlayState::Inactive,
+                            ..
+                        } if self.banner.is_some() => {
+                            self.banner = None;
+                            None
+                        }
+                        _ => None,
+                    },
+                    NavigationAction::Back => match self.app_state {
+                        app::State {
+                            main: MainState::Secrets,
+                            search: SearchState::Inactive | SearchState::Suspended,
+                            overlay: OverlayState::Inactive,
+                        } => Some(Action::Navigation(NavigationAction::Preview)),
+                        app::State {
+                            main: MainState::Preview,
+                            search: SearchState::Inactive | SearchState::Suspended,
+                            overlay: OverlayState::Inactive,
+                        } => {
+                            self.app_state.main = MainState::Table;
+                            None
+                        }
+                        app::State {
+                            main: _,
+                            search: _,
+                            overlay: OverlayState::WifiQr | OverlayState::Qr,
+                        } => {
+                            // The QR codes contain the key of the network or
+                            // secrets of the entry
+                            self.qr_popup.reset_content();
+                            self.app_state.overlay = OverlayState::Inactive;
+                            None
+                        }
+                        app::State {
+                            main: _,
+                            search: _,
+                            overlay: OverlayState::Dialog,
+                        } => {
+                            let (overlay, _) = self.dialog_popup.close(None);
+                            self.app_state.overlay = overlay;
+                            None
+                        }
+                        app::State {
+                            main: _,
+                            search: _,
+                            overlay: OverlayState::History,
+                        } => {
+                            // The changes contain secrets of the entry
+                            if !self.history_popup.close_diff() {
+                                self.history_popup.reset_content();
+                                self.app_state.overlay = OverlayState::Inactive;
+                            }
+                            None
+                        }
+                        app::State {
+                            main: _,
+                            search: _,
+                            overlay: OverlayState::Help,
+                        } => {
+                            self.app_state.overlay = OverlayState::Inactive;
+                            None
+                        }
+                        app::State {
+                            main: _,
+                            search: _,
+                            overlay: OverlayState::OtpAudit,
                         } => {
-                            self.search_field.reset();
-                            self.reset_password_filter();
-                            self.app_state.search = SearchState::Inactive;
+                            self.stop_otp_check();
+                            self.app_state.overlay = OverlayState::Inactive;
                             None
                         }
-                        _ => None,
-                    },
-                    NavigationAction::Back => match self.app_state {
-                        app::State {
-                            main: MainState::Secrets,
-                            search: SearchState::Inactive | SearchState::Suspended,
-                            overlay: OverlayState::Inactive,
-                        } => Some(Action::Navigation(NavigationAction::Preview)),
                         app::State {
-                            main: MainState::Preview,
-                            search: SearchState::Inactive | SearchState::Suspended,
-                            overlay: OverlayState::Inactive,
+                            main: _,
+                            search: _,
+                            overlay: OverlayState::Duplicates,
                         } => {
-                            self.app_state.main = MainState::Table;
+                            self.stop_duplicate_check();
+                            self.app_state.overlay = OverlayState::Inactive;
                             None
                         }
                         app::State {
                             main: _,
                             search: _,
-                            overlay: OverlayState::Help,
+                            overlay: OverlayState::Strength,
                         } => {
+                            self.stop_strength_check();
                             self.app_state.overlay = OverlayState::Inactive;
                             None
                         }
                         app::State {
                             main: _,
                             search: _,
-                            overlay: OverlayState::File,
+                            overlay:
+                                OverlayState::File
+                                | OverlayState::RecoveryCodes
+                                | OverlayState::Commands
+                                | OverlayState::Audit
+                                | OverlayState::SessionLog
+                                | OverlayState::Conflicts
+                                | OverlayState::Insert
+                                | OverlayState::Prompt
+                                | OverlayState::Generator,
                         } => {
                             self.app_state.overlay = OverlayState::Inactive;
                             None
@@ -643,23 +4626,34 @@ impl Component for Dashboard<'_> {
                 }
             }
             Action::Search(action) => match action {
+                SearchAction::Type(_) if !self.config.type_to_search => None,
+                SearchAction::Type(character) => {
+                    self.search_field.reset();
+                    self.app_state.search = SearchState::Active;
+                    self.typed_search = true;
+                    Some(Action::Search(SearchAction::Insert(character)))
+                }
                 SearchAction::Insert(character) => {
                     self.search_field.insert(character);
+                    self.stop_content_search();
                     self.filter_passwords();
                     None
                 }
                 SearchAction::RemoveLeft => {
                     if self.search_field.remove_left() {
+                        self.stop_content_search();
                         self.filter_passwords();
                     }
                     None
                 }
                 SearchAction::RemoveRight => {
                     if self.search_field.remove_right() {
+                        self.stop_content_search();
                         self.filter_passwords();
                     }
                     None
                 }
+                SearchAction::SearchContents => self.start_content_search(),
                 SearchAction::MoveLeft => {
                     self.search_field.move_left();
                     None
@@ -677,6 +4671,458 @@ impl Component for Dashboard<'_> {
                     None
                 }
             },
+            Action::Store(StoreAction::Refresh) => self.refresh_store(),
+            Action::Store(StoreAction::Reconcile) => self.reconcile_store(),
+            Action::Store(StoreAction::Changed) => self.store_changed(),
+            Action::Store(StoreAction::Created(pass_id)) => {
+                if matches!(
+                    self.app_state.overlay,
+                    OverlayState::Insert | OverlayState::Generator
+                ) {
+                    self.app_state.overlay = OverlayState::Inactive;
+                }
+                self.details_cache.invalidate(&pass_id);
+                self.log_action(&pass_id, "saved");
+                // Saving counts as using it, the change is your own
+                self.record_use_of(&pass_id);
+                let message = format!("Created {pass_id}");
+                self.select_after_scan = Some((pass_id, message));
+                self.refresh_store()
+            }
+            Action::Store(StoreAction::Moved { from, to }) => {
+                if self.app_state.overlay == OverlayState::Prompt {
+                    self.app_state.overlay = OverlayState::Inactive;
+                }
+                self.details_cache.invalidate(&from);
+                if let Some(position) = self.pinned.iter().position(|id| *id == from) {
+                    self.pinned[position] = to.clone();
+                    let _ = data::write_lines(self.data_dir.as_deref(), PINNED_FILE, &self.pinned);
+                }
+                self.usage.rename(&from, &to);
+                let file_name = store_file_name(&self.store.store_dir, USAGE_EXTENSION);
+                let _ = self.usage.save(self.data_dir.as_deref(), &file_name);
+                self.move_remembered(&from, Some(&to));
+                self.save_remembered();
+                self.log_action(&from, &format!("moved to {to}"));
+                let message = format!("Moved {from} to {to}");
+                self.select_after_scan = Some((to, message));
+                self.refresh_store()
+            }
+            Action::Store(StoreAction::Cleaned { moved, removed }) => {
+                for (from, to) in &moved {
+                    self.log_action(from, &format!("moved to {to}"));
+                    self.details_cache.invalidate(from);
+                    self.usage.rename(from, to);
+                    self.move_remembered(from, Some(to));
+                    if let Some(position) = self.pinned.iter().position(|id| id == from) {
+                        self.pinned[position] = to.clone();
+                    }
+                }
+                for pass_id in &removed {
+                    self.log_action(pass_id, "deleted");
+                    self.details_cache.invalidate(pass_id);
+                    self.usage.remove(pass_id);
+                    self.move_remembered(pass_id, None);
+                    self.pinned.retain(|id| id != pass_id);
+                }
+                let _ = data::write_lines(self.data_dir.as_deref(), PINNED_FILE, &self.pinned);
+                let file_name = store_file_name(&self.store.store_dir, USAGE_EXTENSION);
+                let _ = self.usage.save(self.data_dir.as_deref(), &file_name);
+                self.save_remembered();
+
+                let mut cleaned: Vec<String> = moved.iter().map(|(from, _)| from.clone()).collect();
+                let message = if removed.is_empty() {
+                    format!(
+                        "Moved {} entries to {}/",
+                        cleaned.len(),
+                        self.config.archive_folder
+                    )
+                } else {
+                    format!("Deleted {} entries", removed.len())
+                };
+                cleaned.extend(removed);
+                self.audit_popup.remove_entries(&cleaned);
+                self.duplicates_popup.remove_entries(&cleaned);
+                // Keep the selection, the status is shown once the store is
+                // scanned again
+                let pass_id = self
+                    .get_selected_info()
+                    .map(|info| {
+                        moved
+                            .iter()
+                            .find(|(from, _)| *from == info.id)
+                            .map_or_else(|| info.id.clone(), |(_, to)| to.clone())
+                    })
+                    .unwrap_or_default();
+                self.select_after_scan = Some((pass_id, message));
+                self.refresh_store()
+            }
+            Action::Store(StoreAction::Aliased { target, pass_ids }) => {
+                for pass_id in &pass_ids {
+                    self.log_action(pass_id, &format!("replaced with an alias of {target}"));
+                    self.details_cache.invalidate(pass_id);
+                    self.aliases.record(pass_id, &target);
+                }
+                self.password_table.markers.aliases = self.aliases.pass_ids();
+                self.rebuild_table();
+                self.save_remembered();
+                self.duplicates_popup.remove_entries(&pass_ids);
+                let message = format!("Merged {} entries into {target}", pass_ids.len());
+                // Keep the selection, the status is shown once the store is
+                // scanned again
+                let pass_id = self
+                    .get_selected_info()
+                    .map(|info| info.id.clone())
+                    .unwrap_or_default();
+                self.select_after_scan = Some((pass_id, message));
+                self.refresh_store()
+            }
+            Action::Store(
+                action @ (StoreAction::GitPull
+                | StoreAction::GitPullRebase
+                | StoreAction::GitPush
+                | StoreAction::GitStatus),
+            ) => self.run_git(action),
+            Action::Store(StoreAction::Pulled(message)) => {
+                // Keep the selection, the status is shown once the store is
+                // scanned again
+                let pass_id = self
+                    .get_selected_info()
+                    .map(|info| info.id.clone())
+                    .unwrap_or_default();
+                self.select_after_scan = Some((pass_id, message));
+                self.refresh_store()
+            }
+            Action::Store(StoreAction::Conflicts(conflicts)) if conflicts.is_empty() => {
+                if self.app_state.overlay == OverlayState::Conflicts {
+                    self.app_state.overlay = OverlayState::Inactive;
+                }
+                None
+            }
+            Action::Store(StoreAction::Conflicts(conflicts)) => {
+                let message = match conflicts.len() {
+                    1 => "✗ Pulling left a conflict, keep a version of the file".to_string(),
+                    count => {
+                        format!("✗ Pulling left {count} conflicts, keep a version of each file")
+                    }
+                };
+                self.conflicts_popup.set_conflicts(conflicts);
+                if self.app_state.overlay != OverlayState::Dialog {
+                    self.app_state.overlay = OverlayState::Conflicts;
+                }
+                Some(Action::SetStatus(message))
+            }
+            Action::Conflict(action) => match action {
+                ConflictAction::KeepLocal => self.resolve_selected_conflict(Some(true)),
+                ConflictAction::KeepRemote => self.resolve_selected_conflict(Some(false)),
+                ConflictAction::Edit => {
+                    let pass_id = self
+                        .conflicts_popup
+                        .selected_conflict()
+                        .and_then(Conflict::pass_id)
+                        .map(String::from);
+                    match pass_id {
+                        Some(pass_id) => {
+                            let result = self.backend.edit(&pass_id);
+                            self.details_cache.invalidate(&pass_id);
+                            match result {
+                                Ok(()) => {
+                                    self.resolve_selected_conflict(None);
+                                }
+                                Err(e) => self
+                                    .event_tx
+                                    .send(PasswordEvent::Status(Err(e)))
+                                    .expect("receiver deallocated"),
+                            }
+                            // The editor used the terminal
+                            Some(Action::Redraw)
+                        }
+                        None => Some(Action::SetStatus(
+                            "✗ Only entries can be edited, keep a version of the file instead"
+                                .to_string(),
+                        )),
+                    }
+                }
+            },
+            Action::Strength(action) => match action {
+                StrengthAction::Open => {
+                    let pass_id = self.strength_popup.selected_pass_id().map(String::from);
+                    pass_id.and_then(|pass_id| {
+                        self.stop_strength_check();
+                        self.app_state.overlay = OverlayState::Inactive;
+                        self.goto_entry(&pass_id)
+                    })
+                }
+            },
+            Action::OtpAudit(action) => match action {
+                OtpAuditAction::Open => {
+                    let pass_id = self.otp_audit_popup.selected_pass_id().map(String::from);
+                    pass_id.and_then(|pass_id| {
+                        self.stop_otp_check();
+                        self.app_state.overlay = OverlayState::Inactive;
+                        self.goto_entry(&pass_id)
+                    })
+                }
+            },
+            Action::Scan(action) => match action {
+                ScanAction::Decrypt(audit) => self.confirm_scan(audit),
+                ScanAction::DecryptConfirmed(Audit::Otp) => self.start_otp_check(),
+                ScanAction::DecryptConfirmed(Audit::Duplicates) => self.start_duplicate_check(),
+                ScanAction::DecryptConfirmed(Audit::Strength) => self.start_strength_check(),
+                ScanAction::Scanned {
+                    scan_id,
+                    pass_id,
+                    finding,
+                } => self.entry_scanned(scan_id, pass_id, finding),
+                ScanAction::Failed { scan_id, message } => self.entry_scan_failed(scan_id, message),
+            },
+            Action::Duplicates(action) => match action {
+                DuplicatesAction::Merge
+                | DuplicatesAction::MergeAliases
+                | DuplicatesAction::MergeArchive
+                | DuplicatesAction::MergeDelete
+                    if self.read_only =>
+                {
+                    Some(Action::SetStatus(
+                        "✗ Read-only, another passepartui instance uses this store".to_string(),
+                    ))
+                }
+                DuplicatesAction::Merge => self.confirm_merge(),
+                DuplicatesAction::MergeAliases
+                | DuplicatesAction::MergeArchive
+                | DuplicatesAction::MergeDelete => self.merge_duplicates(action),
+            },
+            Action::History(action) => match action {
+                HistoryAction::MarkBase => {
+                    self.history_popup.toggle_base();
+                    None
+                }
+                HistoryAction::Diff => self.compare_versions(false),
+                HistoryAction::Difftool => self.compare_versions(true),
+            },
+            Action::Audit(action) => match action {
+                AuditAction::ToggleMark => {
+                    self.audit_popup.toggle_mark();
+                    None
+                }
+                AuditAction::ToggleMarkAll => {
+                    self.audit_popup.toggle_mark_all();
+                    None
+                }
+                AuditAction::Archive | AuditAction::Delete | AuditAction::FixPermissions
+                    if self.read_only =>
+                {
+                    Some(Action::SetStatus(
+                        "✗ Read-only, another passepartui instance uses this store".to_string(),
+                    ))
+                }
+                AuditAction::FixPermissions if self.audit_popup.permissive().is_empty() => {
+                    Some(Action::SetStatus(
+                        "No files or folders are more open than the umask allows".to_string(),
+                    ))
+                }
+                AuditAction::FixPermissions => {
+                    let umask = pass::umask();
+                    let paths = self.audit_popup.permissive().to_vec();
+                    let status = match store::fix_permissions(&self.store.store_dir, &paths, umask)
+                    {
+                        Ok(fixed) => {
+                            format!("Removed the permissions {umask:03o} from {fixed} files and folders")
+                        }
+                        Err(e) => format!("✗ Failed to fix permissions: {e}"),
+                    };
+                    // Changing permissions leaves the folders unmodified,
+                    // the scan updates the remaining ones quietly
+                    self.store_changed();
+                    Some(Action::SetStatus(status))
+                }
+                AuditAction::Delete => {
+                    let count = self.audit_popup.marked_pass_ids().len();
+                    if count > 0 {
+                        self.open_dialog(
+                            Dialog::new(
+                                "Delete entries",
+                                format!("Delete {count} entries with pass rm?"),
+                            )
+                            .hint("Deleted entries can only be restored from git")
+                            .button("Delete", 'y', Action::Audit(AuditAction::DeleteConfirmed))
+                            .cancel_button("Cancel"),
+                        );
+                    }
+                    None
+                }
+                AuditAction::Archive | AuditAction::DeleteConfirmed => {
+                    let pass_ids = self.audit_popup.marked_pass_ids();
+                    let archive_folder = (action == AuditAction::Archive)
+                        .then(|| self.audit_popup.archive_folder().to_string());
+                    if pass_ids.is_empty() {
+                        None
+                    } else if let Some(completion_beacon) = self.last_op.allows("", "audit") {
+                        let store_dir = self.store.store_dir.clone();
+                        let backend = Arc::clone(&self.backend);
+                        let event_tx = self.event_tx.clone();
+                        let status_message = if archive_folder.is_some() {
+                            "⧗ Archiving entries..."
+                        } else {
+                            "⧗ Deleting entries..."
+                        };
+
+                        let future = async move {
+                            let (moved, removed, error) = clean_entries(
+                                &*backend,
+                                &store_dir,
+                                pass_ids,
+                                archive_folder.as_deref(),
+                            );
+                            event_tx
+                                .send(PasswordEvent::EntriesCleaned { moved, removed })
+                                .expect("receiver deallocated");
+                            if let Some(e) = error {
+                                event_tx
+                                    .send(PasswordEvent::Status(Err(e)))
+                                    .expect("receiver deallocated");
+                            }
+                            let _ = completion_beacon.send(1);
+                        };
+
+                        if self.tty_pinentry {
+                            block_on(future);
+                            Some(Action::Redraw)
+                        } else {
+                            self.pool.spawn_ok(future);
+                            Some(Action::SetStatus(status_message.to_string()))
+                        }
+                    } else {
+                        None
+                    }
+                }
+            },
+            Action::Form(FormAction::Complete) if self.app_state.search == SearchState::Goto => {
+                self.complete_goto();
+                None
+            }
+            Action::Form(FormAction::Submit) if self.app_state.search == SearchState::Goto => {
+                self.confirm_goto()
+            }
+            Action::Form(FormAction::Submit) if self.app_state.overlay == OverlayState::Prompt => {
+                self.submit_prompt()
+            }
+            Action::Form(action) if self.app_state.overlay == OverlayState::Prompt => {
+                self.prompt_popup.update(action);
+                None
+            }
+            Action::Form(action) if self.app_state.overlay == OverlayState::Generator => {
+                self.generator_popup.update(action);
+                None
+            }
+            Action::Form(action) => {
+                self.insert_popup.update(action);
+                // Follow the folder of the path with the template
+                let template = self.config.template(&self.insert_popup.pass_id());
+                self.insert_popup.set_template(template);
+                None
+            }
+            Action::Table(TableAction::TogglePin) => self.toggle_pin(),
+            Action::Table(TableAction::DragOver { from, to }) => {
+                match to.and_then(|to| self.drop_path(from, to)) {
+                    Some((_, to)) => Some(Action::SetStatus(format!("Drop to move to {to}"))),
+                    None => Some(Action::ResetStatus),
+                }
+            }
+            Action::Table(TableAction::Drop { .. } | TableAction::MoveConfirmed { .. })
+                if self.read_only =>
+            {
+                Some(Action::SetStatus(
+                    "✗ Read-only, another passepartui instance uses this store".to_string(),
+                ))
+            }
+            Action::Table(TableAction::Drop { from, to }) => self.confirm_drop(from, to),
+            Action::Table(TableAction::MoveConfirmed { from, to }) => self.spawn_move(from, to),
+            Action::Table(TableAction::CopyLogin(index)) => {
+                let login = self
+                    .password_subset
+                    .get(index)
+                    .map(|&idx| &self.store.passwords[idx].id)
+                    .and_then(|pass_id| self.password_table.logins.as_ref()?.get(pass_id));
+                match login.map(|login| self.backend.copy(login, Content::Login)) {
+                    Some(Ok(())) => {
+                        Some(Action::SetStatus("Login copied to clipboard".to_string()))
+                    }
+                    Some(Err(passepartout::Error::Clipboard(e))) => {
+                        Some(Action::ReportError(format!("Clipboard error: {e}")))
+                    }
+                    Some(Err(_)) => None,
+                    // Rows without a remembered login are selected instead
+                    None => Some(Action::Navigation(NavigationAction::SelectAndFetch(index))),
+                }
+            }
+            Action::Table(TableAction::Sort(column)) => {
+                let sort = self.password_table.sort.cycle(column);
+                self.password_table.sort = sort;
+                self.rebuild_table();
+                let order = if sort.descending {
+                    "descending"
+                } else {
+                    "ascending"
+                };
+                let message = match sort.column {
+                    Some(SortColumn::PassId) => format!("Sorted by name, {order}"),
+                    Some(SortColumn::Modified) => format!("Sorted by modification date, {order}"),
+                    None => "Sort order reset".to_string(),
+                };
+                Some(Action::SetStatus(message))
+            }
+            Action::Table(TableAction::ToggleArchive) => {
+                self.show_archive = !self.show_archive;
+                self.rebuild_table();
+                let message = if self.show_archive {
+                    "Showing archived entries"
+                } else {
+                    "Hiding archived entries"
+                };
+                Some(Action::SetStatus(message.to_string()))
+            }
+            Action::Table(TableAction::EnterFolder) => {
+                let folder = self
+                    .get_selected_info()
+                    .and_then(|info| info.id.rsplit_once('/'))
+                    .map(|(folder, _)| folder.to_string());
+                match folder {
+                    Some(folder) if self.scope.as_ref() != Some(&folder) => {
+                        self.set_scope(Some(folder))
+                    }
+                    _ => None,
+                }
+            }
+            Action::Table(TableAction::LeaveFolder) => match self.scope.clone() {
+                Some(folder) => {
+                    let parent = folder
+                        .rsplit_once('/')
+                        .map(|(parent, _)| parent.to_string());
+                    self.set_scope(parent)
+                }
+                None => None,
+            },
+            Action::Table(TableAction::WidenScope) => match self.scope {
+                Some(_) => self.set_scope(None),
+                None => None,
+            },
+            Action::Dialog(action) if self.app_state.overlay == OverlayState::Dialog => {
+                let index = match action {
+                    DialogAction::Press(index) => Some(index),
+                    DialogAction::Key(key) => self.dialog_popup.button_index(key),
+                };
+                // Other keys are ignored
+                index.and_then(|index| {
+                    let (overlay, action) = self.dialog_popup.close(Some(index));
+                    self.app_state.overlay = overlay;
+                    action
+                })
+            }
+            Action::DisplayGitStatus(summary) => {
+                self.status_bar.git_status = summary;
+                None
+            }
             Action::SetStatus(message) => {
                 self.status_bar.set_status(message);
                 None
@@ -685,19 +5131,104 @@ impl Component for Dashboard<'_> {
                 self.status_bar.reset_status();
                 None
             }
+            Action::ReportError(message) => {
+                let summary = status::redact(&message);
+                self.last_error = Some(message);
+                Some(Action::SetStatus(format!("✗ {summary} (e) Details")))
+            }
+            Action::DisplaySecrets { generation, .. } if generation != self.lock_generation => None,
+            Action::DisplayOneTimePassword { generation, .. }
+                if generation != self.lock_generation =>
+            {
+                None
+            }
+            Action::DisplayDiff { generation, .. } if generation != self.lock_generation => None,
             Action::DisplaySecrets {
                 pass_id,
                 file_contents,
+                modified,
+                target,
+                ..
+            } => {
+                self.status_bar.reset_status();
+                self.remember(&pass_id, file_contents.as_str(), target.as_deref());
+                self.password_details.target = target;
+                self.details_cache
+                    .insert(&pass_id, Arc::clone(&file_contents), modified);
+                self.update_pass_details(pass_id, file_contents.as_str(), modified)
+            }
+            Action::DisplayFileModified { pass_id, modified } => {
+                self.stale_check_pending = false;
+                let details = &mut self.password_details;
+                if details.pass_id.as_ref() == Some(&pass_id) {
+                    if let Some(fetched) = details.fetched_modified {
+                        details.stale = modified != Some(fetched);
+                    }
+                }
+                if details.stale {
+                    self.details_cache.invalidate(&pass_id);
+                }
+                None
+            }
+            Action::DisplayFetchError { pass_id, message } => {
+                self.details_cache.invalidate(&pass_id);
+                match self.get_selected_info() {
+                    Some(info) if pass_id == info.id => {
+                        self.password_details.fetch_error = Some(status::redact(&message));
+                    }
+                    _ => (),
+                }
+                Some(Action::ReportError(message))
+            }
+            Action::DisplayLastChange {
+                pass_id,
+                last_change,
+            } => {
+                match self.get_selected_info() {
+                    Some(info) if pass_id == info.id => {
+                        self.file_popup.set_last_change(last_change);
+                    }
+                    _ => (),
+                }
+                None
+            }
+            Action::DisplayHistory { pass_id, revisions } => {
+                self.history_popup.set_revisions(&pass_id, revisions);
+                None
+            }
+            Action::DisplayDiff {
+                pass_id,
+                old,
+                new,
+                lines,
+                ..
             } => {
                 self.status_bar.reset_status();
-                self.update_pass_details(pass_id, file_contents)
+                if self.app_state.overlay == OverlayState::History {
+                    self.history_popup.set_diff(&pass_id, old, new, lines);
+                }
+                None
             }
-            Action::DisplayOneTimePassword { pass_id, otp } => {
+            Action::DisplayOneTimePassword {
+                pass_id,
+                otp,
+                valid_until,
+                period,
+                ..
+            } => {
                 self.status_bar.reset_status();
                 match self.get_selected_info() {
                     Some(info) if pass_id == info.id => {
-                        self.password_details.one_time_password = Some(otp);
-                        None
+                        self.password_details.one_time_password.zeroize();
+                        self.password_details.one_time_password = Some(otp.clone());
+                        self.password_details.otp_window = Some((valid_until, period));
+                        // Only the first password after opening the entry, not
+                        // the ones following it when it expires
+                        if self.otp_auto_pending.take() == Some(pass_id.clone()) {
+                            self.deliver_otp(pass_id, otp)
+                        } else {
+                            None
+                        }
                     }
                     _ => None,
                 }
@@ -711,53 +5242,89 @@ impl Component for Dashboard<'_> {
 impl Widget for &mut Dashboard<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         self.area = Some(area);
+        self.sync_focus();
+
+        // Banner
+        let area = match &mut self.banner {
+            Some(banner) => {
+                let [banner_area, area] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+                banner.render(banner_area, buf);
+                area
+            }
+            None => area,
+        };
 
         // Layout
-        let layout = match self.app_state.main {
-            MainState::Table => Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1),
-                    Constraint::Min(1),
-                    Constraint::Length(1),
-                ])
-                .split(area),
-            MainState::Preview | MainState::Secrets => Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([
-                    Constraint::Length(1),
-                    Constraint::Min(1),
-                    Constraint::Length(14),
-                    Constraint::Length(1),
-                ])
-                .split(area),
+        let [menu_area, body_area, status_bar_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(area);
+        let [table_area, details_area] = match (self.app_state.main, self.details_position) {
+            (MainState::Table, _) => [body_area, Rect::default()],
+            (_, DetailsPosition::Bottom) => Layout::vertical([
+                Constraint::Min(1),
+                Constraint::Length(self.details_area_height(area.height)),
+            ])
+            .areas(body_area),
+            (_, DetailsPosition::Right) => Layout::horizontal([
+                Constraint::Min(1),
+                Constraint::Length(self.details_area_width(area.width)),
+            ])
+            .areas(body_area),
         };
 
+        self.details_area = details_area;
+
         // Menu
-        self.menu.render(layout[0], buf);
+        self.menu.render(menu_area, buf);
 
         // Table
-        self.password_table.render(layout[1], buf);
+        self.password_table.render(table_area, buf);
 
         // Details
-        let mut status_bar_area = layout[2];
-        if self.app_state.main != MainState::Table {
-            if self.render_details {
-                self.password_details.render(layout[2], buf);
-            }
-            status_bar_area = layout[3];
+        if self.app_state.main != MainState::Table
+            && self.render_details
+            && !details_area.is_empty()
+        {
+            self.check_stale();
+            self.refresh_expired_otp();
+            self.password_details.available_fields = self
+                .password_details
+                .pass_id
+                .as_ref()
+                .filter(|_| !self.password_details.show_secrets)
+                .and_then(|pass_id| self.details_cache.peek(pass_id))
+                .map(entry::available_fields);
+            self.password_details.sidebar = self.details_position == DetailsPosition::Right;
+            self.password_details.render(details_area, buf);
         }
 
         // Statusbar
+        self.status_bar.position = self
+            .password_table
+            .selected()
+            .filter(|_| !self.password_subset.is_empty())
+            .map(|selected| (selected, self.password_subset.len()));
+        let now = self.backend.clock().moment();
+        self.status_bar.clipboard_remaining = self
+            .backend
+            .clipboard_expiry()
+            .and_then(|expiry| expiry.remaining_at(now));
+        if let Some(failure) = self.backend.take_clear_failure() {
+            self.status_bar.set_status(format!("⚠ {failure}"));
+        }
         self.status_bar.render(status_bar_area, buf);
 
         // Search field
         match self.app_state.search {
-            SearchState::Active | SearchState::Suspended => {
+            SearchState::Active | SearchState::Suspended | SearchState::Goto => {
                 let search_width = 35.min(area.width);
                 let popup_area = Rect {
                     x: area.width.saturating_sub(search_width + 1),
-                    y: 3.min(area.height),
+                    y: area.y + 3.min(area.height),
                     width: search_width,
                     height: 3.min(area.height.saturating_sub(3)),
                 };
@@ -766,22 +5333,122 @@ impl Widget for &mut Dashboard<'_> {
             SearchState::Inactive => (),
         }
 
+        // A dialog is shown on top of the overlay it was opened from
+        let overlay = match self.app_state.overlay {
+            OverlayState::Dialog => self.dialog_popup.previous_overlay(),
+            overlay => overlay,
+        };
+
         // Help popup
-        if self.app_state.overlay == OverlayState::Help {
+        if overlay == OverlayState::Help {
             let popup_area = area.inner(Margin::new(6, 3));
             self.help_popup.render(popup_area, buf);
         }
 
         // File contents popup
-        if self.app_state.overlay == OverlayState::File {
+        if overlay == OverlayState::File {
             let popup_area = area.inner(Margin::new(8, 4));
             self.file_popup.render(popup_area, buf);
         }
+
+        // Recovery codes popup
+        if overlay == OverlayState::RecoveryCodes {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.recovery_codes_popup.render(popup_area, buf);
+        }
+
+        // Commands for the selected entry
+        if overlay == OverlayState::Commands {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.commands_popup.render(popup_area, buf);
+        }
+
+        // Stale entries
+        if overlay == OverlayState::Audit {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.audit_popup.render(popup_area, buf);
+        }
+
+        // Entries without a one-time password
+        if overlay == OverlayState::OtpAudit {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.otp_audit_popup.render(popup_area, buf);
+        }
+
+        // Entries sharing a password and login
+        if overlay == OverlayState::Duplicates {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.duplicates_popup.render(popup_area, buf);
+        }
+
+        // Entries with weak passwords
+        if overlay == OverlayState::Strength {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.strength_popup.render(popup_area, buf);
+        }
+
+        // Actions taken on entries in this session
+        if overlay == OverlayState::SessionLog {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.session_log_popup.render(popup_area, buf);
+        }
+
+        // Commits that changed the selected entry
+        if overlay == OverlayState::History {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.history_popup.render(popup_area, buf);
+        }
+
+        // Files left to merge after pulling
+        if overlay == OverlayState::Conflicts {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.conflicts_popup.render(popup_area, buf);
+        }
+
+        // QR code popup
+        if matches!(overlay, OverlayState::WifiQr | OverlayState::Qr) {
+            let popup_area = area.inner(Margin::new(8, 2));
+            self.qr_popup.render(popup_area, buf);
+        }
+
+        // Password generator
+        if overlay == OverlayState::Generator {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.generator_popup.render(popup_area, buf);
+        }
+
+        // Prompt for a path of the store
+        if overlay == OverlayState::Prompt {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.prompt_popup.render(popup_area, buf);
+        }
+
+        // Form for a new entry
+        if overlay == OverlayState::Insert {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.insert_popup.render(popup_area, buf);
+        }
+
+        // Confirmations and the full message of the last error
+        if self.app_state.overlay == OverlayState::Dialog {
+            let popup_area = area.inner(Margin::new(8, 4));
+            self.dialog_popup.render(popup_area, buf);
+        }
     }
 }
 
 impl MouseSupport for Dashboard<'_> {
     fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        // The mouse takes over from the keyboard focus
+        if matches!(event.kind, MouseEventKind::Moved | MouseEventKind::Down(_)) {
+            if let Some(target) = self.focus_target() {
+                target.clear_focus();
+            }
+        }
+        if self.drag_details_border(event) {
+            return Some(Action::NoOp);
+        }
+
         // TODO: Currently this only returns the latest action
         // if components overlap, place them last
         // Should be refactored to account for current app state
@@ -789,8 +5456,15 @@ impl MouseSupport for Dashboard<'_> {
         if let Some(latest_action) = self.password_table.handle_mouse_event(event) {
             action = Some(latest_action);
         }
+        if let Some(latest_action) = self
+            .banner
+            .as_mut()
+            .and_then(|banner| banner.handle_mouse_event(event))
+        {
+            action = Some(latest_action);
+        }
         match self.app_state.search {
-            SearchState::Active | SearchState::Suspended => {
+            SearchState::Active | SearchState::Suspended | SearchState::Goto => {
                 if let Some(latest_action) = self.search_field.handle_mouse_event(event) {
                     action = Some(latest_action);
                 }
@@ -811,6 +5485,76 @@ impl MouseSupport for Dashboard<'_> {
                     action = Some(latest_action);
                 }
             }
+            OverlayState::RecoveryCodes => {
+                if let Some(latest_action) = self.recovery_codes_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Audit => {
+                if let Some(latest_action) = self.audit_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::OtpAudit => {
+                if let Some(latest_action) = self.otp_audit_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Duplicates => {
+                if let Some(latest_action) = self.duplicates_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Strength => {
+                if let Some(latest_action) = self.strength_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::SessionLog => {
+                if let Some(latest_action) = self.session_log_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::History => {
+                if let Some(latest_action) = self.history_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Conflicts => {
+                if let Some(latest_action) = self.conflicts_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Commands => {
+                if let Some(latest_action) = self.commands_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::WifiQr | OverlayState::Qr => {
+                if let Some(latest_action) = self.qr_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Dialog => {
+                if let Some(latest_action) = self.dialog_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Generator => {
+                if let Some(latest_action) = self.generator_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Prompt => {
+                if let Some(latest_action) = self.prompt_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
+            OverlayState::Insert => {
+                if let Some(latest_action) = self.insert_popup.handle_mouse_event(event) {
+                    action = Some(latest_action);
+                }
+            }
             OverlayState::Inactive => (),
         }
         if let Some(latest_action) = self.menu.handle_mouse_event(event) {
@@ -823,3 +5567,21 @@ impl MouseSupport for Dashboard<'_> {
         self.area
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_file_names_are_stable() {
+        // Reference values of FNV-1a, the names must never change
+        assert_eq!(
+            store_file_name(Path::new(""), "lock"),
+            "store-cbf29ce484222325.lock"
+        );
+        assert_eq!(
+            store_file_name(Path::new("a"), "usage"),
+            "store-af63dc4c8601ec8c.usage"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::MouseEvent,
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget, Wrap},
+};
+
+use crate::{
+    actions::{Action, DialogAction},
+    app::OverlayState,
+    components::{Button, FocusSupport, MouseSupport},
+    theme::Theme,
+};
+
+const MAX_WIDTH: u16 = 72;
+
+/// Contents of a dialog, e.g. for confirming that entries are deleted.
+#[derive(Debug, Default, Clone)]
+pub struct Dialog {
+    title: String,
+    message: String,
+    hint: Option<String>,
+    buttons: Vec<DialogButton>,
+}
+
+#[derive(Debug, Clone)]
+struct DialogButton {
+    label: String,
+    /// Key pressing the button, buttons without a key are pressed with (Esc)
+    key: Option<char>,
+    /// Runs after the dialog was closed
+    action: Option<Action>,
+}
+
+impl Dialog {
+    pub fn new(title: &str, message: String) -> Self {
+        Self {
+            title: title.to_string(),
+            message,
+            hint: None,
+            buttons: Vec::new(),
+        }
+    }
+
+    /// Sets a line shown below the message.
+    pub fn hint(mut self, hint: &str) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    pub fn button(mut self, label: &str, key: char, action: Action) -> Self {
+        self.buttons.push(DialogButton {
+            label: label.to_string(),
+            key: Some(key),
+            action: Some(action),
+        });
+        self
+    }
+
+    /// Adds a button that only closes the dialog, it is focused when the
+    /// dialog is opened so that (Enter) does not confirm by accident.
+    pub fn cancel_button(mut self, label: &str) -> Self {
+        self.buttons.push(DialogButton {
+            label: label.to_string(),
+            key: None,
+            action: None,
+        });
+        self
+    }
+}
+
+/// Modal dialog shown on top of the overlay it was opened from, which is
+/// restored when the dialog is closed.
+#[derive(Debug, Default, Clone)]
+pub struct DialogPopup<'a> {
+    area: Option<Rect>,
+    theme: Theme,
+    dialog: Dialog,
+    buttons: Vec<Button<'a>>,
+    focused_button: Option<usize>,
+    /// Overlay shown before the dialog was opened
+    previous_overlay: OverlayState,
+}
+
+impl DialogPopup<'_> {
+    pub fn new() -> Self {
+        DialogPopup {
+            theme: Theme::new(),
+            ..Default::default()
+        }
+    }
+
+    pub fn open(&mut self, dialog: Dialog, previous_overlay: OverlayState) {
+        let theme = self.theme;
+        self.buttons = dialog
+            .buttons
+            .iter()
+            .enumerate()
+            .map(|(index, button)| {
+                let keyboard_label = match button.key {
+                    Some(key) => format!("({key})"),
+                    None => "(Esc)".to_string(),
+                };
+                let width = (button.label.chars().count() + keyboard_label.chars().count()) as u16;
+                Button::new(button.label.clone().fg(theme.button_label))
+                    .keyboard_label(keyboard_label.fg(theme.button_keyboard_label))
+                    .dimensions(width + 5, 3)
+                    .padded()
+                    .action_on_click(Action::Dialog(DialogAction::Press(index)))
+            })
+            .collect();
+        self.dialog = dialog;
+        self.previous_overlay = previous_overlay;
+        let cancel = self
+            .dialog
+            .buttons
+            .iter()
+            .rposition(|button| button.key.is_none());
+        self.set_focus(cancel);
+    }
+
+    /// Closes the dialog with the given button, returns the overlay to show
+    /// again and the action of the button.
+    pub fn close(&mut self, index: Option<usize>) -> (OverlayState, Option<Action>) {
+        let action = index
+            .and_then(|index| self.dialog.buttons.get(index))
+            .and_then(|button| button.action.clone());
+        self.dialog = Dialog::default();
+        self.buttons.clear();
+        self.focused_button = None;
+        (self.previous_overlay, action)
+    }
+
+    pub fn previous_overlay(&self) -> OverlayState {
+        self.previous_overlay
+    }
+
+    /// Returns the index of the button pressed with the key.
+    pub fn button_index(&self, key: char) -> Option<usize> {
+        self.dialog
+            .buttons
+            .iter()
+            .position(|button| button.key == Some(key))
+    }
+
+    fn height(&self, width: u16) -> u16 {
+        let text_width = width.saturating_sub(4).max(1) as usize;
+        let message_height: usize = self
+            .dialog
+            .message
+            .lines()
+            .map(|line| line.chars().count().div_ceil(text_width).max(1))
+            .sum();
+        let hint_height = if self.dialog.hint.is_some() { 2 } else { 0 };
+        // Borders, space above the buttons and buttons
+        message_height as u16 + hint_height + 2 + 1 + 3
+    }
+}
+
+impl Widget for &mut DialogPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // The dialog only takes the space it needs
+        let [area] = Layout::horizontal([Constraint::Max(MAX_WIDTH)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Max(self.height(area.width))])
+            .flex(Flex::Center)
+            .areas(area);
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from(self.dialog.title.as_str())
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let hint_height = if self.dialog.hint.is_some() { 2 } else { 0 };
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(hint_height),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let lines: Vec<Line> = self.dialog.message.lines().map(Line::from).collect();
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .wrap(Wrap { trim: false })
+            .render(layout[0], buf);
+
+        if let Some(hint) = &self.dialog.hint {
+            Paragraph::new(vec![Line::default(), Line::from(hint.as_str())])
+                .style(Style::new().fg(theme.details_hint_fg))
+                .alignment(Alignment::Center)
+                .render(layout[1], buf);
+        }
+
+        let button_areas = Layout::horizontal(
+            self.buttons
+                .iter()
+                .map(|button| Constraint::Length(button.dimensions.0)),
+        )
+        .flex(Flex::Center)
+        .spacing(2)
+        .split(layout[3]);
+        for (button, &button_area) in self.buttons.iter_mut().zip(button_areas.iter()) {
+            button.render(button_area, buf);
+        }
+    }
+}
+
+impl<'a> FocusSupport<'a> for DialogPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        self.buttons.iter_mut().collect()
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for DialogPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let mut action = None;
+        for button in &mut self.buttons {
+            if let Some(latest_action) = button.handle_mouse_event(event) {
+                action = Some(latest_action);
+            }
+        }
+        action.or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, Audit, DuplicatesAction, NavigationAction, ScanAction},
+    components::{Button, FocusSupport, MouseSupport},
+    theme::Theme,
+};
+
+/// Lists entries that share the same password and login for merging them
+/// into one.
+///
+/// Entries are compared by a hash of their password and login, the
+/// decrypted files themselves are not kept. Entries that are not in the
+/// cache are listed as unchecked until they are decrypted.
+#[derive(Debug, Default, Clone)]
+pub struct DuplicatesPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    /// Checked entries by the hash of their password and login
+    credentials: HashMap<u64, Vec<String>>,
+    /// Entries sharing a password and login, sorted by their first entry
+    groups: Vec<Vec<String>>,
+    checked: usize,
+    /// Entries that were not decrypted yet
+    unchecked: Vec<String>,
+    /// Index of the selected entry counted over all groups
+    selected: usize,
+    offset: usize,
+    /// Entries of the list lines, `None` for the gaps between groups
+    lines: Vec<Option<usize>>,
+    decrypt_button: Button<'a>,
+    merge_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl DuplicatesPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        DuplicatesPopup {
+            theme,
+            decrypt_button: Button::new("Decrypt".fg(theme.button_label))
+                .keyboard_label("(d)".fg(theme.button_keyboard_label))
+                .dimensions(15, 3)
+                .padded()
+                .action_on_click(Action::Scan(ScanAction::Decrypt(Audit::Duplicates))),
+            merge_button: Button::new("Merge".fg(theme.button_label))
+                .keyboard_label("(↵)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Duplicates(DuplicatesAction::Merge)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the results, e.g. when the popup is opened again.
+    pub fn set_entries(
+        &mut self,
+        credentials: HashMap<u64, Vec<String>>,
+        checked: usize,
+        unchecked: Vec<String>,
+    ) {
+        self.checked = checked;
+        self.credentials = credentials;
+        self.unchecked = unchecked;
+        self.selected = 0;
+        self.offset = 0;
+        self.group();
+    }
+
+    /// Moves a decrypted entry from the unchecked ones to the results,
+    /// entries without a password have no credential to compare.
+    pub fn checked(&mut self, pass_id: String, credential: Option<u64>) {
+        self.unchecked.retain(|unchecked| *unchecked != pass_id);
+        self.checked += 1;
+        if let Some(credential) = credential {
+            self.credentials
+                .entry(credential)
+                .or_default()
+                .push(pass_id);
+            self.group();
+        }
+    }
+
+    /// Drops entries that were merged into another one.
+    pub fn remove_entries(&mut self, pass_ids: &[String]) {
+        for entries in self.credentials.values_mut() {
+            entries.retain(|pass_id| !pass_ids.contains(pass_id));
+        }
+        self.group();
+    }
+
+    fn group(&mut self) {
+        self.groups = self
+            .credentials
+            .values()
+            .filter(|entries| entries.len() > 1)
+            .map(|entries| {
+                let mut entries = entries.clone();
+                entries.sort();
+                entries
+            })
+            .collect();
+        self.groups.sort();
+        self.selected = self.selected.min(self.duplicate_count().saturating_sub(1));
+
+        self.lines.clear();
+        let mut index = 0;
+        for entries in &self.groups {
+            if index > 0 {
+                self.lines.push(None);
+            }
+            for _ in entries {
+                self.lines.push(Some(index));
+                index += 1;
+            }
+        }
+    }
+
+    pub fn unchecked(&self) -> &[String] {
+        &self.unchecked
+    }
+
+    /// Returns the number of entries that have a duplicate.
+    pub fn duplicate_count(&self) -> usize {
+        self.groups.iter().map(Vec::len).sum()
+    }
+
+    /// Returns the selected entry and the others of its group.
+    pub fn selected_group(&self) -> Option<(String, Vec<String>)> {
+        let mut index = self.selected;
+        for entries in &self.groups {
+            if index < entries.len() {
+                let survivor = entries[index].clone();
+                let others = entries
+                    .iter()
+                    .filter(|pass_id| **pass_id != survivor)
+                    .cloned()
+                    .collect();
+                return Some((survivor, others));
+            }
+            index -= entries.len();
+        }
+        None
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.duplicate_count().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl Widget for &mut DuplicatesPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from("Duplicate entries")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let duplicates = self.duplicate_count();
+        let summary = if self.unchecked.is_empty() {
+            format!(
+                "{duplicates} of {} entries share their password and login with another one",
+                self.checked
+            )
+        } else {
+            format!(
+                "{duplicates} of {} checked entries share their password and login with another \
+                 one, {} are not decrypted yet",
+                self.checked,
+                self.unchecked.len()
+            )
+        };
+        Paragraph::new(Line::from(summary))
+            .style(Style::new().fg(theme.debug))
+            .render(layout[0], buf);
+
+        // Keep the selected entry visible
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        let selected_line = self
+            .lines
+            .iter()
+            .position(|line| *line == Some(self.selected))
+            .unwrap_or(0);
+        if selected_line < self.offset {
+            self.offset = selected_line;
+        } else if height > 0 && selected_line >= self.offset + height {
+            self.offset = selected_line + 1 - height;
+        }
+
+        let entries: Vec<&String> = self.groups.iter().flatten().collect();
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.offset)
+            .take(height)
+            .map(|line| match line {
+                Some(index) if *index == self.selected => {
+                    Line::from(entries[*index].as_str()).reversed()
+                }
+                Some(index) => Line::from(entries[*index].as_str()),
+                None => Line::default(),
+            })
+            .collect();
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        let hint = if self.unchecked.is_empty() {
+            Line::from("(↵) Keeps the selected entry and merges the others of its group into it")
+        } else {
+            Line::from("(d) Decrypts the unchecked entries to compare them as well")
+        };
+        Paragraph::new(hint)
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+
+        let [decrypt_area, merge_area, close_area] = Layout::horizontal([
+            Constraint::Length(15),
+            Constraint::Length(13),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[3]);
+        self.decrypt_button.render(decrypt_area, buf);
+        self.merge_button.render(merge_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for DuplicatesPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.decrypt_button,
+            &mut self.merge_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for DuplicatesPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let line = self.offset + (position.y - area.y) as usize;
+                if let Some(Some(index)) = self.lines.get(line) {
+                    self.selected = *index;
+                    return Some(Action::NoOp);
+                }
+            }
+        }
+        self.decrypt_button
+            .handle_mouse_event(event)
+            .or_else(|| self.merge_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
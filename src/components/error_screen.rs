@@ -0,0 +1,62 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Padding, Paragraph, Widget, Wrap},
+};
+
+use crate::{app::StartupError, theme::Theme};
+
+/// Full screen message for errors that occur before the dashboard exists.
+#[derive(Debug, Clone)]
+pub struct ErrorScreen {
+    error: StartupError,
+    theme: Theme,
+}
+
+impl ErrorScreen {
+    pub fn new(error: StartupError) -> Self {
+        Self {
+            error,
+            theme: Theme::new(),
+        }
+    }
+}
+
+impl Widget for &ErrorScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        Block::new().bg(theme.standard_bg).render(area, buf);
+
+        let [area] = Layout::horizontal([Constraint::Max(64)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(11)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        let block = Block::bordered()
+            .title(
+                Line::from("passepartui could not start")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::uniform(1))
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let text = vec![
+            Line::from(format!("✗ {}", self.error.message)),
+            Line::default(),
+            Line::from(self.error.hint.clone().fg(theme.details_hint_fg)),
+            Line::default(),
+            Line::from("(q) (Esc) Quit".fg(theme.debug)),
+        ];
+        Paragraph::new(text)
+            .block(block)
+            .style(Style::new().fg(theme.standard_fg))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
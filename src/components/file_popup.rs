@@ -10,9 +10,10 @@ use ratatui::{
 
 use crate::{
     actions::{Action, NavigationAction},
-    components::{Button, MouseSupport},
+    components::{Button, FocusSupport, MouseSupport},
     theme::Theme,
 };
+use zeroize::Zeroize;
 
 #[derive(Debug, Default, Clone)]
 pub struct FilePopup<'a> {
@@ -20,7 +21,18 @@ pub struct FilePopup<'a> {
     theme: Theme,
     pass_id: Option<String>,
     content: Option<String>,
+    last_change: Option<String>,
+    selected_line: usize,
+    offset: usize,
     close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+/// The decrypted file is wiped when replaced or dropped
+impl Drop for FilePopup<'_> {
+    fn drop(&mut self) {
+        self.content.zeroize();
+    }
 }
 
 impl FilePopup<'_> {
@@ -31,22 +43,60 @@ impl FilePopup<'_> {
             theme,
             pass_id: None,
             content: None,
+            last_change: None,
+            selected_line: 0,
+            offset: 0,
             close_button: Button::new("Close".fg(theme.button_label))
                 .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
                 .dimensions(13, 3)
                 .padded()
                 .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
         }
     }
 
     pub fn set_content(&mut self, pass_id: &str, content: &str) {
+        if self.pass_id.as_deref() != Some(pass_id) {
+            self.selected_line = 0;
+            self.offset = 0;
+        }
         self.pass_id = Some(pass_id.into());
+        self.content.zeroize();
         self.content = Some(content.into());
+        self.selected_line = self.selected_line.min(self.line_count().saturating_sub(1));
+    }
+
+    pub fn set_last_change(&mut self, last_change: Option<String>) {
+        self.last_change = last_change;
     }
 
     pub fn reset_content(&mut self) {
         self.pass_id = None;
-        self.content = None;
+        self.content.zeroize();
+        self.last_change = None;
+        self.selected_line = 0;
+        self.offset = 0;
+    }
+
+    fn line_count(&self) -> usize {
+        self.content
+            .as_ref()
+            .map(|content| content.lines().count())
+            .unwrap_or(0)
+    }
+
+    pub fn next_line(&mut self) {
+        self.selected_line = (self.selected_line + 1).min(self.line_count().saturating_sub(1));
+    }
+
+    pub fn previous_line(&mut self) {
+        self.selected_line = self.selected_line.saturating_sub(1);
+    }
+
+    /// Returns the number and the content of the line at the cursor
+    pub fn selected_line(&self) -> Option<(usize, &str)> {
+        let line = self.content.as_ref()?.lines().nth(self.selected_line)?;
+        Some((self.selected_line + 1, line))
     }
 }
 
@@ -57,6 +107,11 @@ impl Widget for &mut FilePopup<'_> {
 
         let block = Block::bordered()
             .title(Line::from("File").fg(theme.standard_fg).centered())
+            .title_bottom(
+                Line::from(" (↓) (↑) Select line  (y) (↵) Copy line ")
+                    .fg(theme.details_hint_fg)
+                    .centered(),
+            )
             .padding(Padding::horizontal(1))
             .bg(theme.standard_bg)
             .border_set(symbols::border::ROUNDED)
@@ -64,7 +119,7 @@ impl Widget for &mut FilePopup<'_> {
         let layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(2),
+                Constraint::Length(3),
                 Constraint::Min(1),
                 Constraint::Length(3),
             ])
@@ -74,28 +129,49 @@ impl Widget for &mut FilePopup<'_> {
         block.render(area, buf);
 
         if let Some(pass_id) = self.pass_id.clone() {
-            Paragraph::new(Line::from(vec![
-                "Password file ID: ".fg(theme.debug),
-                pass_id.into(),
-            ]))
+            let last_change = self
+                .last_change
+                .clone()
+                .unwrap_or("not tracked by git".to_string());
+            Paragraph::new(vec![
+                Line::from(vec!["Password file ID: ".fg(theme.debug), pass_id.into()]),
+                Line::from(vec!["Last change: ".fg(theme.debug), last_change.into()]),
+            ])
             .alignment(Alignment::Left)
             .style(Style::new().fg(theme.standard_fg))
             .render(layout[0], buf);
         }
 
         if let Some(content) = self.content.clone() {
-            let lines: Vec<&str> = content.lines().collect();
-            let content: Vec<Line> = lines
-                .iter()
-                .map(|line| Line::from(line.fg(theme.standard_fg)))
-                .collect();
-
             let content_area = layout[1];
             let content_area = Rect {
                 x: content_area.x + 2,
                 width: content_area.width.saturating_sub(2),
                 ..content_area
             };
+
+            // Keep the line cursor visible
+            let height = content_area.height as usize;
+            if self.selected_line < self.offset {
+                self.offset = self.selected_line;
+            } else if height > 0 && self.selected_line >= self.offset + height {
+                self.offset = self.selected_line + 1 - height;
+            }
+
+            let content: Vec<Line> = content
+                .lines()
+                .enumerate()
+                .skip(self.offset)
+                .map(|(i, line)| {
+                    let line = Line::from(line.fg(theme.standard_fg));
+                    if i == self.selected_line {
+                        line.reversed()
+                    } else {
+                        line
+                    }
+                })
+                .collect();
+
             Paragraph::new(content)
                 .style(Style::new().fg(theme.standard_fg))
                 .alignment(Alignment::Left)
@@ -110,6 +186,16 @@ impl Widget for &mut FilePopup<'_> {
     }
 }
 
+impl<'a> FocusSupport<'a> for FilePopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.close_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
 impl MouseSupport for FilePopup<'_> {
     fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
         self.close_button
@@ -0,0 +1,252 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, FormAction, NavigationAction, PasswordAction},
+    components::{Button, FocusSupport, MouseSupport, TextInput},
+    generator::{self, Options},
+    theme::Theme,
+};
+
+const LENGTH: usize = 0;
+const SYMBOLS: usize = 1;
+const NO_AMBIGUOUS: usize = 2;
+const PATH: usize = 3;
+
+/// Generates passwords for the clipboard or for new entries.
+#[derive(Debug, Default, Clone)]
+pub struct GeneratorPopup<'a> {
+    area: Option<Rect>,
+    row_areas: Vec<Rect>,
+    theme: Theme,
+    options: Options,
+    focus: usize,
+    path: TextInput,
+    /// Password that is copied with (^Y)
+    preview: String,
+    copy_button: Button<'a>,
+    create_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl GeneratorPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        GeneratorPopup {
+            area: None,
+            row_areas: Vec::new(),
+            theme,
+            options: Options::default(),
+            focus: LENGTH,
+            path: TextInput::default(),
+            preview: String::new(),
+            copy_button: Button::new("Copy".fg(theme.button_label))
+                .keyboard_label("(^Y)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Password(PasswordAction::CopyGenerated)),
+            create_button: Button::new("Create".fg(theme.button_label))
+                .keyboard_label("(^S)".fg(theme.button_keyboard_label))
+                .dimensions(15, 3)
+                .padded()
+                .action_on_click(Action::Password(PasswordAction::Generate)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
+        }
+    }
+
+    /// Generates a new password, the path is prefilled with the given folder
+    /// while the options are kept.
+    pub fn reset(&mut self, folder: Option<&str>) {
+        self.focus = LENGTH;
+        let path = folder
+            .map(|folder| format!("{folder}/"))
+            .unwrap_or_default();
+        self.path = TextInput::new(&path, path.chars().count());
+        self.preview = generator::generate(&self.options);
+    }
+
+    pub fn update(&mut self, action: FormAction) {
+        let options = self.options;
+        if !matches!(action, FormAction::NextField | FormAction::PreviousField) {
+            self.clear_focus();
+        }
+        match (self.focus, action) {
+            // The buttons follow the path
+            (PATH, FormAction::NextField) => self.focus_next(),
+            (_, FormAction::PreviousField) if self.focused_button.is_some() => {
+                self.focus_previous();
+            }
+            (_, FormAction::NextField) => self.focus = (self.focus + 1).min(PATH),
+            (_, FormAction::PreviousField) => self.focus = self.focus.saturating_sub(1),
+            (_, FormAction::Focus(index)) => self.focus = index.min(PATH),
+            (LENGTH, FormAction::MoveLeft | FormAction::RemoveLeft) => {
+                self.options.length = (self.options.length - 1).max(generator::MIN_LENGTH);
+            }
+            (LENGTH, FormAction::MoveRight) => {
+                self.options.length = (self.options.length + 1).min(generator::MAX_LENGTH);
+            }
+            (LENGTH, FormAction::MoveToStart) => self.options.length = generator::MIN_LENGTH,
+            (LENGTH, FormAction::MoveToEnd) => self.options.length = generator::MAX_LENGTH,
+            (SYMBOLS, FormAction::Insert(' ') | FormAction::Enter) => {
+                self.options.symbols = !self.options.symbols;
+            }
+            (NO_AMBIGUOUS, FormAction::Insert(' ') | FormAction::Enter) => {
+                self.options.no_ambiguous = !self.options.no_ambiguous;
+            }
+            (PATH, action) => self.path.edit(action),
+            _ => (),
+        }
+        if self.options != options {
+            self.preview = generator::generate(&self.options);
+        }
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    pub fn preview(&self) -> &str {
+        &self.preview
+    }
+
+    pub fn pass_id(&self) -> String {
+        self.path.get_content().trim().to_string()
+    }
+}
+
+impl Widget for &mut GeneratorPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from("Password generator")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" };
+        let values = [
+            Line::from(format!("◂ {} ▸", self.options.length)),
+            Line::from(checkbox(self.options.symbols)),
+            Line::from(checkbox(self.options.no_ambiguous)),
+        ];
+        let labels = ["Length:", "Symbols:", "No ambiguous:", "Path:"];
+        self.row_areas = layout[..4].to_vec();
+        for (i, label) in labels.into_iter().enumerate() {
+            let focused = i == self.focus && self.focused_button.is_none();
+            let [label_area, value_area] =
+                Layout::horizontal([Constraint::Length(15), Constraint::Min(1)]).areas(layout[i]);
+            let label = Line::from(label);
+            Paragraph::new(if focused { label.bold() } else { label })
+                .style(Style::new().fg(theme.details_field_fg))
+                .render(label_area, buf);
+            let value = match values.get(i) {
+                Some(value) if focused => vec![value.clone().reversed()],
+                Some(value) => vec![value.clone()],
+                None => self.path.lines(false, focused),
+            };
+            Paragraph::new(value)
+                .style(Style::new().fg(theme.standard_fg))
+                .render(value_area, buf);
+        }
+
+        Paragraph::new(vec![
+            Line::from("Password:".fg(theme.details_field_fg)),
+            Line::from(self.preview.as_str()),
+        ])
+        .style(Style::new().fg(theme.standard_fg))
+        .render(layout[5], buf);
+
+        Paragraph::new(Line::from(
+            "(↓) (↑) Select  (←) (→) Length  (␣) Toggle  Create uses pass generate",
+        ))
+        .style(Style::new().fg(theme.details_hint_fg))
+        .alignment(Alignment::Center)
+        .render(layout[6], buf);
+
+        let [copy_area, create_area, close_area] = Layout::horizontal([
+            Constraint::Length(13),
+            Constraint::Length(15),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[7]);
+        self.copy_button.render(copy_area, buf);
+        self.create_button.render(create_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for GeneratorPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.copy_button,
+            &mut self.create_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for GeneratorPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if event.kind == MouseEventKind::Down(MouseButton::Left) {
+            if let Some(index) = self
+                .row_areas
+                .iter()
+                .position(|area| area.contains(position))
+            {
+                return Some(Action::Form(FormAction::Focus(index)));
+            }
+        }
+        self.copy_button
+            .handle_mouse_event(event)
+            .or_else(|| self.create_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -1,39 +1,71 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::MouseEvent,
-    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    crossterm::event::{MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
     style::{Style, Stylize},
     symbols,
     text::Line,
-    widgets::{Block, Clear, Padding, Paragraph, Widget, Wrap},
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
 };
 
 use crate::{
     actions::{Action, NavigationAction},
-    components::{Button, MouseSupport},
+    components::{Button, FocusSupport, MouseSupport},
     theme::Theme,
 };
 
+/// Lists the keyboard shortcuts, scrolled line by line or by the page when
+/// they don't fit.
 #[derive(Debug, Default, Clone)]
 pub struct HelpPopup<'a> {
     area: Option<Rect>,
+    text_area: Option<Rect>,
     theme: Theme,
+    /// First visible line
+    offset: usize,
+    /// Number of lines that fit, known after rendering
+    page_height: usize,
     close_button: Button<'a>,
+    focused_button: Option<usize>,
 }
 
 impl HelpPopup<'_> {
     pub fn new() -> Self {
         let theme = Theme::new();
         HelpPopup {
-            area: None,
             theme,
             close_button: Button::new("Close".fg(theme.button_label))
                 .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
                 .dimensions(13, 3)
                 .padded()
                 .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
         }
     }
+
+    pub fn next(&mut self) {
+        self.offset = self.offset.saturating_add(1);
+    }
+
+    pub fn previous(&mut self) {
+        self.offset = self.offset.saturating_sub(1);
+    }
+
+    pub fn next_page(&mut self) {
+        self.offset = self.offset.saturating_add(self.page_height.max(1));
+    }
+
+    pub fn previous_page(&mut self) {
+        self.offset = self.offset.saturating_sub(self.page_height.max(1));
+    }
+
+    pub fn top(&mut self) {
+        self.offset = 0;
+    }
+
+    pub fn bottom(&mut self) {
+        self.offset = usize::MAX;
+    }
 }
 
 impl Widget for &mut HelpPopup<'_> {
@@ -54,7 +86,11 @@ impl Widget for &mut HelpPopup<'_> {
             .border_style(Style::new().fg(theme.popup_border));
         let layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
             .split(block.inner(area));
         Clear.render(area, buf);
         block.render(area, buf);
@@ -70,6 +106,44 @@ impl Widget for &mut HelpPopup<'_> {
             Line::from("(←) (h) (→) (l) (↵) Switch between view modes".fg(theme.standard_fg)),
             Line::from("for password list, preview and secrets".fg(theme.standard_fg)),
             Line::default(),
+            Line::from("(o) Open URL in browser, (O) and copy login first".fg(theme.standard_fg)),
+            Line::from("(F5) Reload changed entries of the password store".fg(theme.standard_fg)),
+            Line::from("(^L) Lock, forget decrypted files and passphrases".fg(theme.standard_fg)),
+            Line::from("(R) Show recovery codes of the entry".fg(theme.standard_fg)),
+            Line::from("(w) Show QR code for joining the Wi-Fi network".fg(theme.standard_fg)),
+            Line::from("(^Q) Show QR codes of the OTP and the password".fg(theme.standard_fg)),
+            Line::from("there (u) shows the OTP URI, (E) exports it".fg(theme.standard_fg)),
+            Line::from("(!) Run a command with the password on stdin".fg(theme.standard_fg)),
+            Line::from("(S) List stale entries and loose permissions".fg(theme.standard_fg)),
+            Line::from("(T) List entries without a one-time password".fg(theme.standard_fg)),
+            Line::from("(=) List entries with the same password and login".fg(theme.standard_fg)),
+            Line::from("(W) List entries with weak passwords".fg(theme.standard_fg)),
+            Line::from("(E) Review and export the actions of this session".fg(theme.standard_fg)),
+            Line::from("(U) Select the entry matching the host of a URL".fg(theme.standard_fg)),
+            Line::from("(t) Switch between one-time passwords of the entry".fg(theme.standard_fg)),
+            Line::from("(+) (-) Resize the details pane, (z) collapse it".fg(theme.standard_fg)),
+            Line::from("(|) Show the details pane on the right or below".fg(theme.standard_fg)),
+            Line::from("(;) and a letter: Jump to the first entry with it".fg(theme.standard_fg)),
+            Line::from(
+                "(m) and a letter: Mark entry, (') and it: Jump to it".fg(theme.standard_fg),
+            ),
+            Line::from("(^O) ('') Jump back to the previous selection".fg(theme.standard_fg)),
+            Line::from("(>) (<) Show only entries in folder, leave folder".fg(theme.standard_fg)),
+            Line::from("(p) Pin entry to the top of the list or unpin it".fg(theme.standard_fg)),
+            Line::from("(A) Archive or unarchive entry, (H) show archive".fg(theme.standard_fg)),
+            Line::from("(s) (M) Sort by name or date, again to reverse".fg(theme.standard_fg)),
+            Line::from("(a) Add a new entry, (^S) saves it".fg(theme.standard_fg)),
+            Line::from("(n) Generate a password or a new entry".fg(theme.standard_fg)),
+            Line::from("(F2) Rename entry or move it to another folder".fg(theme.standard_fg)),
+            Line::from("(:) Go to entry by path, (N) create a new folder".fg(theme.standard_fg)),
+            Line::from("(e) Show the full message of the last error".fg(theme.standard_fg)),
+            Line::from("(F6) (F7) Pull or push with git, (F8) git status".fg(theme.standard_fg)),
+            Line::from("(l) (r) (e) Keep local, remote or edit after a pull".fg(theme.standard_fg)),
+            Line::from("(L) Compare versions of the entry from git history".fg(theme.standard_fg)),
+            Line::from(
+                "(⇥) (⇤) Focus buttons, (↵) (␣) press the focused one".fg(theme.standard_fg),
+            ),
+            Line::default(),
             Line::from(
                 "Keyboard shortcuts are mapped in all view modes."
                     .fg(theme.standard_fg)
@@ -79,27 +153,79 @@ impl Widget for &mut HelpPopup<'_> {
             Line::from("Search".fg(theme.debug).italic()),
             Line::default(),
             Line::from("(Esc) (↵) Suspend search".fg(theme.standard_fg)),
-            Line::from(
-                "Pressing (Esc) a second time clears the search and resets the filter."
-                    .fg(theme.standard_fg),
-            ),
+            Line::from("Pressing (Esc) a second time clears the search".fg(theme.standard_fg)),
+            Line::from("and resets the filter.".fg(theme.standard_fg)),
             Line::from("(↓) and (↑) work as usual to select a result.".fg(theme.standard_fg)),
+            Line::from("(⇥) Search the whole store instead of the folder".fg(theme.standard_fg)),
+            Line::from("(^G) Search the decrypted password files instead".fg(theme.standard_fg)),
         ];
-        Paragraph::new(text)
+
+        let text_area = layout[0];
+        self.text_area = Some(text_area);
+        self.page_height = text_area.height as usize;
+        let last_offset = text.len().saturating_sub(self.page_height);
+        self.offset = self.offset.min(last_offset);
+        let lines: Vec<Line> = text
+            .into_iter()
+            .skip(self.offset)
+            .take(self.page_height)
+            .collect();
+        Paragraph::new(lines)
             .style(Style::new().fg(theme.standard_fg))
             .alignment(Alignment::Center)
-            .wrap(Wrap { trim: true })
-            .render(layout[0], buf);
+            .render(text_area, buf);
+
+        // The last page starts wherever the text ends on a full page
+        if last_offset > 0 {
+            let pages = last_offset.div_ceil(self.page_height) + 1;
+            let page = if self.offset == last_offset {
+                pages
+            } else {
+                self.offset / self.page_height + 1
+            };
+            Paragraph::new(Line::from(format!(
+                "Page {page} of {pages}, (↓) (↑) scroll, (⇣) (⇡) turn pages"
+            )))
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[1], buf);
+        }
 
         let [button_area] = Layout::horizontal([Constraint::Length(13)])
             .flex(Flex::Center)
-            .areas(layout[1]);
+            .areas(layout[2]);
         self.close_button.render(button_area, buf);
     }
 }
 
+impl<'a> FocusSupport<'a> for HelpPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.close_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
 impl MouseSupport for HelpPopup<'_> {
     fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.text_area {
+            if area.contains(position) {
+                match event.kind {
+                    MouseEventKind::ScrollDown => {
+                        self.next();
+                        return Some(Action::NoOp);
+                    }
+                    MouseEventKind::ScrollUp => {
+                        self.previous();
+                        return Some(Action::NoOp);
+                    }
+                    _ => {}
+                }
+            }
+        }
         self.close_button
             .handle_mouse_event(event)
             .or(Some(Action::NoOp))
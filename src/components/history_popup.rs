@@ -0,0 +1,325 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, HistoryAction, NavigationAction},
+    components::{Button, FocusSupport, MouseSupport},
+    diff::DiffLine,
+    git::Revision,
+    theme::Theme,
+};
+
+/// Decrypted changes between two versions, shown instead of the list
+#[derive(Debug, Clone)]
+struct Diff {
+    old: String,
+    new: String,
+    lines: Vec<DiffLine>,
+}
+
+/// Lists the commits that changed an entry and compares the decrypted
+/// versions.
+#[derive(Debug, Default, Clone)]
+pub struct HistoryPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    pass_id: String,
+    /// `None` while the history is looked up
+    revisions: Option<Vec<Revision>>,
+    selected: usize,
+    offset: usize,
+    /// Version the selected one is compared with instead of the one before
+    base: Option<usize>,
+    diff: Option<Diff>,
+    scroll: usize,
+    diff_button: Button<'a>,
+    difftool_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl HistoryPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        HistoryPopup {
+            theme,
+            diff_button: Button::new("Diff".fg(theme.button_label))
+                .keyboard_label("(↵)".fg(theme.button_keyboard_label))
+                .dimensions(12, 3)
+                .padded()
+                .action_on_click(Action::History(HistoryAction::Diff)),
+            difftool_button: Button::new("Difftool".fg(theme.button_label))
+                .keyboard_label("(D)".fg(theme.button_keyboard_label))
+                .dimensions(16, 3)
+                .padded()
+                .action_on_click(Action::History(HistoryAction::Difftool)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    /// Shows the popup for the entry while its history is looked up.
+    pub fn open(&mut self, pass_id: &str) {
+        self.reset_content();
+        self.pass_id = pass_id.to_string();
+    }
+
+    pub fn set_revisions(&mut self, pass_id: &str, revisions: Vec<Revision>) {
+        if pass_id == self.pass_id {
+            self.revisions = Some(revisions);
+        }
+    }
+
+    pub fn set_diff(&mut self, pass_id: &str, old: String, new: String, lines: Vec<DiffLine>) {
+        if pass_id == self.pass_id {
+            self.diff = Some(Diff { old, new, lines });
+            self.scroll = 0;
+        }
+    }
+
+    /// Goes back from the changes to the list, returns false if the list
+    /// is shown already.
+    pub fn close_diff(&mut self) -> bool {
+        self.scroll = 0;
+        self.diff.take().is_some()
+    }
+
+    pub fn reset_content(&mut self) {
+        self.pass_id.clear();
+        self.revisions = None;
+        self.selected = 0;
+        self.offset = 0;
+        self.base = None;
+        self.diff = None;
+        self.scroll = 0;
+    }
+
+    pub fn pass_id(&self) -> &str {
+        &self.pass_id
+    }
+
+    fn revision_count(&self) -> usize {
+        self.revisions.as_ref().map_or(0, Vec::len)
+    }
+
+    pub fn next(&mut self) {
+        if let Some(diff) = &self.diff {
+            self.scroll = (self.scroll + 1).min(diff.lines.len().saturating_sub(1));
+        } else {
+            self.selected = (self.selected + 1).min(self.revision_count().saturating_sub(1));
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if self.diff.is_some() {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn toggle_base(&mut self) {
+        if self.diff.is_none() && self.selected < self.revision_count() {
+            self.base = (self.base != Some(self.selected)).then_some(self.selected);
+        }
+    }
+
+    /// Returns the hashes of the older and the newer version to compare,
+    /// the older one is `None` if the selected version added the entry.
+    pub fn compared(&self) -> Option<(Option<String>, String)> {
+        let revisions = self.revisions.as_ref()?;
+        let hash = |index: usize| revisions.get(index).map(|revision| revision.hash.clone());
+        // Revisions are listed latest first
+        match self.base.filter(|&base| base != self.selected) {
+            Some(base) if base < self.selected => Some((hash(self.selected), hash(base)?)),
+            Some(base) => Some((hash(base), hash(self.selected)?)),
+            None => Some((hash(self.selected + 1), hash(self.selected)?)),
+        }
+    }
+
+    fn render_list(&mut self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        self.list_area = Some(area);
+
+        // Keep the selected version visible
+        let height = area.height as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        let lines: Vec<Line> = match &self.revisions {
+            None => vec![Line::from(
+                "⧗ Looking up the history...".fg(theme.details_hint_fg),
+            )],
+            Some(revisions) if revisions.is_empty() => vec![Line::from(
+                "No commits, the store is not a git repository or the entry is not committed"
+                    .fg(theme.details_hint_fg),
+            )],
+            Some(revisions) => revisions
+                .iter()
+                .enumerate()
+                .skip(self.offset)
+                .take(height)
+                .map(|(i, revision)| {
+                    let line = Line::from(vec![
+                        if self.base == Some(i) { "● " } else { "  " }.into(),
+                        revision.hash.clone().fg(theme.details_field_fg),
+                        format!("  {}  ", revision.date).fg(theme.details_hint_fg),
+                        revision.subject.clone().into(),
+                        format!("  {}", revision.author).fg(theme.details_hint_fg),
+                    ]);
+                    if i == self.selected {
+                        line.reversed()
+                    } else {
+                        line
+                    }
+                })
+                .collect(),
+        };
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(area, buf);
+    }
+
+    fn render_diff(&self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        let Some(diff) = &self.diff else {
+            return;
+        };
+        let lines: Vec<Line> = if diff.lines.is_empty() {
+            vec![Line::from("No changes".fg(theme.details_hint_fg))]
+        } else {
+            diff.lines
+                .iter()
+                .skip(self.scroll)
+                .take(area.height as usize)
+                .map(|line| match line {
+                    DiffLine::Unchanged(text) => Line::from(format!("  {text}")),
+                    DiffLine::Removed(text) => {
+                        Line::from(format!("- {text}").fg(theme.diff_removed_fg))
+                    }
+                    DiffLine::Added(text) => {
+                        Line::from(format!("+ {text}").fg(theme.diff_added_fg))
+                    }
+                })
+                .collect()
+        };
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(area, buf);
+    }
+}
+
+impl Widget for &mut HistoryPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(Line::from("History").fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let description = match &self.diff {
+            Some(diff) => format!(
+                "Changes of {} from {} to {}",
+                self.pass_id, diff.old, diff.new
+            ),
+            None => format!("Commits that changed {}", self.pass_id),
+        };
+        Paragraph::new(Line::from(description))
+            .style(Style::new().fg(theme.debug))
+            .render(layout[0], buf);
+
+        let hint = if self.diff.is_some() {
+            self.list_area = None;
+            self.render_diff(layout[1], buf);
+            "(↑↓) Scroll  (D) Difftool  (Esc) Back to the commits"
+        } else {
+            self.render_list(layout[1], buf);
+            "(␣) Compare with this commit  (↵) Diff  (D) Difftool"
+        };
+        Paragraph::new(Line::from(hint))
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+
+        let [diff_area, difftool_area, close_area] = Layout::horizontal([
+            Constraint::Length(12),
+            Constraint::Length(16),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[3]);
+        self.diff_button.render(diff_area, buf);
+        self.difftool_button.render(difftool_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for HistoryPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.diff_button,
+            &mut self.difftool_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for HistoryPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = self.offset + (position.y - area.y) as usize;
+                if index < self.revision_count() {
+                    self.selected = index;
+                    return Some(Action::NoOp);
+                }
+            }
+        }
+        self.diff_button
+            .handle_mouse_event(event)
+            .or_else(|| self.difftool_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
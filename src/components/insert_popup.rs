@@ -0,0 +1,236 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, FormAction, NavigationAction, PasswordAction},
+    components::{Button, FocusSupport, MouseSupport, TextInput},
+    config::Template,
+    theme::Theme,
+};
+
+const LABELS: [&str; 4] = ["Path", "Password", "Login", "Extra"];
+const PASSWORD: usize = 1;
+const EXTRA: usize = 3;
+
+#[derive(Debug, Default, Clone)]
+pub struct InsertPopup<'a> {
+    area: Option<Rect>,
+    input_areas: Vec<Rect>,
+    theme: Theme,
+    inputs: [TextInput; 4],
+    focus: usize,
+    /// Template the extra lines were prefilled with
+    template: Option<Template>,
+    save_button: Button<'a>,
+    cancel_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl InsertPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        InsertPopup {
+            area: None,
+            input_areas: Vec::new(),
+            theme,
+            inputs: Default::default(),
+            focus: 0,
+            template: None,
+            save_button: Button::new("Save".fg(theme.button_label))
+                .keyboard_label("(^S)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Password(PasswordAction::Insert)),
+            cancel_button: Button::new("Cancel".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(14, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
+        }
+    }
+
+    /// Clears the form, the path is prefilled with the given folder.
+    pub fn reset(&mut self, folder: Option<&str>) {
+        self.inputs = Default::default();
+        self.focus = 0;
+        self.template = None;
+        if let Some(folder) = folder {
+            for character in folder.chars().chain(['/']) {
+                self.inputs[0].insert(character);
+            }
+        }
+    }
+
+    pub fn update(&mut self, action: FormAction) {
+        match action {
+            // The buttons follow the last input
+            FormAction::NextField if self.focused_button.is_some() || self.focus == EXTRA => {
+                self.focus_next();
+            }
+            FormAction::PreviousField if self.focused_button.is_some() => self.focus_previous(),
+            FormAction::Enter if self.focus == EXTRA => self.inputs[EXTRA].insert('\n'),
+            FormAction::Enter | FormAction::NextField => {
+                self.focus = (self.focus + 1).min(EXTRA);
+            }
+            FormAction::PreviousField => self.focus = self.focus.saturating_sub(1),
+            FormAction::Focus(index) => {
+                self.clear_focus();
+                self.focus = index.min(EXTRA);
+            }
+            action => {
+                self.clear_focus();
+                self.inputs[self.focus].edit(action);
+            }
+        }
+    }
+
+    /// Prefills the extra lines with the keys of a template unless they were
+    /// edited, the cursor is placed after the first key.
+    pub fn set_template(&mut self, template: Option<&Template>) {
+        if self.template.as_ref() == template {
+            return;
+        }
+        let applied = self
+            .template
+            .as_ref()
+            .map(template_text)
+            .unwrap_or_default();
+        if self.inputs[EXTRA].get_content() != applied {
+            return;
+        }
+        let text = template.map(template_text).unwrap_or_default();
+        let cursor_position = text.chars().position(|c| c == '\n').unwrap_or(text.len());
+        self.inputs[EXTRA] = TextInput::new(&text, cursor_position);
+        self.template = template.cloned();
+    }
+
+    pub fn pass_id(&self) -> String {
+        self.inputs[0].get_content().trim().to_string()
+    }
+
+    /// Returns the contents of the new password file: the password on the
+    /// first line, the login on the second and the extra lines below.
+    pub fn file_contents(&self) -> String {
+        let [_, password, login, extra] = self.inputs.each_ref().map(TextInput::get_content);
+        let extra = extra.trim_end_matches('\n');
+        let mut file_contents = format!("{password}\n");
+        if !login.is_empty() || !extra.is_empty() {
+            file_contents.push_str(&format!("{login}\n"));
+        }
+        if !extra.is_empty() {
+            file_contents.push_str(&format!("{extra}\n"));
+        }
+        file_contents
+    }
+}
+
+fn template_text(template: &Template) -> String {
+    template
+        .keys
+        .iter()
+        .map(|key| format!("{key}: "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Widget for &mut InsertPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let title = match &self.template {
+            Some(template) => format!("New entry ({} template)", template.name),
+            None => "New entry".to_string(),
+        };
+        let block = Block::bordered()
+            .title(Line::from(title).fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(2),
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        self.input_areas = layout[..4].to_vec();
+        for (i, input) in self.inputs.iter().enumerate() {
+            let focused = i == self.focus && self.focused_button.is_none();
+            let [label_area, input_area] =
+                Layout::horizontal([Constraint::Length(10), Constraint::Min(1)]).areas(layout[i]);
+            let label = Line::from(format!("{}:", LABELS[i]));
+            Paragraph::new(if focused { label.bold() } else { label })
+                .style(Style::new().fg(theme.details_field_fg))
+                .render(label_area, buf);
+            Paragraph::new(input.lines(i == PASSWORD, focused))
+                .style(Style::new().fg(theme.standard_fg))
+                .render(input_area, buf);
+        }
+
+        Paragraph::new(Line::from(
+            "(⇥) (⇤) Switch field  (↵) Next field or new line in extra",
+        ))
+        .style(Style::new().fg(theme.details_hint_fg))
+        .alignment(Alignment::Center)
+        .render(layout[4], buf);
+
+        let [save_area, cancel_area] =
+            Layout::horizontal([Constraint::Length(13), Constraint::Length(14)])
+                .flex(Flex::Center)
+                .spacing(2)
+                .areas(layout[5]);
+        self.save_button.render(save_area, buf);
+        self.cancel_button.render(cancel_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for InsertPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.save_button, &mut self.cancel_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for InsertPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if event.kind == MouseEventKind::Down(MouseButton::Left) {
+            if let Some(index) = self
+                .input_areas
+                .iter()
+                .position(|area| area.contains(position))
+            {
+                return Some(Action::Form(FormAction::Focus(index)));
+            }
+        }
+        self.save_button
+            .handle_mouse_event(event)
+            .or_else(|| self.cancel_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
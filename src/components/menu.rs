@@ -3,7 +3,7 @@ use ratatui::{
 };
 
 use crate::{
-    actions::{Action, NavigationAction},
+    actions::{Action, NavigationAction, StoreAction},
     components::{Button, MouseSupport},
     theme::Theme,
 };
@@ -14,6 +14,8 @@ pub struct Menu<'a> {
     area: Option<Rect>,
     search_button: Button<'a>,
     help_button: Button<'a>,
+    pull_button: Button<'a>,
+    push_button: Button<'a>,
     quit_button: Button<'a>,
 }
 
@@ -38,6 +40,24 @@ impl Menu<'_> {
                 theme.menu_button_shadow,
             )
             .action_on_click(Action::Navigation(NavigationAction::Help));
+        let pull_button = Button::new("Pull".fg(theme.menu_button_label))
+            .keyboard_label("(F6)".fg(theme.menu_button_keyboard_label))
+            .vertical_accents()
+            .theme(
+                theme.menu_button_background,
+                theme.menu_button_highlight,
+                theme.menu_button_shadow,
+            )
+            .action_on_click(Action::Store(StoreAction::GitPull));
+        let push_button = Button::new("Push".fg(theme.menu_button_label))
+            .keyboard_label("(F7)".fg(theme.menu_button_keyboard_label))
+            .vertical_accents()
+            .theme(
+                theme.menu_button_background,
+                theme.menu_button_highlight,
+                theme.menu_button_shadow,
+            )
+            .action_on_click(Action::Store(StoreAction::GitPush));
         let quit_button = Button::new("Quit".fg(theme.menu_button_label))
             .keyboard_label("(q)".fg(theme.menu_button_keyboard_label))
             .vertical_accents()
@@ -52,6 +72,8 @@ impl Menu<'_> {
             area: None,
             help_button,
             search_button,
+            pull_button,
+            push_button,
             quit_button,
         }
     }
@@ -71,24 +93,40 @@ impl Widget for &mut Menu<'_> {
 
         // Search button
         let button_area = Rect {
-            x: 0,
-            y: 0,
+            x: area.x,
+            y: area.y,
             width: 12,
             height: 1,
         };
         self.search_button.render(button_area, buf);
         // Help button
         let button_area = Rect {
-            x: 12,
-            y: 0,
+            x: area.x + 12,
+            y: area.y,
             width: 11,
             height: 1,
         };
         self.help_button.render(button_area, buf);
+        // Pull button
+        let button_area = Rect {
+            x: area.x + 23,
+            y: area.y,
+            width: 11,
+            height: 1,
+        };
+        self.pull_button.render(button_area, buf);
+        // Push button
+        let button_area = Rect {
+            x: area.x + 34,
+            y: area.y,
+            width: 11,
+            height: 1,
+        };
+        self.push_button.render(button_area, buf);
         // Quit button
         let button_area = Rect {
-            x: 23,
-            y: 0,
+            x: area.x + 45,
+            y: area.y,
             width: 10,
             height: 1,
         };
@@ -101,6 +139,8 @@ impl MouseSupport for Menu<'_> {
         let buttons = vec![
             &mut self.search_button,
             &mut self.help_button,
+            &mut self.pull_button,
+            &mut self.push_button,
             &mut self.quit_button,
         ];
         // TODO: Currently this only returns the latest actions
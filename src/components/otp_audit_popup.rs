@@ -0,0 +1,240 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, Audit, NavigationAction, OtpAuditAction, ScanAction},
+    components::{Button, FocusSupport, MouseSupport},
+    theme::Theme,
+};
+
+/// Lists the entries without a one-time password for enrolling the missing
+/// second factors.
+///
+/// Entries are checked from the decrypted files in the cache, the others
+/// are listed as unchecked until they are decrypted.
+#[derive(Debug, Default, Clone)]
+pub struct OtpAuditPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    /// Entries that were checked and have no `otpauth://` URI
+    missing: Vec<String>,
+    /// Number of checked entries with a one-time password
+    with_otp: usize,
+    /// Entries that were not decrypted yet
+    unchecked: Vec<String>,
+    selected: usize,
+    offset: usize,
+    decrypt_button: Button<'a>,
+    open_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl OtpAuditPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        OtpAuditPopup {
+            theme,
+            decrypt_button: Button::new("Decrypt".fg(theme.button_label))
+                .keyboard_label("(d)".fg(theme.button_keyboard_label))
+                .dimensions(15, 3)
+                .padded()
+                .action_on_click(Action::Scan(ScanAction::Decrypt(Audit::Otp))),
+            open_button: Button::new("Go to".fg(theme.button_label))
+                .keyboard_label("(↵)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::OtpAudit(OtpAuditAction::Open)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the results, e.g. when the popup is opened again.
+    pub fn set_entries(&mut self, missing: Vec<String>, with_otp: usize, unchecked: Vec<String>) {
+        self.missing = missing;
+        self.with_otp = with_otp;
+        self.unchecked = unchecked;
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    /// Moves a decrypted entry from the unchecked ones to the results.
+    pub fn checked(&mut self, pass_id: String, has_otp: bool) {
+        self.unchecked.retain(|unchecked| *unchecked != pass_id);
+        if has_otp {
+            self.with_otp += 1;
+        } else {
+            let index = self.missing.partition_point(|missing| *missing < pass_id);
+            self.missing.insert(index, pass_id);
+        }
+    }
+
+    pub fn unchecked(&self) -> &[String] {
+        &self.unchecked
+    }
+
+    pub fn missing_count(&self) -> usize {
+        self.missing.len()
+    }
+
+    pub fn selected_pass_id(&self) -> Option<&str> {
+        self.missing.get(self.selected).map(String::as_str)
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.missing.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl Widget for &mut OtpAuditPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from("Entries without one-time password")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let checked = self.missing.len() + self.with_otp;
+        let summary = if self.unchecked.is_empty() {
+            format!(
+                "{} of {checked} entries have no one-time password",
+                self.missing.len()
+            )
+        } else {
+            format!(
+                "{} of {checked} checked entries have no one-time password, {} are not decrypted yet",
+                self.missing.len(),
+                self.unchecked.len()
+            )
+        };
+        Paragraph::new(Line::from(summary))
+            .style(Style::new().fg(theme.debug))
+            .render(layout[0], buf);
+
+        // Keep the selected entry visible
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        let lines: Vec<Line> = self
+            .missing
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(height)
+            .map(|(i, pass_id)| {
+                let line = Line::from(pass_id.as_str());
+                if i == self.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        let hint = if self.unchecked.is_empty() {
+            Line::from("Entries with an otpauth:// line have a one-time password")
+        } else {
+            Line::from("(d) Decrypts the unchecked entries to check them as well")
+        };
+        Paragraph::new(hint)
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+
+        let [decrypt_area, open_area, close_area] = Layout::horizontal([
+            Constraint::Length(15),
+            Constraint::Length(13),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[3]);
+        self.decrypt_button.render(decrypt_area, buf);
+        self.open_button.render(open_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for OtpAuditPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.decrypt_button,
+            &mut self.open_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for OtpAuditPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = self.offset + (position.y - area.y) as usize;
+                if index < self.missing.len() {
+                    self.selected = index;
+                    return Some(Action::NoOp);
+                }
+            }
+        }
+        self.decrypt_button
+            .handle_mouse_event(event)
+            .or_else(|| self.open_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
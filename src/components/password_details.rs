@@ -12,10 +12,12 @@ mod details_field;
 
 use crate::{
     actions::{Action, NavigationAction, PasswordAction},
-    components::{Button, MouseSupport},
+    components::{Button, FocusSupport, MouseSupport},
     theme::Theme,
 };
 use details_field::DetailsField;
+use std::time::{Duration, SystemTime};
+use zeroize::Zeroize;
 
 #[derive(Debug, Default)]
 pub struct PasswordDetails<'a> {
@@ -24,17 +26,77 @@ pub struct PasswordDetails<'a> {
     pub line_count: Option<usize>,
     pub password: Option<String>,
     pub one_time_password: Option<String>,
+    pub otp_labels: Vec<String>,
+    pub otp_index: usize,
+    /// End of the time step of the shown one-time password and its length
+    pub otp_window: Option<(SystemTime, Duration)>,
+    /// Time left until the one-time password expires, set before rendering
+    pub otp_remaining: Option<Duration>,
     pub login: Option<String>,
+    /// URL of a `url: ...` line or the first web address in the file
+    pub url: Option<String>,
+    /// Label of the field copied with (y) if it is not the password
+    pub copy_default: Option<String>,
+    /// Error message of the last failed attempt to decrypt the file
+    pub fetch_error: Option<String>,
+    /// Modification time of the password file when it was decrypted
+    pub fetched_modified: Option<SystemTime>,
+    /// Whether the file changed since it was decrypted
+    pub stale: bool,
+    /// Entry the decrypted alias stands in for
+    pub target: Option<String>,
+    /// Labels of the fields the entry holds, known without showing the
+    /// secrets while its decrypted file is cached
+    pub available_fields: Option<Vec<&'static str>>,
+    /// Shown on the right of the table instead of below it
+    pub sidebar: bool,
     pass_id_field: DetailsField<'a>,
     lines_field: DetailsField<'a>,
+    fields_field: DetailsField<'a>,
     password_field: DetailsField<'a>,
     otp_field: DetailsField<'a>,
     login_field: DetailsField<'a>,
+    url_field: DetailsField<'a>,
+    /// Fields of the `key: value` lines, shown below the login
+    key_fields: Vec<DetailsField<'a>>,
+    /// Number of key fields that fit below the others when last rendered
+    shown_key_fields: usize,
     theme: Theme,
     area: Option<Rect>,
+    focused_button: Option<usize>,
 }
 
 impl PasswordDetails<'_> {
+    /// Lines needed for showing anything, the pane is left empty otherwise
+    pub const MIN_HEIGHT: u16 = 4;
+    /// Columns of the narrowest sidebar
+    pub const MIN_WIDTH: u16 = 30;
+
+    /// Replaces the fields of the `key: value` lines of the file, each one
+    /// can be copied.
+    pub fn set_fields(&mut self, fields: Vec<(String, String)>) {
+        let theme = self.theme;
+        self.key_fields = fields
+            .into_iter()
+            .map(|(key, value)| {
+                let action = Action::Password(PasswordAction::CopyField(key.clone()));
+                let mut field = DetailsField::new(Line::from(
+                    key.underlined().italic().bold().fg(theme.details_field_fg),
+                ))
+                .button(
+                    Button::new("Copy".fg(theme.button_label))
+                        .dimensions(10, 3)
+                        .padded()
+                        .action_on_click(action.clone()),
+                )
+                .action_on_click(action);
+                field.set_content(&value);
+                field
+            })
+            .collect();
+        self.shown_key_fields = 0;
+    }
+
     pub fn new() -> Self {
         let theme = Theme::new();
         let pass_id_field = DetailsField::new(Line::from(vec![
@@ -51,7 +113,8 @@ impl PasswordDetails<'_> {
                 .dimensions(10, 3)
                 .padded()
                 .action_on_click(Action::Password(PasswordAction::CopyPassId)),
-        );
+        )
+        .action_on_click(Action::Password(PasswordAction::CopyPassId));
         let lines_field = DetailsField::new(Line::from(vec![
             "Number of lines"
                 .underlined()
@@ -67,6 +130,14 @@ impl PasswordDetails<'_> {
                 .padded()
                 .action_on_click(Action::Navigation(NavigationAction::File)),
         );
+        let fields_field = DetailsField::new(Line::from(vec![
+            "Available fields"
+                .underlined()
+                .italic()
+                .bold()
+                .fg(theme.details_field_fg),
+            " 🗟".fg(theme.details_field_fg),
+        ]));
         let password_field = DetailsField::new(Line::from(vec![
             "Password"
                 .underlined()
@@ -82,7 +153,8 @@ impl PasswordDetails<'_> {
                 .dimensions(10, 3)
                 .padded()
                 .action_on_click(Action::Password(PasswordAction::CopyPassword)),
-        );
+        )
+        .action_on_click(Action::Password(PasswordAction::CopyPassword));
         let otp_field = DetailsField::new(Line::from(vec![
             "One-time password (OTP)"
                 .underlined()
@@ -99,6 +171,7 @@ impl PasswordDetails<'_> {
                 .padded()
                 .action_on_click(Action::Password(PasswordAction::CopyOtp)),
         )
+        .action_on_click(Action::Password(PasswordAction::CopyOtp))
         .button(
             Button::new("Refresh".fg(theme.button_label))
                 .keyboard_label("(r)".fg(theme.button_keyboard_label))
@@ -120,52 +193,123 @@ impl PasswordDetails<'_> {
                 .dimensions(10, 3)
                 .padded()
                 .action_on_click(Action::Password(PasswordAction::CopyLogin)),
-        );
+        )
+        .action_on_click(Action::Password(PasswordAction::CopyLogin));
+        let url_field = DetailsField::new(Line::from(vec![
+            "URL"
+                .underlined()
+                .italic()
+                .bold()
+                .fg(theme.details_field_fg),
+            " 🌐".fg(theme.details_field_fg),
+        ]))
+        .button(
+            Button::new("Open".fg(theme.button_label))
+                .keyboard_label("(o)".fg(theme.button_keyboard_label))
+                .dimensions(10, 3)
+                .padded()
+                .action_on_click(Action::Password(PasswordAction::OpenUrl)),
+        )
+        .action_on_click(Action::Password(PasswordAction::OpenUrl));
         Self {
             show_secrets: false,
             pass_id: None,
             line_count: None,
             password: None,
             one_time_password: None,
+            otp_labels: Vec::new(),
+            otp_index: 0,
+            otp_window: None,
+            otp_remaining: None,
             login: None,
+            url: None,
+            copy_default: None,
+            fetch_error: None,
+            fetched_modified: None,
+            stale: false,
+            target: None,
+            available_fields: None,
             pass_id_field,
             lines_field,
+            fields_field,
             password_field,
             otp_field,
             login_field,
+            url_field,
+            key_fields: Vec::new(),
+            shown_key_fields: 0,
+            sidebar: false,
             theme,
             area: None,
+            focused_button: None,
         }
     }
 
+    /// Wipes the decrypted values before they are replaced or dropped.
+    pub fn wipe_secrets(&mut self) {
+        self.password.zeroize();
+        self.one_time_password.zeroize();
+        self.login.zeroize();
+    }
+
     // Does not reset pass id
     pub fn clear_secrets(&mut self) {
+        self.wipe_secrets();
         self.show_secrets = false;
         self.line_count = None;
         self.password = None;
         self.one_time_password = None;
+        self.otp_labels = Vec::new();
+        self.otp_index = 0;
+        self.otp_window = None;
+        self.otp_remaining = None;
         self.login = None;
+        self.url = None;
+        self.fetch_error = None;
+        self.fetched_modified = None;
+        self.stale = false;
+        self.target = None;
+        self.available_fields = None;
+        self.set_fields(Vec::new());
     }
 
     pub fn reset(&mut self) {
+        self.wipe_secrets();
         self.show_secrets = false;
+        self.copy_default = None;
         self.pass_id = None;
         self.line_count = None;
         self.password = None;
         self.one_time_password = None;
+        self.otp_labels = Vec::new();
+        self.otp_index = 0;
+        self.otp_window = None;
+        self.otp_remaining = None;
         self.login = None;
+        self.url = None;
+        self.fetch_error = None;
+        self.fetched_modified = None;
+        self.stale = false;
+        self.target = None;
+        self.available_fields = None;
+        self.set_fields(Vec::new());
     }
 }
 
 impl Widget for &mut PasswordDetails<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         self.area = Some(area);
-        if area.height < 4 {
+        self.shown_key_fields = 0;
+        if area.height < PasswordDetails::MIN_HEIGHT {
             return;
         }
 
         let block = Block::new()
-            .borders(Borders::TOP)
+            .borders(if self.sidebar {
+                Borders::LEFT
+            } else {
+                Borders::TOP
+            })
             .border_set(symbols::border::FULL)
             .border_style(Style::default().fg(self.theme.details_border))
             .bg(self.theme.standard_bg);
@@ -181,12 +325,21 @@ impl Widget for &mut PasswordDetails<'_> {
         };
         block.render(area, buf);
 
-        let [left_area, right_area] = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .spacing(2)
-            .constraints(Constraint::from_mins([1, 1]))
-            .areas(content_area);
+        // The sidebar stacks the columns
+        let [left_area, right_area] = if self.sidebar {
+            Layout::default()
+                .direction(Direction::Vertical)
+                .horizontal_margin(1)
+                .constraints([Constraint::Length(12), Constraint::Min(0)])
+                .areas(content_area)
+        } else {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .spacing(2)
+                .constraints(Constraint::from_mins([1, 1]))
+                .areas(content_area)
+        };
 
         let left_layout = Layout::default()
             .direction(Direction::Vertical)
@@ -197,6 +350,15 @@ impl Widget for &mut PasswordDetails<'_> {
         if let Some(pass_id) = &self.pass_id {
             let field_area = left_layout[0];
             self.pass_id_field.set_content(pass_id);
+            let info = if self.stale {
+                Some("⚠ changed since decryption, (→) refresh".to_string())
+            } else {
+                self.target
+                    .as_ref()
+                    .map(|target| format!("↪ alias of {target}"))
+            };
+            self.pass_id_field
+                .set_info(info.filter(|_| self.show_secrets));
             self.pass_id_field.render(field_area, buf);
         }
 
@@ -209,26 +371,79 @@ impl Widget for &mut PasswordDetails<'_> {
             }
         }
 
+        // Available fields, shown instead of the number of lines
+        if let Some(fields) = &self.available_fields {
+            if !self.show_secrets {
+                let field_area = left_layout[1];
+                let content = if fields.is_empty() {
+                    "none".to_string()
+                } else {
+                    fields.join(" · ")
+                };
+                self.fields_field.set_content(&content);
+                self.fields_field.render(field_area, buf);
+            }
+        }
+
+        // Count how many fields will be rendered
+        let mut visible_fields = 1;
+        if self.one_time_password.is_some() {
+            visible_fields += 1;
+        }
+        if self.login.is_some() {
+            visible_fields += 1;
+        }
+        if self.url.is_some() {
+            visible_fields += 1;
+        }
+        // The fields of `key: value` lines take the rows that are left
+        let capacity = (right_area.height / 4).max(1) as usize;
+        self.shown_key_fields = if self.show_secrets && self.fetch_error.is_none() {
+            self.key_fields
+                .len()
+                .min(capacity.saturating_sub(visible_fields))
+        } else {
+            0
+        };
+        visible_fields += self.shown_key_fields;
+        let hidden = match self.shown_key_fields {
+            _ if !self.show_secrets || self.fetch_error.is_some() => 0,
+            shown => self.key_fields.len() - shown,
+        };
+
         // Hint
         let hint = if self.show_secrets {
             "(←) Hide secrets  (→) Refresh"
         } else {
             "(←) View list     (→) Secrets"
         };
-        Paragraph::new(vec![Line::default(), Line::from(hint.to_string())])
+        // Fields that don't fit are counted above the hint
+        let more = match hidden {
+            0 => Line::default(),
+            1 => Line::from("+1 more field, (i) shows the file"),
+            count => Line::from(format!("+{count} more fields, (i) shows the file")),
+        };
+        Paragraph::new(vec![more, Line::from(hint.to_string())])
             .style(Style::new().fg(self.theme.details_hint_fg))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
             .render(left_layout[2], buf);
 
-        // Count how many fields will be rendered
-        let mut visible_fields = 1;
-        if self.one_time_password.is_some() {
-            visible_fields += 1;
-        }
-        if self.login.is_some() {
-            visible_fields += 1;
+        // Failed decryption replaces the secret fields
+        if let Some(error) = &self.fetch_error {
+            if self.show_secrets {
+                Paragraph::new(vec![
+                    Line::from("✗ Failed to decrypt — press (r) to retry".fg(self.theme.debug)),
+                    Line::default(),
+                    Line::from(error.clone()),
+                ])
+                .style(Style::new().fg(self.theme.standard_fg))
+                .wrap(Wrap { trim: true })
+                .render(right_area, buf);
+                return;
+            }
         }
+
         let constraints = vec![4; visible_fields];
 
         let right_areas = Layout::vertical(Constraint::from_lengths(constraints))
@@ -238,6 +453,11 @@ impl Widget for &mut PasswordDetails<'_> {
 
         // Password field
         if self.pass_id.is_some() {
+            self.password_field.set_info(
+                self.copy_default
+                    .as_ref()
+                    .map(|label| format!("(y) copies {label}")),
+            );
             let field_area = right_areas.next().expect("counted before");
             if !self.show_secrets {
                 self.password_field.reset_content()
@@ -254,6 +474,24 @@ impl Widget for &mut PasswordDetails<'_> {
             if self.show_secrets {
                 let field_area = right_areas.next().expect("counted before");
                 self.otp_field.set_content(otp);
+                let info = match self.otp_labels.len() {
+                    0 => None,
+                    1 => Some(self.otp_labels[0].clone()),
+                    count => Some(format!(
+                        "{} · {}/{count} (t)",
+                        self.otp_labels[self.otp_index],
+                        self.otp_index + 1
+                    )),
+                };
+                self.otp_field.set_info(info);
+                let gauge =
+                    self.otp_window
+                        .zip(self.otp_remaining)
+                        .map(|((_, period), remaining)| {
+                            let ratio = remaining.as_secs_f64() / period.as_secs_f64().max(1.0);
+                            (ratio.min(1.0), format!("{}s", remaining.as_secs() + 1))
+                        });
+                self.otp_field.set_gauge(gauge);
                 self.otp_field.render(*field_area, buf);
             }
         }
@@ -266,18 +504,75 @@ impl Widget for &mut PasswordDetails<'_> {
                 self.login_field.render(*field_area, buf);
             }
         }
+
+        // URL field
+        if let Some(ref url) = self.url {
+            if self.show_secrets {
+                let field_area = right_areas.next().expect("counted before");
+                self.url_field.set_content(url);
+                self.url_field.render(*field_area, buf);
+            }
+        }
+
+        // Fields of `key: value` lines
+        for field in &mut self.key_fields[..self.shown_key_fields] {
+            let field_area = right_areas.next().expect("counted before");
+            field.render(*field_area, buf);
+        }
+    }
+}
+
+impl<'a> FocusSupport<'a> for PasswordDetails<'a> {
+    /// Returns the buttons of the fields that are shown, the left column
+    /// first.
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        let secrets_shown = self.show_secrets && self.fetch_error.is_none();
+        let mut fields = Vec::new();
+        if self.pass_id.is_some() {
+            fields.push(&mut self.pass_id_field);
+        }
+        if self.show_secrets && self.line_count.is_some() {
+            fields.push(&mut self.lines_field);
+        }
+        if self.pass_id.is_some() && (!self.show_secrets || secrets_shown) {
+            fields.push(&mut self.password_field);
+        }
+        if secrets_shown && self.one_time_password.is_some() {
+            fields.push(&mut self.otp_field);
+        }
+        if secrets_shown && self.login.is_some() {
+            fields.push(&mut self.login_field);
+        }
+        if secrets_shown && self.url.is_some() {
+            fields.push(&mut self.url_field);
+        }
+        if secrets_shown {
+            fields.extend(&mut self.key_fields[..self.shown_key_fields]);
+        }
+        fields
+            .into_iter()
+            .flat_map(|field| field.buttons_mut().iter_mut())
+            .collect()
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
     }
 }
 
 impl MouseSupport for PasswordDetails<'_> {
     fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
-        let fields = [
+        let mut fields = vec![
             &mut self.pass_id_field,
             &mut self.lines_field,
             &mut self.otp_field,
             &mut self.password_field,
             &mut self.login_field,
+            &mut self.url_field,
         ];
+        if self.show_secrets {
+            fields.extend(&mut self.key_fields[..self.shown_key_fields]);
+        }
 
         let mut action = None;
         for field in fields {
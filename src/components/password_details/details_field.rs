@@ -1,11 +1,13 @@
 use ratatui::{
     buffer::Buffer,
-    crossterm::event::MouseEvent,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
     layout::{Alignment, Position, Rect},
-    style::Stylize,
+    style::{Style, Stylize},
     text::{Line, Text},
-    widgets::{Paragraph, Widget},
+    widgets::{LineGauge, Paragraph, Widget},
 };
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
 
 use crate::{
     actions::Action,
@@ -13,23 +15,48 @@ use crate::{
     theme::Theme,
 };
 
+/// Maximum width of the gauge below the content
+const GAUGE_WIDTH: u16 = 24;
+/// How long the content is shown inverted after clicking it
+const FLASH_DURATION: Duration = Duration::from_millis(300);
+
 #[derive(Debug, Default, Clone)]
 pub struct DetailsField<'a> {
     title: Line<'a>,
+    info: Option<String>,
+    gauge: Option<(f64, String)>,
     content: Option<String>,
     placeholder: String,
     buttons: Vec<Button<'a>>,
+    /// Action when the content is clicked, e.g. copying it
+    content_action: Option<Action>,
+    /// Area of the shown content text
+    content_area: Option<Rect>,
+    /// Time the content was last clicked
+    flashed_at: Option<Instant>,
     area: Option<Rect>,
     theme: Theme,
 }
 
+/// The content may be a secret, it is wiped when replaced or dropped
+impl Drop for DetailsField<'_> {
+    fn drop(&mut self) {
+        self.content.zeroize();
+    }
+}
+
 impl<'a> DetailsField<'a> {
     pub fn new<T: Into<Line<'a>>>(title: T) -> Self {
         DetailsField {
             title: title.into(),
+            info: None,
+            gauge: None,
             content: None,
             placeholder: String::default(),
             buttons: Vec::new(),
+            content_action: None,
+            content_area: None,
+            flashed_at: None,
             area: None,
             theme: Theme::new(),
         }
@@ -40,20 +67,55 @@ impl<'a> DetailsField<'a> {
         self
     }
 
+    /// Sets the action when the content is clicked, which is flashed as
+    /// feedback.
+    pub fn action_on_click(mut self, action: Action) -> Self {
+        self.content_action = Some(action);
+        self
+    }
+
+    pub fn buttons_mut(&mut self) -> &mut [Button<'a>] {
+        &mut self.buttons
+    }
+
     pub fn placeholder(mut self, placeholder: &str) -> Self {
         self.placeholder = placeholder.into();
         self
     }
 
     pub fn set_content(&mut self, content: &str) {
+        self.content.zeroize();
         self.content = Some(content.into());
     }
 
     pub fn reset_content(&mut self) {
-        self.content = None;
+        self.content.zeroize();
+    }
+
+    /// Sets additional information displayed next to the title
+    pub fn set_info(&mut self, info: Option<String>) {
+        self.info = info;
+    }
+
+    /// Sets the share of a period that is left with its label, shown as a
+    /// gauge below the content
+    pub fn set_gauge(&mut self, gauge: Option<(f64, String)>) {
+        self.gauge = gauge;
     }
 
     fn in_focus(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if event.kind == MouseEventKind::Down(MouseButton::Left)
+            && self
+                .content_area
+                .is_some_and(|area| area.contains(position))
+        {
+            if let Some(action) = self.content_action.clone() {
+                self.flashed_at = Some(Instant::now());
+                return Some(action);
+            }
+        }
+
         let mut latest_action = None;
         for button in &mut self.buttons {
             if let Some(action) = button.handle_mouse_event(event) {
@@ -109,13 +171,55 @@ impl Widget for &mut DetailsField<'_> {
             content
         };
 
+        self.content_area = Some(Rect {
+            y: area.y + 2,
+            width: (content.chars().count() as u16).min(area.width),
+            height: 1,
+            ..area
+        });
+        let mut content_style = Style::new().bg(theme.standard_bg).fg(theme.standard_fg);
+        if self
+            .flashed_at
+            .is_some_and(|flashed_at| flashed_at.elapsed() < FLASH_DURATION)
+        {
+            content_style = content_style.reversed();
+        }
+
+        let mut title = self.title.clone();
+        if let Some(info) = &self.info {
+            title.push_span(format!("  {info}").fg(theme.details_hint_fg));
+        }
+
         Paragraph::new(Text::from(vec![
-            self.title.clone(),
+            title,
             Line::default(),
-            content.bg(theme.standard_bg).fg(theme.standard_fg).into(),
+            Line::styled(content, content_style),
         ]))
         .alignment(Alignment::Left)
         .render(area, buf);
+
+        if let Some((ratio, label)) = &self.gauge {
+            if area.height >= 4 {
+                let gauge_area = Rect {
+                    y: area.y + 3,
+                    height: 1,
+                    width: max_content_length.saturating_sub(1).min(GAUGE_WIDTH),
+                    ..area
+                };
+                // Running out soon
+                let color = if *ratio < 0.2 {
+                    theme.debug
+                } else {
+                    theme.details_field_fg
+                };
+                LineGauge::default()
+                    .ratio(*ratio)
+                    .label(label.clone().fg(theme.details_hint_fg))
+                    .filled_style(Style::new().fg(color))
+                    .unfilled_style(Style::new().fg(theme.details_border))
+                    .render(gauge_area, buf);
+            }
+        }
     }
 }
 
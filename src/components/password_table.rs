@@ -1,3 +1,8 @@
+use icu::{
+    calendar::{DateTime, Gregorian},
+    datetime::{options::length, TypedDateTimeFormatter},
+    locid::{locale, Locale},
+};
 use passepartout::PasswordInfo;
 use ratatui::{
     buffer::Buffer,
@@ -10,13 +15,206 @@ use ratatui::{
         StatefulWidget, Table, TableState, Widget,
     },
 };
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::{
-    actions::{Action, NavigationAction},
+    actions::{Action, NavigationAction, TableAction},
     components::MouseSupport,
+    matcher,
+    remembered::Remembered,
+    row_format::{Piece, RowFormat},
     theme::Theme,
 };
 
+fn last_modified(info: &PasswordInfo, pending: bool, date_format: &DateFormat) -> String {
+    if pending {
+        "…".to_string()
+    } else {
+        date_format.format(info)
+    }
+}
+
+/// Returns how long ago the entry was modified, e.g. `5d`, `7mo` or `2y`.
+fn age(info: &PasswordInfo, pending: bool, now: SystemTime) -> String {
+    let days = info
+        .metadata
+        .modified()
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .map(|age| age.as_secs() / (24 * 60 * 60));
+    match days {
+        _ if pending => "…".to_string(),
+        None => "?".to_string(),
+        Some(days) if days < 60 => format!("{days}d"),
+        Some(days) if days < 2 * 365 => format!("{}mo", days * 12 / 365),
+        Some(days) => format!("{}y", days / 365),
+    }
+}
+
+/// Returns the remembered login of an entry, empty if it is not known.
+fn login_cell<'a>(logins: &Remembered, pass_id: &str, theme: &Theme) -> Cell<'a> {
+    let login = logins.get(pass_id).unwrap_or_default().to_string();
+    Cell::from(login).style(Style::new().fg(theme.details_hint_fg))
+}
+
+/// Formats the modification dates like the conventions of a locale.
+#[derive(Debug)]
+pub struct DateFormat {
+    formatter: Option<TypedDateTimeFormatter<Gregorian>>,
+}
+
+impl Default for DateFormat {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl DateFormat {
+    /// Uses the locale of the system if none is given, English if it is not
+    /// known.
+    pub fn new(locale: Option<Locale>) -> Self {
+        let locale = locale
+            .or_else(|| sys_locale::get_locale()?.parse().ok())
+            .unwrap_or(locale!("en"));
+        let options = length::Bag::from_date_time_style(length::Date::Medium, length::Time::Short);
+        let formatter = TypedDateTimeFormatter::try_new(&locale.into(), options.into())
+            .or_else(|_| TypedDateTimeFormatter::try_new(&locale!("en").into(), options.into()))
+            .ok();
+        Self { formatter }
+    }
+
+    fn format(&self, info: &PasswordInfo) -> String {
+        let minutes = info
+            .metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .and_then(|duration| i32::try_from(duration.as_secs() / 60).ok());
+        match (&self.formatter, minutes) {
+            (Some(formatter), Some(minutes)) => {
+                // The dates are shown in UTC
+                let datetime =
+                    DateTime::from_minutes_since_local_unix_epoch(minutes).to_calendar(Gregorian);
+                formatter.format_to_string(&datetime)
+            }
+            _ => "Unknown".to_string(),
+        }
+    }
+}
+
+/// Entries marked after their pass ID.
+#[derive(Debug, Default, Clone)]
+pub struct Markers {
+    /// Symbolic links of the store, entries reached through them are marked
+    pub links: Vec<String>,
+    /// Decrypted entries that are aliases of another entry, marked like
+    /// symbolic links
+    pub aliases: Vec<String>,
+    /// Entries that were changed since their secrets were last used
+    pub changed: Vec<String>,
+    /// Entries that were not modified for longer than the configured age
+    pub aged: Vec<String>,
+    /// Entries and folders, with a trailing slash, the user may not read,
+    /// they are greyed out
+    pub unreadable: Vec<String>,
+}
+
+impl Markers {
+    /// Returns the marks of the entry with their styles.
+    fn symbols(&self, pass_id: &str, theme: &Theme) -> Vec<(char, Style)> {
+        let mut symbols = Vec::new();
+        if self.is_linked(pass_id) {
+            symbols.push(('↪', Style::new().fg(theme.details_hint_fg)));
+        }
+        if self.changed.iter().any(|changed| changed == pass_id) {
+            symbols.push(('↻', Style::new().fg(theme.debug)));
+        }
+        if self.aged.iter().any(|aged| aged == pass_id) {
+            symbols.push(('⚠', Style::new().fg(theme.table_aged_marker_fg)));
+        }
+        if self.is_unreadable(pass_id) {
+            symbols.push(('⊘', Style::new().fg(theme.debug)));
+        }
+        symbols
+    }
+
+    /// Returns the marks of the entry shown after its pass ID.
+    fn spans<'a>(&self, pass_id: &str, theme: &Theme) -> Vec<Span<'a>> {
+        self.symbols(pass_id, theme)
+            .into_iter()
+            .map(|(symbol, style)| Span::styled(format!(" {symbol}"), style))
+            .collect()
+    }
+
+    /// Returns true if the entry or one of its folders is a symbolic link,
+    /// aliases are marked like them, see [`crate::alias`].
+    fn is_linked(&self, pass_id: &str) -> bool {
+        self.aliases.iter().any(|alias| alias == pass_id)
+            || self.links.iter().any(|link| {
+                pass_id
+                    .strip_prefix(link.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+    }
+
+    /// Returns true if the entry or one of its folders can't be read.
+    pub fn is_unreadable(&self, pass_id: &str) -> bool {
+        self.unreadable.iter().any(|unreadable| {
+            unreadable == pass_id
+                || unreadable
+                    .strip_suffix('/')
+                    .is_some_and(|folder| pass_id.starts_with(&format!("{folder}/")))
+        })
+    }
+}
+
+/// Width of the column with the modification dates
+const MODIFIED_WIDTH: u16 = 25;
+const LOGIN_WIDTH: u16 = 24;
+const AGE_WIDTH: u16 = 5;
+
+/// Column the table is sorted by
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    #[default]
+    PassId,
+    Modified,
+}
+
+/// Sort order of the table, the entries are in the order of the store,
+/// i.e. by pass ID, if no column is sorted by.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Sort {
+    pub column: Option<SortColumn>,
+    pub descending: bool,
+}
+
+impl Sort {
+    /// Sorts by another column in ascending order, a column that is already
+    /// sorted by cycles through descending and unsorted.
+    pub fn cycle(self, column: SortColumn) -> Self {
+        match (self.column == Some(column), self.descending) {
+            (false, _) => Self {
+                column: Some(column),
+                descending: false,
+            },
+            (true, false) => Self {
+                column: Some(column),
+                descending: true,
+            },
+            (true, true) => Self::default(),
+        }
+    }
+
+    fn indicator(&self, column: SortColumn) -> &'static str {
+        match (self.column == Some(column), self.descending) {
+            (false, _) => "",
+            (true, false) => " ▲",
+            (true, true) => " ▼",
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct PasswordTable<'a> {
     theme: Theme,
@@ -24,129 +222,214 @@ pub struct PasswordTable<'a> {
     length: usize,
     table_state: TableState,
     pub highlight_pattern: Option<String>,
+    /// Highlight the characters found by the fuzzy matcher
+    pub fuzzy: bool,
+    pub pinned_rows: usize,
+    /// Folder the table is restricted to, shown in the header
+    pub scope: Option<String>,
+    /// Sort order, shown in the header
+    pub sort: Sort,
+    /// Show a placeholder for the dates until the store has been scanned
+    pub dates_pending: bool,
+    /// Show an A–Z index strip for jumping with the mouse
+    pub index_strip: bool,
+    pub markers: Markers,
+    /// Logins shown in a column between the pass ID and the date, the
+    /// column is hidden if `None`
+    pub logins: Option<Remembered>,
+    /// Time the ages of entries are counted up to, the age column is hidden
+    /// if `None`
+    pub ages_at: Option<SystemTime>,
+    pub date_format: DateFormat,
+    /// Layout of the pass ID column, the pass ID followed by the marks if
+    /// `None`
+    pub row_format: Option<RowFormat>,
+    index_area: Option<Rect>,
+    index_letters: Vec<char>,
     scrollbar_state: ScrollbarState,
     area: Option<Rect>,
+    header_area: Option<Rect>,
+    /// Header cells that sort the table by their column when clicked
+    header_cells: Vec<(Rect, SortColumn)>,
     mouse_content_area: Option<Rect>,
     mouse_track_area: Option<Rect>,
+    /// Row dragged with the mouse and the row it is over, which is
+    /// highlighted as the target for moving it to the folder of that row
+    drag: Option<(usize, Option<usize>)>,
 }
 
 impl<'a> PasswordTable<'a> {
     pub fn new(passwords: &[&PasswordInfo]) -> Self {
-        let theme = Theme::new();
-        let rows = Self::build_rows(passwords, &theme);
-        let length = rows.len();
-        let table = Self::build_table(rows, &theme);
-        let scrollbar_state = ScrollbarState::new(length);
-        Self {
-            theme,
-            table,
-            length,
+        let mut password_table = Self {
+            theme: Theme::new(),
+            table: Table::default(),
+            length: 0,
             table_state: TableState::new(),
             highlight_pattern: None,
-            scrollbar_state,
+            fuzzy: false,
+            pinned_rows: 0,
+            scope: None,
+            sort: Sort::default(),
+            dates_pending: false,
+            index_strip: false,
+            markers: Markers::default(),
+            logins: None,
+            ages_at: None,
+            date_format: DateFormat::default(),
+            row_format: None,
+            index_area: None,
+            index_letters: Vec::new(),
+            scrollbar_state: ScrollbarState::default(),
             area: None,
+            header_area: None,
+            header_cells: Vec::new(),
             mouse_content_area: None,
             mouse_track_area: None,
-        }
+            drag: None,
+        };
+        password_table.update_passwords(passwords);
+        password_table
     }
 
     pub fn update_passwords(&mut self, passwords: &[&PasswordInfo]) {
-        let rows = if let Some(pattern) = &self.highlight_pattern {
-            passwords
-                .iter()
-                .enumerate()
-                .map(|(i, info)| {
-                    let bg_color = match i % 2 {
-                        0 => self.theme.table_normal_row,
-                        _ => self.theme.table_alt_row,
-                    };
-                    let pass_id = info.id.clone();
-                    let pass_id_parts: Vec<_> = if !pattern.is_empty() {
-                        let pass_id_lower = pass_id.to_lowercase();
-                        let pattern_lower = pattern.to_lowercase();
-
-                        if let Some(_first_idx) = pass_id_lower.find(&pattern_lower) {
-                            let mut styled_parts = Vec::new();
-                            let mut last_idx = 0;
-
-                            pass_id_lower
-                                .match_indices(&pattern_lower)
-                                .for_each(|(idx, _)| {
-                                    // Add non-matching part
-                                    if idx > last_idx {
-                                        styled_parts.push(Span::styled(
-                                            pass_id[last_idx..idx].to_string(),
-                                            Style::default().fg(self.theme.table_row_fg),
-                                        ));
-                                    }
-
-                                    // Add matching part
-                                    styled_parts.push(Span::styled(
-                                        pass_id[idx..idx + pattern.len()].to_string(),
-                                        Style::default()
-                                            .fg(self.theme.table_row_fg)
-                                            .bg(self.theme.table_pattern_highlight_bg)
-                                            .add_modifier(Modifier::BOLD),
-                                    ));
-
-                                    last_idx = idx + pattern.len();
-                                });
-
-                            // Add remaining part
-                            if last_idx < pass_id.len() {
-                                styled_parts.push(Span::styled(
-                                    pass_id[last_idx..].to_string(),
-                                    Style::default().fg(self.theme.table_row_fg),
-                                ));
-                            }
-
-                            styled_parts
-                        } else {
-                            vec![Span::styled(
-                                pass_id,
-                                Style::default().fg(self.theme.table_row_fg),
-                            )]
-                        }
-                    } else {
-                        vec![Span::styled(
-                            pass_id,
-                            Style::default().fg(self.theme.table_row_fg),
-                        )]
-                    };
-
-                    Row::new(vec![
-                        Cell::from(Line::from(pass_id_parts)),
-                        Cell::from(info.last_modified()),
-                    ])
-                    .style(Style::default().fg(self.theme.table_row_fg).bg(bg_color))
-                })
-                .collect()
-        } else {
-            Self::build_rows(passwords, &self.theme)
-        };
+        let rows: Vec<Row<'a>> = passwords
+            .iter()
+            .enumerate()
+            .map(|(i, info)| {
+                let bg_color = match i % 2 {
+                    0 => self.theme.table_normal_row,
+                    _ => self.theme.table_alt_row,
+                };
+                let positions = self
+                    .highlight_pattern
+                    .as_ref()
+                    .and_then(|pattern| matcher::find(pattern, &info.id, self.fuzzy))
+                    .map(|found| found.positions)
+                    .unwrap_or_default();
+
+                let mut cells = vec![Cell::from(self.pass_id_line(&info.id, &positions))];
+                if let Some(logins) = &self.logins {
+                    cells.push(login_cell(logins, &info.id, &self.theme));
+                }
+                if let Some(now) = self.ages_at {
+                    cells.push(Cell::from(age(info, self.dates_pending, now)));
+                }
+                cells.push(Cell::from(last_modified(
+                    info,
+                    self.dates_pending,
+                    &self.date_format,
+                )));
+                let fg_color = if self.markers.is_unreadable(&info.id) {
+                    self.theme.table_unreadable_row_fg
+                } else {
+                    self.theme.table_row_fg
+                };
+                Row::new(cells).style(Style::default().fg(fg_color).bg(bg_color))
+            })
+            .collect();
+
+        // Divider below pinned entries
+        let rows: Vec<Row<'a>> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                if i + 1 == self.pinned_rows && i + 1 < passwords.len() {
+                    row.underlined()
+                } else {
+                    row
+                }
+            })
+            .collect();
 
         self.length = rows.len();
-        self.table = Self::build_table(rows, &self.theme);
+        self.table = Self::build_table(
+            rows,
+            &self.theme,
+            self.scope.as_deref(),
+            self.sort,
+            self.logins.is_some(),
+            self.ages_at.is_some(),
+        );
         self.table_state = TableState::new();
         self.scrollbar_state = ScrollbarState::new(self.length);
     }
 
-    fn build_rows(passwords: &[&PasswordInfo], theme: &Theme) -> Vec<Row<'a>> {
-        passwords
-            .iter()
-            .enumerate()
-            .map(|(i, info)| {
-                let color = match i % 2 {
-                    0 => theme.table_normal_row,
-                    _ => theme.table_alt_row,
-                };
-                Row::new(vec![info.id.clone(), info.last_modified()])
-                    .style(Style::new().fg(theme.table_row_fg).bg(color))
-            })
-            .collect()
+    /// Replaces the displayed passwords while keeping the scroll position,
+    /// e.g. after the store has been re-scanned.
+    pub fn replace_passwords(&mut self, passwords: &[&PasswordInfo]) {
+        let offset = self.table_state.offset();
+        self.update_passwords(passwords);
+        *self.table_state.offset_mut() = offset.min(self.length.saturating_sub(1));
+    }
+
+    /// Splits the pass ID into spans with the matched characters
+    /// highlighted.
+    fn highlight(&self, pass_id: &str, positions: &[usize]) -> Vec<Span<'a>> {
+        let normal = Style::default().fg(self.theme.table_row_fg);
+        let highlighted = normal
+            .bg(self.theme.table_pattern_highlight_bg)
+            .add_modifier(Modifier::BOLD);
+        let mut spans = Vec::new();
+        let mut part = String::new();
+        let mut part_matched = false;
+        for (i, c) in pass_id.chars().enumerate() {
+            let matched = positions.contains(&i);
+            if matched != part_matched && !part.is_empty() {
+                let style = if part_matched { highlighted } else { normal };
+                spans.push(Span::styled(std::mem::take(&mut part), style));
+            }
+            part_matched = matched;
+            part.push(c);
+        }
+        let style = if part_matched { highlighted } else { normal };
+        spans.push(Span::styled(part, style));
+        spans
+    }
+
+    /// Returns the pass ID with the matched characters highlighted and its
+    /// marks, laid out with the row format if one is configured.
+    fn pass_id_line(&self, pass_id: &str, positions: &[usize]) -> Line<'a> {
+        let Some(row_format) = &self.row_format else {
+            let mut spans = self.highlight(pass_id, positions);
+            spans.extend(self.markers.spans(pass_id, &self.theme));
+            return Line::from(spans);
+        };
+        let symbols = self.markers.symbols(pass_id, &self.theme);
+        let flags: String = symbols.iter().map(|(symbol, _)| *symbol).collect();
+        let mut spans = Vec::new();
+        for piece in row_format.format(pass_id, &flags) {
+            match piece {
+                Piece::Text(text) => spans.push(Span::from(text)),
+                Piece::Id { text, offset } => {
+                    let positions: Vec<usize> = positions
+                        .iter()
+                        .filter_map(|position| position.checked_sub(offset))
+                        .collect();
+                    spans.extend(self.highlight(&text, &positions));
+                }
+                Piece::Flags(text) => spans.extend(text.chars().map(|c| {
+                    let style = symbols
+                        .iter()
+                        .find(|(symbol, _)| *symbol == c)
+                        .map_or_else(Style::new, |(_, style)| *style);
+                    Span::styled(c.to_string(), style)
+                })),
+            }
+        }
+        if !row_format.has_flags() {
+            spans.extend(self.markers.spans(pass_id, &self.theme));
+        }
+        Line::from(spans)
     }
 
-    fn build_table(rows: Vec<Row<'a>>, theme: &Theme) -> Table<'a> {
+    fn build_table(
+        rows: Vec<Row<'a>>,
+        theme: &Theme,
+        scope: Option<&str>,
+        sort: Sort,
+        login_column: bool,
+        age_column: bool,
+    ) -> Table<'a> {
         let header_style = Style::default()
             .fg(theme.table_header_fg)
             .bg(theme.table_header_bg);
@@ -157,13 +440,31 @@ impl<'a> PasswordTable<'a> {
         let selected_cell_style = Style::default()
             .add_modifier(Modifier::REVERSED)
             .fg(theme.table_selected_cell_style_fg);
-        let header = ["Password file", "Last modified (UTC)"]
+        let title = match scope {
+            Some(folder) => format!("Password file in {folder}/"),
+            None => "Password file".to_string(),
+        };
+        let mut header = vec![format!("{title}{}", sort.indicator(SortColumn::PassId))];
+        let mut widths = vec![Constraint::Min(25)];
+        if login_column {
+            header.push("Login".to_string());
+            widths.push(Constraint::Length(LOGIN_WIDTH));
+        }
+        if age_column {
+            header.push("Age".to_string());
+            widths.push(Constraint::Length(AGE_WIDTH));
+        }
+        header.push(format!(
+            "Last modified (UTC){}",
+            sort.indicator(SortColumn::Modified)
+        ));
+        widths.push(Constraint::Max(MODIFIED_WIDTH));
+        let header = header
             .into_iter()
             .map(Cell::from)
             .collect::<Row>()
             .style(header_style)
             .height(1);
-        let widths = [Constraint::Min(25), Constraint::Max(25)];
         Table::new(rows.clone(), widths)
             .column_spacing(1)
             .style(Style::new().white())
@@ -185,6 +486,76 @@ impl<'a> PasswordTable<'a> {
     pub fn selected(&self) -> Option<usize> {
         self.table_state.selected()
     }
+
+    /// Returns the width of the columns right of the login column, which
+    /// show the dates of entries.
+    fn dates_width(&self) -> u16 {
+        match self.ages_at {
+            Some(_) => MODIFIED_WIDTH + AGE_WIDTH + 1,
+            None => MODIFIED_WIDTH,
+        }
+    }
+
+    /// Returns the areas of the header cells of the sortable columns, the
+    /// dates and ages both sort by the modification date.
+    fn header_cells(&self, header_area: Rect) -> Vec<(Rect, SortColumn)> {
+        let cell = |left: u16, right: u16| Rect {
+            x: left,
+            width: right.saturating_sub(left),
+            ..header_area
+        };
+        let right = header_area.right();
+        let dates_left = right.saturating_sub(self.dates_width());
+        let mut pass_id_right = dates_left.saturating_sub(1);
+        if self.logins.is_some() {
+            pass_id_right = pass_id_right.saturating_sub(LOGIN_WIDTH + 1);
+        }
+        let mut cells = vec![(cell(header_area.x, pass_id_right), SortColumn::PassId)];
+        match self.ages_at {
+            Some(_) => {
+                let ages_right = dates_left + AGE_WIDTH;
+                cells.push((cell(dates_left, ages_right), SortColumn::Modified));
+                cells.push((cell(ages_right + 1, right), SortColumn::Modified));
+            }
+            None => cells.push((cell(dates_left, right), SortColumn::Modified)),
+        }
+        cells
+    }
+
+    /// Returns true if the position is in the login column, which ends
+    /// before the date columns.
+    fn in_login_column(&self, position: Position) -> bool {
+        let Some(header_area) = self.header_area.filter(|_| self.logins.is_some()) else {
+            return false;
+        };
+        let right = header_area.right().saturating_sub(self.dates_width() + 1);
+        (right.saturating_sub(LOGIN_WIDTH)..right).contains(&position.x)
+    }
+}
+
+impl PasswordTable<'_> {
+    /// Renders the letters A–Z below each other, letters are skipped
+    /// evenly if the area is not high enough.
+    fn render_index_strip(&mut self, area: Rect, buf: &mut Buffer) {
+        let letters: Vec<char> = ('A'..='Z').collect();
+        let rows = (area.height as usize).min(letters.len());
+        self.index_letters = (0..rows)
+            .map(|row| letters[row * letters.len() / rows])
+            .collect();
+        self.index_area = Some(Rect {
+            height: rows as u16,
+            ..area
+        });
+        buf.set_style(area, Style::new().bg(self.theme.table_header_bg));
+        for (row, letter) in self.index_letters.iter().enumerate() {
+            buf.set_string(
+                area.x,
+                area.y + row as u16,
+                letter.to_string(),
+                Style::new().fg(self.theme.table_header_fg),
+            );
+        }
+    }
 }
 
 impl Widget for &mut PasswordTable<'_> {
@@ -192,8 +563,23 @@ impl Widget for &mut PasswordTable<'_> {
         self.area = Some(area);
         let theme = self.theme;
 
-        let [table_area, right_area] =
+        let [mut table_area, right_area] =
             Layout::horizontal([Constraint::Min(1), Constraint::Length(1)]).areas(area);
+
+        // Index strip
+        if self.index_strip && table_area.width > 2 && table_area.height > 1 {
+            let [remaining_area, strip_area] =
+                Layout::horizontal([Constraint::Min(1), Constraint::Length(1)]).areas(table_area);
+            table_area = remaining_area;
+            let strip_area = Rect {
+                y: strip_area.y + 1,
+                height: strip_area.height - 1,
+                ..strip_area
+            };
+            self.render_index_strip(strip_area, buf);
+        } else {
+            self.index_area = None;
+        }
         let [above_track_area, track_area] =
             Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(right_area);
         buf.set_style(above_track_area, Style::new().bg(theme.table_header_bg));
@@ -201,6 +587,12 @@ impl Widget for &mut PasswordTable<'_> {
         buf.set_style(track_area, Style::new().bg(theme.standard_fg));
 
         // Calculate areas for mouse interaction
+        let header_area = Rect {
+            height: 1,
+            ..table_area
+        };
+        self.header_area = Some(header_area);
+        self.header_cells = self.header_cells(header_area);
         let mouse_content_area = Rect {
             x: area.x,
             y: area.y + 1,
@@ -217,6 +609,22 @@ impl Widget for &mut PasswordTable<'_> {
 
         StatefulWidget::render(&self.table, table_area, buf, &mut self.table_state);
 
+        // Row a dragged row is dropped on, below the header
+        if let Some((_, Some(target))) = self.drag {
+            let line = target
+                .checked_sub(self.table_state.offset())
+                .map(|line| line as u16 + 1)
+                .filter(|line| *line < table_area.height);
+            if let Some(line) = line {
+                let target_area = Rect {
+                    y: table_area.y + line,
+                    height: 1,
+                    ..table_area
+                };
+                buf.set_style(target_area, Style::new().bg(theme.table_drop_target_bg));
+            }
+        }
+
         Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
             .track_style(
@@ -235,6 +643,42 @@ impl MouseSupport for PasswordTable<'_> {
     fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
         let position = Position::new(event.column, event.row);
 
+        // Releasing a dragged row anywhere drops it on the row it was last
+        // over
+        if event.kind == MouseEventKind::Up(MouseButton::Left) {
+            if let Some((from, to)) = self.drag.take() {
+                return to.map(|to| Action::Table(TableAction::Drop { from, to }));
+            }
+        }
+
+        // Mouse position on the header row, only the cells of sortable
+        // columns react to clicks
+        if let Some(area) = self.header_area {
+            if area.contains(position) {
+                return match event.kind {
+                    MouseEventKind::Down(MouseButton::Left) => self
+                        .header_cells
+                        .iter()
+                        .find(|(cell, _)| cell.contains(position))
+                        .map(|&(_, column)| Action::Table(TableAction::Sort(column))),
+                    _ => None,
+                };
+            }
+        }
+
+        // Mouse position on the index strip
+        if let Some(area) = self.index_area {
+            if area.contains(position) {
+                return match event.kind {
+                    MouseEventKind::Down(MouseButton::Left) => {
+                        let letter = self.index_letters[(position.y - area.y) as usize];
+                        Some(Action::Navigation(NavigationAction::JumpToLetter(letter)))
+                    }
+                    _ => None,
+                };
+            }
+        }
+
         // Mouse position on password table contents
         if let Some(area) = self.mouse_content_area {
             if area.contains(position) {
@@ -242,7 +686,21 @@ impl MouseSupport for PasswordTable<'_> {
                     MouseEventKind::Down(MouseButton::Left) => {
                         let line = position.y - area.y;
                         let i = self.table_state.offset() + line as usize;
-                        Some(Action::Navigation(NavigationAction::SelectAndFetch(i)))
+                        if self.in_login_column(position) {
+                            Some(Action::Table(TableAction::CopyLogin(i)))
+                        } else {
+                            self.drag = (i < self.length).then_some((i, None));
+                            Some(Action::Navigation(NavigationAction::SelectAndFetch(i)))
+                        }
+                    }
+                    MouseEventKind::Drag(MouseButton::Left) => {
+                        let (from, to) = self.drag.as_mut()?;
+                        let i = self.table_state.offset() + (position.y - area.y) as usize;
+                        *to = (i != *from && i < self.length).then_some(i);
+                        Some(Action::Table(TableAction::DragOver {
+                            from: *from,
+                            to: *to,
+                        }))
                     }
                     MouseEventKind::ScrollDown => Some(Action::Navigation(NavigationAction::Down)),
                     MouseEventKind::ScrollUp => Some(Action::Navigation(NavigationAction::Up)),
@@ -0,0 +1,259 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::MouseEvent,
+    layout::{Constraint, Direction, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, FormAction, NavigationAction},
+    completion,
+    components::{Button, FocusSupport, MouseSupport, TextInput},
+    theme::Theme,
+};
+
+/// Number of completions listed below the input
+const LISTED_COMPLETIONS: usize = 4;
+
+/// What the entered path is used for
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum PromptPurpose {
+    /// Renames the entry with the ID
+    Move(String),
+    /// Opens the form for a new entry inside the folder
+    #[default]
+    NewFolder,
+    /// Selects the entry matching the host of a URL
+    LookupUrl,
+    /// Writes the session log to the file
+    ExportSessionLog,
+}
+
+/// Returns why the input can not be submitted given the paths of the store.
+pub type Validator = fn(&str, &[String]) -> Option<String>;
+
+/// Prompt for a single path with completion against the paths of the store.
+#[derive(Debug, Default, Clone)]
+pub struct PromptPopup<'a> {
+    area: Option<Rect>,
+    theme: Theme,
+    purpose: PromptPurpose,
+    input: TextInput,
+    validate: Option<Validator>,
+    /// Entries and folders of the store, folders end with a slash
+    paths: Vec<String>,
+    /// Set after editing if the input is invalid
+    error: Option<String>,
+    submit_button: Button<'a>,
+    cancel_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl PromptPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        PromptPopup {
+            theme,
+            cancel_button: Button::new("Cancel".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(14, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    /// Prefills the prompt with the text, the paths are used for completion.
+    pub fn open(
+        &mut self,
+        purpose: PromptPurpose,
+        text: &str,
+        validate: Validator,
+        paths: Vec<String>,
+    ) {
+        let theme = self.theme;
+        let label = match purpose {
+            PromptPurpose::Move(_) => "Move",
+            PromptPurpose::NewFolder => "Create",
+            PromptPurpose::LookupUrl => "Look up",
+            PromptPurpose::ExportSessionLog => "Export",
+        };
+        self.submit_button = Button::new(label.fg(theme.button_label))
+            .keyboard_label("(↵)".fg(theme.button_keyboard_label))
+            .dimensions(label.chars().count() as u16 + 8, 3)
+            .padded()
+            .action_on_click(Action::Form(FormAction::Submit));
+        self.purpose = purpose;
+        self.input = TextInput::new(text, text.chars().count());
+        self.validate = Some(validate);
+        self.paths = paths;
+        self.error = None;
+        self.focused_button = None;
+    }
+
+    pub fn update(&mut self, action: FormAction) {
+        match action {
+            FormAction::NextField => self.focus_next(),
+            FormAction::PreviousField => self.focus_previous(),
+            FormAction::Complete => {
+                self.clear_focus();
+                self.complete();
+            }
+            action => {
+                self.clear_focus();
+                self.input.edit(action);
+                self.error = self.validate();
+            }
+        }
+    }
+
+    pub fn purpose(&self) -> &PromptPurpose {
+        &self.purpose
+    }
+
+    pub fn text(&self) -> String {
+        self.input.get_content().trim().to_string()
+    }
+
+    /// Returns why the input can not be submitted.
+    pub fn validate(&self) -> Option<String> {
+        self.validate
+            .and_then(|validate| validate(&self.text(), &self.paths))
+    }
+
+    /// Extends the input with the part that all completions share.
+    fn complete(&mut self) {
+        if let Some(text) = completion::complete(&self.paths, &self.input.get_content()) {
+            self.input = TextInput::new(&text, text.chars().count());
+            self.error = self.validate();
+        }
+    }
+}
+
+impl Widget for &mut PromptPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // The prompt only takes the space it needs
+        let [area] = Layout::horizontal([Constraint::Length(64)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Constraint::Length(10)])
+            .flex(Flex::Center)
+            .areas(area);
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let (title, label, description) = match &self.purpose {
+            PromptPurpose::Move(pass_id) => (
+                "Move entry",
+                "To:",
+                Line::from(vec!["From: ".fg(theme.details_field_fg), pass_id.into()]),
+            ),
+            PromptPurpose::NewFolder => (
+                "New folder",
+                "Folder:",
+                Line::from("Opens the form for the first entry in the folder"),
+            ),
+            PromptPurpose::LookupUrl => (
+                "Look up URL",
+                "URL:",
+                Line::from("Selects the entry whose URL or name matches the host"),
+            ),
+            PromptPurpose::ExportSessionLog => (
+                "Export session log",
+                "File:",
+                Line::from("Writes the actions of this session to the file"),
+            ),
+        };
+        let block = Block::bordered()
+            .title(Line::from(title).fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        Paragraph::new(description)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(layout[0], buf);
+
+        let [label_area, input_area] =
+            Layout::horizontal([Constraint::Length(8), Constraint::Min(1)]).areas(layout[1]);
+        Paragraph::new(Line::from(label.bold()))
+            .style(Style::new().fg(theme.details_field_fg))
+            .render(label_area, buf);
+        Paragraph::new(self.input.lines(false, self.focused_button.is_none()))
+            .style(Style::new().fg(theme.standard_fg))
+            .render(input_area, buf);
+
+        // Paths the input can be completed to, otherwise a problem with it
+        let text = self.input.get_content();
+        let completions = completion::completions(&self.paths, &text);
+        let completable = completions.iter().any(|completion| *completion != text);
+        let hint = match &self.error {
+            Some(error) if !completable => Line::from(format!("✗ {error}").fg(theme.debug)),
+            _ => {
+                let mut listed = completions
+                    .iter()
+                    .take(LISTED_COMPLETIONS)
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join("  ");
+                if completions.len() > LISTED_COMPLETIONS {
+                    listed.push_str(&format!("  +{}", completions.len() - LISTED_COMPLETIONS));
+                }
+                Line::from(listed.fg(theme.details_hint_fg))
+            }
+        };
+        let [_, hint_area] =
+            Layout::horizontal([Constraint::Length(8), Constraint::Min(1)]).areas(layout[2]);
+        Paragraph::new(hint).render(hint_area, buf);
+
+        let [submit_area, cancel_area] = Layout::horizontal([
+            Constraint::Length(self.submit_button.dimensions.0),
+            Constraint::Length(self.cancel_button.dimensions.0),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[4]);
+        self.submit_button.render(submit_area, buf);
+        self.cancel_button.render(cancel_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for PromptPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.submit_button, &mut self.cancel_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for PromptPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        self.submit_button
+            .handle_mouse_event(event)
+            .or_else(|| self.cancel_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
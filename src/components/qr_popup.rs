@@ -0,0 +1,278 @@
+use qrcode::{render::unicode::Dense1x2, QrCode};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::MouseEvent,
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget, Wrap},
+};
+
+use crate::{
+    actions::{Action, NavigationAction},
+    components::{Button, FocusSupport, MouseSupport},
+    entry,
+    theme::Theme,
+};
+
+/// A QR code with the title it is shown with.
+#[derive(Debug, Clone)]
+struct QrCodeView {
+    title: String,
+    /// Rows of the rendered QR code or why there is none
+    code: Result<Vec<String>, String>,
+    /// The `otpauth://` URI of a one-time password
+    uri: Option<String>,
+}
+
+/// Shows QR codes of an entry, either for joining its Wi-Fi network or for
+/// its one-time passwords and its password, e.g. to enroll an authenticator
+/// app on a phone.
+#[derive(Debug, Default, Clone)]
+pub struct QrPopup<'a> {
+    area: Option<Rect>,
+    theme: Theme,
+    pass_id: Option<String>,
+    codes: Vec<QrCodeView>,
+    /// Index of the shown QR code
+    index: usize,
+    /// The URI is shown as text instead of the QR code
+    show_uri: bool,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl QrPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        QrPopup {
+            area: None,
+            theme,
+            pass_id: None,
+            codes: Vec::new(),
+            index: 0,
+            show_uri: false,
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
+        }
+    }
+
+    /// Shows the QR code for joining the Wi-Fi network of the entry.
+    pub fn set_wifi(&mut self, pass_id: &str, file_contents: &str) {
+        self.pass_id = Some(pass_id.to_string());
+        let payload = entry::wifi_qr_payload(file_contents)
+            .ok_or_else(|| "No Wi-Fi network found, add a line like `ssid: Name`".to_string());
+        self.codes = vec![QrCodeView {
+            title: "Wi-Fi QR code".to_string(),
+            code: payload.and_then(|payload| render_code(&payload)),
+            uri: None,
+        }];
+        self.index = 0;
+    }
+
+    /// Shows the QR codes of the `otpauth://` URIs of the entry followed by
+    /// the one of the password, starting with the given one-time password.
+    pub fn set_secrets(&mut self, pass_id: &str, file_contents: &str, otp_index: usize) {
+        self.pass_id = Some(pass_id.to_string());
+        self.codes = entry::otp_uris(file_contents)
+            .into_iter()
+            .map(|otp_uri| QrCodeView {
+                title: match otp_uri.label() {
+                    Some(label) => format!("OTP QR code · {label}"),
+                    None => "OTP QR code".to_string(),
+                },
+                code: render_code(&otp_uri.uri),
+                uri: Some(otp_uri.uri),
+            })
+            .collect();
+        let otp_count = self.codes.len();
+        let password = file_contents.lines().next().unwrap_or_default();
+        self.codes.push(QrCodeView {
+            title: "Password QR code".to_string(),
+            code: if password.is_empty() {
+                Err("The password is empty".to_string())
+            } else {
+                render_code(password)
+            },
+            uri: None,
+        });
+        self.index = otp_index.min(otp_count);
+    }
+
+    /// Shows the next QR code of the entry.
+    pub fn next(&mut self) {
+        if !self.codes.is_empty() {
+            self.index = (self.index + 1) % self.codes.len();
+        }
+    }
+
+    /// Switches between the QR code and the text of the URI, returns false
+    /// if the shown code has no URI.
+    pub fn toggle_uri(&mut self) -> bool {
+        if self.otp_index().is_none() {
+            return false;
+        }
+        self.show_uri = !self.show_uri;
+        true
+    }
+
+    /// Returns the index of the shown one-time password among the
+    /// `otpauth://` URIs of the entry.
+    pub fn otp_index(&self) -> Option<usize> {
+        self.codes
+            .get(self.index)
+            .and_then(|view| view.uri.as_ref())
+            .map(|_| self.index)
+    }
+
+    pub fn reset_content(&mut self) {
+        self.pass_id = None;
+        self.codes = Vec::new();
+        self.index = 0;
+        self.show_uri = false;
+    }
+}
+
+/// Renders the payload as rows of unicode half blocks.
+fn render_code(payload: &str) -> Result<Vec<String>, String> {
+    let code =
+        QrCode::new(payload.as_bytes()).map_err(|e| format!("Failed to create QR code: {e}"))?;
+    Ok(code
+        .render::<Dense1x2>()
+        .quiet_zone(true)
+        .build()
+        .lines()
+        .map(String::from)
+        .collect())
+}
+
+impl Widget for &mut QrPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+        let view = self.codes.get(self.index);
+
+        let title = view.map_or("QR code", |view| view.title.as_str());
+        let mut block = Block::bordered()
+            .title(Line::from(title).fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        if view.is_some_and(|view| view.uri.is_some()) {
+            let toggle = if self.show_uri { "QR code" } else { "URI" };
+            block = block.title_bottom(
+                Line::from(format!(" (u) {toggle} · (E) export "))
+                    .fg(theme.details_hint_fg)
+                    .left_aligned(),
+            );
+        }
+        if self.codes.len() > 1 {
+            block = block.title_bottom(
+                Line::from(format!(
+                    " (t) next {}/{} ",
+                    self.index + 1,
+                    self.codes.len()
+                ))
+                .fg(theme.details_hint_fg)
+                .right_aligned(),
+            );
+        }
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        if let Some(pass_id) = self.pass_id.clone() {
+            Paragraph::new(Line::from(vec![
+                "Password file ID: ".fg(theme.debug),
+                pass_id.into(),
+            ]))
+            .style(Style::new().fg(theme.standard_fg))
+            .render(layout[0], buf);
+        }
+
+        match view.map(|view| (&view.code, &view.uri)) {
+            Some((_, Some(uri))) if self.show_uri => {
+                Paragraph::new(uri.as_str())
+                    .style(Style::new().fg(theme.standard_fg))
+                    .wrap(Wrap { trim: false })
+                    .render(layout[1], buf);
+            }
+            Some((Err(message), _)) => {
+                Paragraph::new(message.as_str())
+                    .style(Style::new().fg(theme.details_hint_fg))
+                    .alignment(Alignment::Center)
+                    .render(layout[1], buf);
+            }
+            Some((Ok(code), _)) if !code.is_empty() => {
+                render_rows(code, layout[1], theme, buf);
+            }
+            _ => (),
+        }
+
+        let [button_area] = Layout::horizontal([Constraint::Length(13)])
+            .flex(Flex::Center)
+            .areas(layout[2]);
+        self.close_button.render(button_area, buf);
+    }
+}
+
+/// Renders the rows of a QR code in the middle of the area if they fit.
+fn render_rows(code: &[String], area: Rect, theme: Theme, buf: &mut Buffer) {
+    let width = code[0].chars().count() as u16;
+    let height = code.len() as u16;
+    if width > area.width || height > area.height {
+        Paragraph::new("Enlarge the terminal to show the QR code")
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(area, buf);
+        return;
+    }
+    let [code_area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [code_area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(code_area);
+    // Dark modules on a light background regardless of the colors of the
+    // terminal
+    let lines: Vec<Line> = code.iter().map(|row| Line::from(row.as_str())).collect();
+    Paragraph::new(lines)
+        .style(Style::new().fg(Color::Black).bg(Color::White))
+        .render(code_area, buf);
+}
+
+impl<'a> FocusSupport<'a> for QrPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.close_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for QrPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        self.close_button
+            .handle_mouse_event(event)
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
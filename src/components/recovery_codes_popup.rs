@@ -0,0 +1,201 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, NavigationAction, PasswordAction},
+    components::{Button, FocusSupport, MouseSupport},
+    entry::RecoveryCode,
+    theme::Theme,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct RecoveryCodesPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    pass_id: Option<String>,
+    codes: Vec<RecoveryCode>,
+    selected: usize,
+    offset: usize,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl RecoveryCodesPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        RecoveryCodesPopup {
+            area: None,
+            list_area: None,
+            theme,
+            pass_id: None,
+            codes: Vec::new(),
+            selected: 0,
+            offset: 0,
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            focused_button: None,
+        }
+    }
+
+    pub fn set_content(&mut self, pass_id: &str, codes: Vec<RecoveryCode>) {
+        if self.pass_id.as_deref() != Some(pass_id) {
+            self.selected = 0;
+            self.offset = 0;
+        }
+        self.pass_id = Some(pass_id.into());
+        self.selected = self.selected.min(codes.len().saturating_sub(1));
+        self.codes = codes;
+    }
+
+    pub fn reset_content(&mut self) {
+        self.pass_id = None;
+        self.codes = Vec::new();
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.codes.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_code(&self) -> Option<&RecoveryCode> {
+        self.codes.get(self.selected)
+    }
+}
+
+impl Widget for &mut RecoveryCodesPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from("Recovery codes")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        if let Some(pass_id) = self.pass_id.clone() {
+            let unused = self.codes.iter().filter(|code| !code.used).count();
+            Paragraph::new(Line::from(vec![
+                "Password file ID: ".fg(theme.debug),
+                pass_id.into(),
+                format!("  ({unused} of {} unused)", self.codes.len()).into(),
+            ]))
+            .alignment(Alignment::Left)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(layout[0], buf);
+        }
+
+        // Keep the selected code visible
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        let lines: Vec<Line> = if self.codes.is_empty() && self.pass_id.is_some() {
+            vec![Line::from(
+                "No recovery codes found".fg(theme.details_hint_fg),
+            )]
+        } else {
+            self.codes
+                .iter()
+                .enumerate()
+                .skip(self.offset)
+                .take(height)
+                .map(|(i, code)| {
+                    let mut line = Line::from(format!("{:>3}. {}", i + 1, code.code));
+                    if code.used {
+                        line = line.crossed_out().dim();
+                    }
+                    if i == self.selected {
+                        line = line.reversed();
+                    }
+                    line
+                })
+                .collect()
+        };
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        Paragraph::new(Line::from(
+            "(↓) (↑) Select  (y) (↵) Copy  (u) Copy and mark as used",
+        ))
+        .style(Style::new().fg(theme.details_hint_fg))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+
+        let [button_area] = Layout::horizontal([Constraint::Length(13)])
+            .flex(Flex::Center)
+            .areas(layout[3]);
+        self.close_button.render(button_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for RecoveryCodesPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.close_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for RecoveryCodesPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = self.offset + (position.y - area.y) as usize;
+                if index < self.codes.len() {
+                    self.selected = index;
+                    return Some(Action::Password(PasswordAction::CopyRecoveryCode));
+                }
+            }
+        }
+        self.close_button
+            .handle_mouse_event(event)
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
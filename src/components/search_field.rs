@@ -20,6 +20,12 @@ pub struct SearchField {
     characters: Vec<char>,
     cursor_position: usize,
     suspended: bool,
+    /// Folder the search is restricted to
+    pub scope: Option<String>,
+    /// The password files are searched instead of the pass IDs
+    pub contents: bool,
+    /// The path of an entry to go to is typed instead of a pattern
+    pub goto: bool,
     theme: Theme,
 }
 
@@ -31,6 +37,9 @@ impl SearchField {
             characters: Vec::new(),
             cursor_position: 0,
             suspended: false,
+            scope: None,
+            contents: false,
+            goto: false,
             theme,
         }
     }
@@ -77,6 +86,12 @@ impl SearchField {
         self.cursor_position = self.characters.len();
     }
 
+    /// Replaces the content, the cursor is placed at the end.
+    pub fn set_content(&mut self, content: &str) {
+        self.characters = content.chars().collect();
+        self.cursor_position = self.characters.len();
+    }
+
     pub fn reset(&mut self) {
         self.characters = Vec::new();
         self.cursor_position = 0;
@@ -126,8 +141,22 @@ impl Widget for &mut SearchField {
         self.area = Some(area);
         let theme = self.theme;
 
+        let search = if self.contents {
+            "Search password files"
+        } else {
+            "Search"
+        };
+        let title = match &self.scope {
+            _ if self.goto => Line::from(vec!["Go to entry ".into(), "(⇥ complete)".dim()]),
+            Some(folder) => Line::from(vec![
+                format!("{search} in ").into(),
+                format!("{folder}/").bold(),
+                " (⇥ all)".dim(),
+            ]),
+            None => Line::from(search),
+        };
         let block = Block::bordered()
-            .title(Line::from("Search").fg(theme.standard_fg).left_aligned())
+            .title(title.fg(theme.standard_fg).left_aligned())
             .bg(theme.search_bg)
             .border_set(symbols::border::ROUNDED)
             .border_style(Style::new().fg(theme.search_border));
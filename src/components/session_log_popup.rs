@@ -0,0 +1,186 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, NavigationAction},
+    components::{Button, FocusSupport, MouseSupport},
+    theme::Theme,
+};
+
+/// Shows the actions taken on entries in this session, the latest at the
+/// bottom.
+#[derive(Debug, Default, Clone)]
+pub struct SessionLogPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    /// Lines of the action log split into time, pass ID and action
+    lines: Vec<(String, String, String)>,
+    /// First visible line, scrolled to the latest ones when opened
+    offset: usize,
+    export_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl SessionLogPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        SessionLogPopup {
+            theme,
+            export_button: Button::new("Export".fg(theme.button_label))
+                .keyboard_label("(s)".fg(theme.button_keyboard_label))
+                .dimensions(14, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::ExportSessionLog)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the shown lines and scrolls to the latest ones.
+    pub fn set_lines(&mut self, lines: &[String]) {
+        self.lines = lines
+            .iter()
+            .map(|line| {
+                let mut parts = line.splitn(3, '\t').map(String::from);
+                (
+                    parts.next().unwrap_or_default(),
+                    parts.next().unwrap_or_default(),
+                    parts.next().unwrap_or_default(),
+                )
+            })
+            .collect();
+        self.offset = usize::MAX;
+    }
+
+    pub fn next(&mut self) {
+        self.offset = self.offset.saturating_add(1);
+    }
+
+    pub fn previous(&mut self) {
+        self.offset = self.offset.saturating_sub(1);
+    }
+}
+
+impl Widget for &mut SessionLogPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(Line::from("Session log").fg(theme.standard_fg).centered())
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let summary = match self.lines.len() {
+            0 => "No actions were taken on entries in this session".to_string(),
+            1 => "1 action was taken on entries in this session".to_string(),
+            count => format!("{count} actions were taken on entries in this session"),
+        };
+        Paragraph::new(Line::from(summary))
+            .style(Style::new().fg(theme.debug))
+            .render(layout[0], buf);
+
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        self.offset = self.offset.min(self.lines.len().saturating_sub(height));
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(self.offset)
+            .take(height)
+            .map(|(time, pass_id, what)| {
+                Line::from(vec![
+                    Span::from(format!("{time}  ")).fg(theme.details_hint_fg),
+                    Span::from(format!("{pass_id}  ")).bold(),
+                    Span::from(what.as_str()),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        Paragraph::new(Line::from(
+            "Entries are logged by their ID only, never with a secret",
+        ))
+        .style(Style::new().fg(theme.details_hint_fg))
+        .alignment(Alignment::Center)
+        .render(layout[2], buf);
+
+        let [export_area, close_area] =
+            Layout::horizontal([Constraint::Length(14), Constraint::Length(13)])
+                .flex(Flex::Center)
+                .spacing(2)
+                .areas(layout[3]);
+        self.export_button.render(export_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for SessionLogPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![&mut self.export_button, &mut self.close_button]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for SessionLogPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) {
+                match event.kind {
+                    MouseEventKind::ScrollDown => {
+                        self.next();
+                        return Some(Action::NoOp);
+                    }
+                    MouseEventKind::ScrollUp => {
+                        self.previous();
+                        return Some(Action::NoOp);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.export_button
+            .handle_mouse_event(event)
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
@@ -1,16 +1,69 @@
-use crate::theme::Theme;
+use crate::{clock::Moment, theme::Theme};
+use anyhow::{anyhow, Result};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Style, Stylize},
     text::Line,
     widgets::{Paragraph, Widget},
 };
+use std::time::Duration;
+
+/// Identical errors within this time are counted instead of replacing the
+/// status
+const REPEAT_WINDOW: Duration = Duration::from_secs(3);
+
+/// Parts of the status bar, shown in the configured order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// Status messages, takes the remaining width
+    Message,
+    /// Seconds until a copied secret is cleared from the clipboard
+    Clipboard,
+    /// Branch of the store with commits ahead and behind and uncommitted
+    /// changes
+    Git,
+    /// Shown if the store is opened read-only
+    Lock,
+    /// Position of the selected entry in the table
+    Position,
+}
+
+impl Segment {
+    pub const DEFAULT: [Segment; 5] = [
+        Segment::Message,
+        Segment::Clipboard,
+        Segment::Git,
+        Segment::Lock,
+        Segment::Position,
+    ];
+
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "message" => Ok(Segment::Message),
+            "clipboard" => Ok(Segment::Clipboard),
+            "git" => Ok(Segment::Git),
+            "lock" => Ok(Segment::Lock),
+            "position" => Ok(Segment::Position),
+            _ => Err(anyhow!("unknown status bar segment `{name}`")),
+        }
+    }
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct StatusBar {
     text: String,
     theme: Theme,
+    /// Last error and when it was last reported
+    last_error: Option<(String, Moment)>,
+    repeats: usize,
+    pub segments: Vec<Segment>,
+    /// Time left until a copied secret is cleared from the clipboard
+    pub clipboard_remaining: Option<Duration>,
+    pub git_status: Option<String>,
+    pub read_only: bool,
+    /// Selected entry and number of entries in the table
+    pub position: Option<(usize, usize)>,
 }
 
 impl StatusBar {
@@ -18,34 +71,90 @@ impl StatusBar {
         Self {
             text: "Ready".into(),
             theme: Theme::new(),
+            last_error: None,
+            repeats: 0,
+            segments: Segment::DEFAULT.to_vec(),
+            clipboard_remaining: None,
+            git_status: None,
+            read_only: false,
+            position: None,
         }
     }
 
     pub fn set_status(&mut self, message: String) {
-        self.text = message;
+        if !message.starts_with('✗') {
+            self.last_error = None;
+            self.text = message;
+            return;
+        }
+        let repeated = self
+            .last_error
+            .as_ref()
+            .is_some_and(|(last, reported)| *last == message && reported.elapsed() < REPEAT_WINDOW);
+        if repeated {
+            self.repeats += 1;
+            self.text = format!("{message} (×{})", self.repeats + 1);
+        } else {
+            self.repeats = 0;
+            self.text = message.clone();
+        }
+        self.last_error = Some((message, Moment::now()));
     }
 
     pub fn reset_status(&mut self) {
         self.text = "Ready".into();
     }
+
+    /// Returns the text of an indicator, `None` hides it.
+    fn indicator(&self, segment: Segment) -> Option<String> {
+        match segment {
+            Segment::Message => None,
+            Segment::Clipboard => {
+                let remaining = self.clipboard_remaining?;
+                Some(format!("clipboard {}s", remaining.as_secs() + 1))
+            }
+            Segment::Git => self.git_status.clone(),
+            Segment::Lock => self.read_only.then(|| "read-only".to_string()),
+            Segment::Position => self
+                .position
+                .map(|(selected, total)| format!("{}/{total}", selected + 1)),
+        }
+    }
 }
 
 impl Widget for &mut StatusBar {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let theme = self.theme;
-        Paragraph::new(Line::from(&*self.text))
-            .style(
-                Style::default()
-                    .bg(theme.status_bar_bg)
-                    .fg(theme.status_bar_fg),
-            )
-            .render(area, buf);
+        let style = Style::default()
+            .bg(theme.status_bar_bg)
+            .fg(theme.status_bar_fg);
+        buf.set_style(area, style);
+
+        let parts: Vec<(Segment, Option<String>)> = self
+            .segments
+            .iter()
+            .map(|&segment| (segment, self.indicator(segment)))
+            .filter(|(segment, text)| *segment == Segment::Message || text.is_some())
+            .collect();
+        let constraints = parts
+            .iter()
+            .map(|(_, text)| match text {
+                Some(text) => Constraint::Length(text.chars().count() as u16 + 3),
+                None => Constraint::Fill(1),
+            })
+            .chain([Constraint::Length(2)]);
+        let areas = Layout::horizontal(constraints).split(area);
+
+        for (i, ((_, text), &area)) in parts.iter().zip(areas.iter()).enumerate() {
+            let separator = if i == 0 { " " } else { " │ " };
+            let line = match text {
+                Some(text) => Line::from(vec![separator.fg(theme.menu_logo_fg), text.into()]),
+                None => Line::from(&*self.text),
+            };
+            Paragraph::new(line).style(style).render(area, buf);
+        }
         Paragraph::new(Line::from("α").right_aligned().fg(theme.menu_logo_fg))
-            .style(
-                Style::default()
-                    .bg(theme.status_bar_bg)
-                    .fg(theme.status_bar_fg),
-            )
-            .render(area, buf);
+            .style(style)
+            .render(areas[areas.len() - 1], buf);
     }
 }
@@ -0,0 +1,252 @@
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::{Alignment, Constraint, Direction, Flex, Layout, Position, Rect},
+    style::{Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{Block, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::{
+    actions::{Action, Audit, NavigationAction, ScanAction, StrengthAction},
+    components::{Button, FocusSupport, MouseSupport},
+    strength::Weakness,
+    theme::Theme,
+};
+
+/// Lists the entries with weak passwords for changing them.
+///
+/// Entries are checked from the decrypted files in the cache, the others
+/// are listed as unchecked until they are decrypted.
+#[derive(Debug, Default, Clone)]
+pub struct StrengthPopup<'a> {
+    area: Option<Rect>,
+    list_area: Option<Rect>,
+    theme: Theme,
+    /// Entries that were checked and have a weak password, sorted by pass ID
+    weak: Vec<(String, Weakness)>,
+    /// Number of checked entries with a strong password
+    strong: usize,
+    /// Entries that were not decrypted yet
+    unchecked: Vec<String>,
+    selected: usize,
+    offset: usize,
+    decrypt_button: Button<'a>,
+    open_button: Button<'a>,
+    close_button: Button<'a>,
+    focused_button: Option<usize>,
+}
+
+impl StrengthPopup<'_> {
+    pub fn new() -> Self {
+        let theme = Theme::new();
+        StrengthPopup {
+            theme,
+            decrypt_button: Button::new("Decrypt".fg(theme.button_label))
+                .keyboard_label("(d)".fg(theme.button_keyboard_label))
+                .dimensions(15, 3)
+                .padded()
+                .action_on_click(Action::Scan(ScanAction::Decrypt(Audit::Strength))),
+            open_button: Button::new("Go to".fg(theme.button_label))
+                .keyboard_label("(↵)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Strength(StrengthAction::Open)),
+            close_button: Button::new("Close".fg(theme.button_label))
+                .keyboard_label("(Esc)".fg(theme.button_keyboard_label))
+                .dimensions(13, 3)
+                .padded()
+                .action_on_click(Action::Navigation(NavigationAction::Back)),
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the results, e.g. when the popup is opened again.
+    pub fn set_entries(
+        &mut self,
+        weak: Vec<(String, Weakness)>,
+        strong: usize,
+        unchecked: Vec<String>,
+    ) {
+        self.weak = weak;
+        self.strong = strong;
+        self.unchecked = unchecked;
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    /// Moves a decrypted entry from the unchecked ones to the results.
+    pub fn checked(&mut self, pass_id: String, weakness: Option<Weakness>) {
+        self.unchecked.retain(|unchecked| *unchecked != pass_id);
+        match weakness {
+            Some(weakness) => {
+                let index = self.weak.partition_point(|(weak, _)| *weak < pass_id);
+                self.weak.insert(index, (pass_id, weakness));
+            }
+            None => self.strong += 1,
+        }
+    }
+
+    pub fn unchecked(&self) -> &[String] {
+        &self.unchecked
+    }
+
+    pub fn weak_count(&self) -> usize {
+        self.weak.len()
+    }
+
+    pub fn selected_pass_id(&self) -> Option<&str> {
+        self.weak
+            .get(self.selected)
+            .map(|(pass_id, _)| pass_id.as_str())
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1).min(self.weak.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl Widget for &mut StrengthPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.area = Some(area);
+        let theme = self.theme;
+
+        let block = Block::bordered()
+            .title(
+                Line::from("Weak passwords")
+                    .fg(theme.standard_fg)
+                    .centered(),
+            )
+            .padding(Padding::horizontal(1))
+            .bg(theme.standard_bg)
+            .border_set(symbols::border::ROUNDED)
+            .border_style(Style::new().fg(theme.popup_border));
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+            ])
+            .split(block.inner(area));
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let checked = self.weak.len() + self.strong;
+        let summary = if self.unchecked.is_empty() {
+            format!(
+                "{} of {checked} entries have a weak password",
+                self.weak.len()
+            )
+        } else {
+            format!(
+                "{} of {checked} checked entries have a weak password, {} are not decrypted yet",
+                self.weak.len(),
+                self.unchecked.len()
+            )
+        };
+        Paragraph::new(Line::from(summary))
+            .style(Style::new().fg(theme.debug))
+            .render(layout[0], buf);
+
+        // Keep the selected entry visible
+        let list_area = layout[1];
+        self.list_area = Some(list_area);
+        let height = list_area.height as usize;
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if height > 0 && self.selected >= self.offset + height {
+            self.offset = self.selected + 1 - height;
+        }
+
+        let lines: Vec<Line> = self
+            .weak
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(height)
+            .map(|(i, (pass_id, weakness))| {
+                let line = Line::from(vec![
+                    Span::from(pass_id.as_str()),
+                    Span::from(format!("  ~{} bits, {}", weakness.bits, weakness.reason))
+                        .fg(theme.details_hint_fg),
+                ]);
+                if i == self.selected {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(lines)
+            .style(Style::new().fg(theme.standard_fg))
+            .render(list_area, buf);
+
+        let hint = if self.unchecked.is_empty() {
+            Line::from("Passwords are rated by length, kinds of characters and common patterns")
+        } else {
+            Line::from("(d) Decrypts the unchecked entries to check them as well")
+        };
+        Paragraph::new(hint)
+            .style(Style::new().fg(theme.details_hint_fg))
+            .alignment(Alignment::Center)
+            .render(layout[2], buf);
+
+        let [decrypt_area, open_area, close_area] = Layout::horizontal([
+            Constraint::Length(15),
+            Constraint::Length(13),
+            Constraint::Length(13),
+        ])
+        .flex(Flex::Center)
+        .spacing(2)
+        .areas(layout[3]);
+        self.decrypt_button.render(decrypt_area, buf);
+        self.open_button.render(open_area, buf);
+        self.close_button.render(close_area, buf);
+    }
+}
+
+impl<'a> FocusSupport<'a> for StrengthPopup<'a> {
+    fn buttons(&mut self) -> Vec<&mut Button<'a>> {
+        vec![
+            &mut self.decrypt_button,
+            &mut self.open_button,
+            &mut self.close_button,
+        ]
+    }
+
+    fn focused_button(&mut self) -> &mut Option<usize> {
+        &mut self.focused_button
+    }
+}
+
+impl MouseSupport for StrengthPopup<'_> {
+    fn handle_mouse_event(&mut self, event: MouseEvent) -> Option<Action> {
+        let position = Position::new(event.column, event.row);
+        if let Some(area) = self.list_area {
+            if area.contains(position) && event.kind == MouseEventKind::Down(MouseButton::Left) {
+                let index = self.offset + (position.y - area.y) as usize;
+                if index < self.weak.len() {
+                    self.selected = index;
+                    return Some(Action::NoOp);
+                }
+            }
+        }
+        self.decrypt_button
+            .handle_mouse_event(event)
+            .or_else(|| self.open_button.handle_mouse_event(event))
+            .or_else(|| self.close_button.handle_mouse_event(event))
+            .or(Some(Action::NoOp))
+    }
+
+    fn get_area(&self) -> Option<Rect> {
+        self.area
+    }
+}
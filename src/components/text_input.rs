@@ -0,0 +1,78 @@
+use ratatui::{style::Stylize, text::Line, text::Span};
+
+use crate::actions::FormAction;
+
+/// Editable text of an input in a form, may span multiple lines.
+#[derive(Debug, Default, Clone)]
+pub struct TextInput {
+    characters: Vec<char>,
+    cursor_position: usize,
+}
+
+impl TextInput {
+    /// Creates an input with the given text and the cursor at the position.
+    pub fn new(text: &str, cursor_position: usize) -> Self {
+        let characters: Vec<char> = text.chars().collect();
+        TextInput {
+            cursor_position: cursor_position.min(characters.len()),
+            characters,
+        }
+    }
+
+    pub fn insert(&mut self, character: char) {
+        self.characters.insert(self.cursor_position, character);
+        self.cursor_position += 1;
+    }
+
+    /// Applies the editing actions of a form, other actions are ignored.
+    pub fn edit(&mut self, action: FormAction) {
+        match action {
+            FormAction::Insert(character) => self.insert(character),
+            FormAction::RemoveLeft if self.cursor_position > 0 => {
+                self.cursor_position -= 1;
+                let _ = self.characters.remove(self.cursor_position);
+            }
+            FormAction::RemoveRight if self.cursor_position < self.characters.len() => {
+                let _ = self.characters.remove(self.cursor_position);
+            }
+            FormAction::MoveLeft => self.cursor_position = self.cursor_position.saturating_sub(1),
+            FormAction::MoveRight => {
+                self.cursor_position = self.characters.len().min(self.cursor_position + 1)
+            }
+            FormAction::MoveToStart => self.cursor_position = 0,
+            FormAction::MoveToEnd => self.cursor_position = self.characters.len(),
+            _ => (),
+        }
+    }
+
+    pub fn get_content(&self) -> String {
+        String::from_iter(&self.characters)
+    }
+
+    /// Returns the lines of the input, the character at the cursor position
+    /// is underlined if the input has the focus.
+    pub fn lines(&self, masked: bool, focused: bool) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::default()];
+        for (i, &character) in self.characters.iter().enumerate() {
+            let cursor = focused && i == self.cursor_position;
+            if character == '\n' {
+                if cursor {
+                    lines.last_mut().unwrap().push_span("⏎".slow_blink());
+                }
+                lines.push(Line::default());
+                continue;
+            }
+            let character = if masked { '•' } else { character };
+            let span = Span::from(character.to_string());
+            lines.last_mut().unwrap().push_span(if cursor {
+                span.underlined().slow_blink()
+            } else {
+                span
+            });
+        }
+        if focused && self.cursor_position == self.characters.len() {
+            lines.last_mut().unwrap().push_span("_".slow_blink());
+        }
+        lines
+    }
+}
@@ -0,0 +1,795 @@
+use anyhow::{anyhow, Context, Result};
+use icu::locid::Locale;
+use std::{env, fs, path::PathBuf, time::Duration};
+use toml::{Table, Value};
+
+use crate::{
+    clipboard,
+    components::{PasswordDetails, Segment},
+    entry::Field,
+    keymap::Keymap,
+    pass::Pass,
+    row_format::RowFormat,
+    store::Excludes,
+};
+
+/// Configuration read from `$XDG_CONFIG_HOME/passepartui/config.toml`.
+///
+/// ```toml
+/// [clipboard]
+/// # Field copied with (y): "password", "login", "otp", the number of a line
+/// # or the key of a `key: value` line in the password file
+/// default_field = "password"
+/// # Seconds until a copied secret is replaced with the previous contents of
+/// # the clipboard, 0 keeps it, defaults to `PASSWORD_STORE_CLIP_TIME` or 45
+/// clear_after = 45
+/// # "system" for X11 or Wayland, "primary" for their primary selection,
+/// # "osc52" for asking the terminal with an escape sequence, e.g. over SSH,
+/// # "auto" for the terminal if the system clipboard is not available or a
+/// # command that reads the text from stdin, defaults to "osc52" over SSH
+/// provider = "auto"
+/// # Selection set by the terminal: "clipboard", "primary" or "both"
+/// osc52_selection = "clipboard"
+/// # Maximum length of the escape sequence in bytes, larger copies fail
+/// # instead of being dropped by the terminal, 0 for no limit
+/// osc52_limit = 100000
+///
+/// # Overrides for folders of the password store
+/// [clipboard.folders]
+/// "work/api" = "token"
+///
+/// # Providers for what is copied: "password", "login", "otp", "field" (a
+/// # `key: value` line), "path", "line" (of the file), "recovery_code" and
+/// # "generated"
+/// [clipboard.providers]
+/// login = "primary"
+/// otp = ["wl-copy", "--type", "text/plain"]
+///
+/// [table]
+/// # Show an A–Z index strip next to the table for jumping with the mouse
+/// index_strip = false
+/// # Show the login of entries in a column, the logins of decrypted entries
+/// # are remembered in plain text in the data directory for it
+/// login_column = false
+/// # Mark entries with ↻ that were changed since you last copied or showed
+/// # their secrets, e.g. a credential rotated by someone else in a shared store
+/// mark_changed = false
+/// # Show how long ago entries were last modified in a column
+/// age_column = false
+/// # Mark entries with ⚠ that were not modified for this many months as due
+/// # for a new password, 0 disables the mark
+/// max_age_months = 0
+/// # Locale for formatting the modification dates, e.g. "de-DE", defaults to
+/// # the locale of the system
+/// locale = "en"
+/// # Layout of the pass ID column, `{id}`, `{folder}`, `{name}` and `{flags}`
+/// # (the marks like ↪) are filled in and padded to a width aligned with `<`,
+/// # `>` or `^`, by default the pass ID is followed by the marks
+/// row_format = "{folder:<20} {name} {flags:>6}"
+///
+/// [details]
+/// # Where the details pane is shown, "bottom" below the table or "right" of
+/// # it as a sidebar for wide terminals, (|) switches while running
+/// position = "bottom"
+/// # Lines of the details pane below the table or columns of the sidebar, (+)
+/// # and (-) change them while running and (z) collapses the pane, small
+/// # terminals get less
+/// height = 14
+/// width = 60
+/// # Start with the details pane collapsed
+/// collapsed = false
+/// # Keep showing secrets while moving through the entries instead of
+/// # switching to the preview, an entry is decrypted once the selection rests
+/// # on it for the delay in milliseconds, so holding (j) doesn't ask the
+/// # agent for every entry on the way
+/// follow_selection = false
+/// follow_delay_ms = 300
+///
+/// [pass]
+/// # Name or path of the `pass` executable
+/// program = "pass"
+/// # Sets `PASSWORD_STORE_ENABLE_EXTENSIONS` and `PASSWORD_STORE_EXTENSIONS_DIR`
+/// # for `pass`, otherwise the variables are taken from the environment
+/// extensions = true
+/// extensions_dir = "~/.password-store/.extensions"
+///
+/// [store]
+/// # Paths skipped when scanning the store, like in `.gitignore` a pattern
+/// # without a slash matches a file or folder name at any depth
+/// exclude = [".obsolete/**", "*.bak"]
+/// # Update the table when entries are added, removed or renamed outside of
+/// # passepartui, e.g. by `pass`, git or a file syncing tool
+/// watch = true
+/// # Command run when starting that prints the path of the password store
+/// # to open, e.g. picked by hostname for sharing the configuration between
+/// # machines, nothing printed keeps `PASSWORD_STORE_DIR` or the default
+/// select_command = ["sh", "-c", "case $(hostname) in work-*) echo ~/.work-store;; esac"]
+///
+/// [git]
+/// # Run `pass git pull --rebase` in the background when starting
+/// pull_on_start = false
+/// # Run `pass git push` when quitting if there are unpushed commits, the app
+/// # stays open if pushing fails
+/// push_on_exit = false
+///
+/// [search]
+/// # Match the characters of the search pattern in order but not necessarily
+/// # next to each other and rank the results, e.g. "ghper" finds
+/// # "github/personal", otherwise the pattern has to be part of the ID
+/// fuzzy = true
+/// # Start searching when typing a character that is not bound to an action
+/// # in the table, (Esc) shows all entries again
+/// type_to_search = false
+///
+/// [status_bar]
+/// # Parts of the status bar in this order: "message", "clipboard" (seconds
+/// # until a copied secret is cleared), "git" (branch, commits ahead and
+/// # behind, uncommitted changes), "lock" (read-only) and "position"
+/// segments = ["message", "clipboard", "git", "lock", "position"]
+///
+/// [templates]
+/// # Keys of `key: value` lines that are prefilled for new entries
+/// wifi = ["ssid", "security"]
+///
+/// # Template used when creating an entry inside a folder
+/// [templates.folders]
+/// "wifi" = "wifi"
+///
+/// [commands]
+/// # Commands that get the password of the selected entry on stdin, `{login}`,
+/// # `{pass_id}` and the keys of `key: value` lines are filled in
+/// docker-login = ["docker", "login", "--username", "{login}", "--password-stdin", "{registry}"]
+///
+/// [archive]
+/// # Folder entries are moved to when archiving them
+/// folder = "archive"
+/// # Leave archived entries out of the table and the search unless the
+/// # archive folder is entered
+/// hidden = true
+///
+/// [audit]
+/// # Entries neither used nor modified for this many days are listed as stale
+/// days = 365
+///
+/// [history]
+/// # Program comparing two decrypted versions of an entry, `{old}` and `{new}`
+/// # are replaced with the paths of temporary files that are removed when it
+/// # exits, so it has to keep running until the comparison is closed. It runs
+/// # without a terminal, so it has to be a graphical tool
+/// difftool = ["meld", "{old}", "{new}"]
+///
+/// [otp]
+/// # Command that gets the `otpauth://` URI shown in the QR codes popup on
+/// # stdin with (E), e.g. for moving it to another authenticator, `{pass_id}`
+/// # and the keys of `key: value` lines are filled in
+/// export = ["ykman", "oath", "accounts", "uri"]
+/// # What happens with a one-time password once it is generated for the
+/// # details: "off", "copy" to the clipboard or a command that types it from
+/// # stdin, e.g. ["wtype", "-"] or ["xdotool", "type", "--file", "-"]
+/// auto = "off"
+///
+/// # Keys for actions in a state of the app, replacing the default keys of
+/// # the action, e.g. `"j"`, `"<Down>"`, `"<C-s>"` or a chord like `"gg"`
+/// [keys.table]
+/// top = ["gg", "<Home>"]
+///
+/// [cache]
+/// # Seconds decrypted password files are kept in memory, 0 disables the cache
+/// ttl = 60
+/// # Maximum number of cached password files
+/// size = 16
+///
+/// [security]
+/// # Disable core dumps and, on Linux, keep other processes from reading the
+/// # memory of passepartui
+/// harden_process = true
+/// # Start even if other users can list the entries of the store
+/// allow_world_readable_store = false
+/// # Append the entries that were viewed, copied or changed to a log in the
+/// # data directory, with pass IDs only and never secrets
+/// action_log = false
+/// # Lock like with (Ctrl+L) when the desktop session is locked, which is
+/// # noticed through systemd-logind
+/// lock_with_session = false
+/// ```
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub default_field: Field,
+    pub folder_fields: Vec<(String, Field)>,
+    pub clear_after: Duration,
+    pub clipboard: clipboard::Options,
+    pub folder_templates: Vec<(String, Template)>,
+    pub commands: Vec<EntryCommand>,
+    pub keymap: Keymap,
+    pub audit_period: Duration,
+    pub difftool: Vec<String>,
+    pub otp_export: Vec<String>,
+    pub otp_auto: OtpAuto,
+    pub archive_folder: String,
+    pub archive_hidden: bool,
+    pub index_strip: bool,
+    pub login_column: bool,
+    pub mark_changed: bool,
+    pub age_column: bool,
+    /// Entries not modified for longer are marked, `None` if disabled
+    pub max_age: Option<Duration>,
+    pub row_format: Option<RowFormat>,
+    pub details_position: DetailsPosition,
+    pub details_height: u16,
+    pub details_width: u16,
+    pub details_collapsed: bool,
+    pub follow_selection: bool,
+    pub follow_delay: Duration,
+    pub date_locale: Option<Locale>,
+    pub fuzzy_search: bool,
+    pub type_to_search: bool,
+    pub excludes: Excludes,
+    pub watch_store: bool,
+    pub select_store: Vec<String>,
+    pub pass: Pass,
+    pub pull_on_start: bool,
+    pub push_on_exit: bool,
+    pub status_segments: Vec<Segment>,
+    pub cache_ttl: Duration,
+    pub cache_size: usize,
+    pub harden_process: bool,
+    pub allow_world_readable_store: bool,
+    pub action_log: bool,
+    pub lock_with_session: bool,
+}
+
+/// Fields prefilled in the form for new entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub name: String,
+    pub keys: Vec<String>,
+}
+
+/// What is done with a one-time password once it is generated for the
+/// details.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum OtpAuto {
+    #[default]
+    Off,
+    /// Copied to the clipboard
+    Copy,
+    /// Typed by a program with its arguments that reads it from stdin
+    Type(Vec<String>),
+}
+
+/// Where the details pane is shown next to the table.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum DetailsPosition {
+    #[default]
+    Bottom,
+    /// A sidebar on the right of the table
+    Right,
+}
+
+impl DetailsPosition {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "bottom" => Ok(Self::Bottom),
+            "right" => Ok(Self::Right),
+            _ => Err(anyhow!("unknown details position `{name}`")),
+        }
+    }
+}
+
+/// Command run with the password of an entry on stdin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryCommand {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl EntryCommand {
+    /// Logs in to a container registry without the password showing up in
+    /// the process list or on the clipboard.
+    fn docker_login() -> Self {
+        Self {
+            name: "docker-login".to_string(),
+            args: [
+                "docker",
+                "login",
+                "--username",
+                "{login}",
+                "--password-stdin",
+                "{registry}",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            default_field: Field::default(),
+            folder_fields: Vec::new(),
+            clear_after: env::var("PASSWORD_STORE_CLIP_TIME")
+                .ok()
+                .and_then(|seconds| seconds.parse().ok())
+                .map_or(clipboard::EXPIRATION_INTERVAL, Duration::from_secs),
+            clipboard: clipboard::Options::default(),
+            folder_templates: Vec::new(),
+            commands: vec![EntryCommand::docker_login()],
+            keymap: Keymap::default(),
+            audit_period: Duration::from_secs(365 * 24 * 60 * 60),
+            difftool: Vec::new(),
+            otp_export: Vec::new(),
+            otp_auto: OtpAuto::default(),
+            archive_folder: "archive".to_string(),
+            archive_hidden: true,
+            index_strip: false,
+            login_column: false,
+            mark_changed: false,
+            age_column: false,
+            max_age: None,
+            row_format: None,
+            details_position: DetailsPosition::default(),
+            details_height: 14,
+            details_width: 60,
+            details_collapsed: false,
+            follow_selection: false,
+            follow_delay: Duration::from_millis(300),
+            date_locale: None,
+            fuzzy_search: true,
+            type_to_search: false,
+            excludes: Excludes::default(),
+            watch_store: true,
+            select_store: Vec::new(),
+            pass: Pass::default(),
+            pull_on_start: false,
+            push_on_exit: false,
+            status_segments: Segment::DEFAULT.to_vec(),
+            cache_ttl: Duration::from_secs(60),
+            cache_size: 16,
+            harden_process: true,
+            allow_world_readable_store: false,
+            action_log: false,
+            lock_with_session: false,
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("passepartui").join("config.toml"))
+    }
+
+    /// Loads the configuration file, a missing file results in the default
+    /// configuration.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).context(format!("failed to read {}", path.display())),
+        };
+        Self::parse(&content).context(format!("invalid configuration in {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let table: Table = content.parse()?;
+        let mut config = Self::default();
+
+        if let Some(clipboard) = table.get("clipboard") {
+            let clipboard = as_table(clipboard, "clipboard")?;
+            if let Some(value) = clipboard.get("default_field") {
+                config.default_field = Field::from_name(as_str(value, "default_field")?);
+            }
+            if let Some(value) = clipboard.get("clear_after") {
+                config.clear_after = Duration::from_secs(as_count(value, "clear_after")?);
+            }
+            if let Some(value) = clipboard.get("provider") {
+                config.clipboard.provider = as_provider(value, "provider")?;
+            }
+            if let Some(providers) = clipboard.get("providers") {
+                for (content, value) in as_table(providers, "clipboard.providers")? {
+                    config.clipboard.providers.push((
+                        clipboard::Content::from_name(content)?,
+                        as_provider(value, content)?,
+                    ));
+                }
+            }
+            if let Some(value) = clipboard.get("osc52_selection") {
+                config.clipboard.selection =
+                    clipboard::Selection::from_name(as_str(value, "osc52_selection")?)?;
+            }
+            if let Some(value) = clipboard.get("osc52_limit") {
+                config.clipboard.osc52_limit = as_count(value, "osc52_limit")? as usize;
+            }
+            if let Some(folders) = clipboard.get("folders") {
+                for (folder, value) in as_table(folders, "clipboard.folders")? {
+                    let field = Field::from_name(as_str(value, folder)?);
+                    config
+                        .folder_fields
+                        .push((folder.trim_matches('/').to_string(), field));
+                }
+            }
+        }
+
+        if let Some(table_config) = table.get("table") {
+            let table_config = as_table(table_config, "table")?;
+            if let Some(value) = table_config.get("index_strip") {
+                config.index_strip = as_bool(value, "index_strip")?;
+            }
+            if let Some(value) = table_config.get("login_column") {
+                config.login_column = as_bool(value, "login_column")?;
+            }
+            if let Some(value) = table_config.get("mark_changed") {
+                config.mark_changed = as_bool(value, "mark_changed")?;
+            }
+            if let Some(value) = table_config.get("age_column") {
+                config.age_column = as_bool(value, "age_column")?;
+            }
+            if let Some(value) = table_config.get("max_age_months") {
+                let months = as_count(value, "max_age_months")?;
+                // Months of an average length
+                config.max_age = (months > 0)
+                    .then(|| Duration::from_secs(months.saturating_mul(365 * 24 * 60 * 60 / 12)));
+            }
+            if let Some(value) = table_config.get("locale") {
+                let locale = as_str(value, "locale")?;
+                config.date_locale = Some(
+                    locale
+                        .parse()
+                        .map_err(|_| anyhow!("invalid locale `{locale}`"))?,
+                );
+            }
+            if let Some(value) = table_config.get("row_format") {
+                let template = as_str(value, "row_format")?;
+                config.row_format = Some(
+                    RowFormat::parse(template).map_err(|e| anyhow!("invalid `row_format`: {e}"))?,
+                );
+            }
+        }
+
+        if let Some(details) = table.get("details") {
+            let details = as_table(details, "details")?;
+            if let Some(value) = details.get("height") {
+                let height = as_count(value, "height")?;
+                if height < PasswordDetails::MIN_HEIGHT as u64 {
+                    return Err(anyhow!(
+                        "`height` of the details should be at least {}",
+                        PasswordDetails::MIN_HEIGHT
+                    ));
+                }
+                config.details_height = height.min(u16::MAX as u64) as u16;
+            }
+            if let Some(value) = details.get("width") {
+                let width = as_count(value, "width")?;
+                if width < PasswordDetails::MIN_WIDTH as u64 {
+                    return Err(anyhow!(
+                        "`width` of the details should be at least {}",
+                        PasswordDetails::MIN_WIDTH
+                    ));
+                }
+                config.details_width = width.min(u16::MAX as u64) as u16;
+            }
+            if let Some(value) = details.get("position") {
+                config.details_position = DetailsPosition::from_name(as_str(value, "position")?)?;
+            }
+            if let Some(value) = details.get("collapsed") {
+                config.details_collapsed = as_bool(value, "collapsed")?;
+            }
+            if let Some(value) = details.get("follow_selection") {
+                config.follow_selection = as_bool(value, "follow_selection")?;
+            }
+            if let Some(value) = details.get("follow_delay_ms") {
+                config.follow_delay = Duration::from_millis(as_count(value, "follow_delay_ms")?);
+            }
+        }
+
+        if let Some(pass) = table.get("pass") {
+            let pass = as_table(pass, "pass")?;
+            if let Some(value) = pass.get("program") {
+                config.pass.program = expand_home(as_str(value, "program")?);
+            }
+            if let Some(value) = pass.get("extensions") {
+                let enabled = as_bool(value, "extensions")?;
+                config.pass.envs.push((
+                    "PASSWORD_STORE_ENABLE_EXTENSIONS".to_string(),
+                    enabled.to_string(),
+                ));
+            }
+            if let Some(value) = pass.get("extensions_dir") {
+                config.pass.envs.push((
+                    "PASSWORD_STORE_EXTENSIONS_DIR".to_string(),
+                    expand_home(as_str(value, "extensions_dir")?),
+                ));
+            }
+        }
+
+        if let Some(store) = table.get("store") {
+            let store = as_table(store, "store")?;
+            if let Some(value) = store.get("exclude") {
+                let patterns = as_array(value, "exclude")?
+                    .iter()
+                    .map(|pattern| as_str(pattern, "exclude").map(String::from))
+                    .collect::<Result<_>>()?;
+                config.excludes = Excludes::new(patterns);
+            }
+            if let Some(value) = store.get("watch") {
+                config.watch_store = as_bool(value, "watch")?;
+            }
+            if let Some(value) = store.get("select_command") {
+                config.select_store = as_array(value, "select_command")?
+                    .iter()
+                    .map(|arg| as_str(arg, "select_command").map(String::from))
+                    .collect::<Result<_>>()?;
+            }
+        }
+
+        if let Some(git) = table.get("git") {
+            let git = as_table(git, "git")?;
+            if let Some(value) = git.get("pull_on_start") {
+                config.pull_on_start = as_bool(value, "pull_on_start")?;
+            }
+            if let Some(value) = git.get("push_on_exit") {
+                config.push_on_exit = as_bool(value, "push_on_exit")?;
+            }
+        }
+
+        if let Some(search) = table.get("search") {
+            let search = as_table(search, "search")?;
+            if let Some(value) = search.get("fuzzy") {
+                config.fuzzy_search = as_bool(value, "fuzzy")?;
+            }
+            if let Some(value) = search.get("type_to_search") {
+                config.type_to_search = as_bool(value, "type_to_search")?;
+            }
+        }
+
+        if let Some(status_bar) = table.get("status_bar") {
+            let status_bar = as_table(status_bar, "status_bar")?;
+            if let Some(value) = status_bar.get("segments") {
+                config.status_segments = as_array(value, "segments")?
+                    .iter()
+                    .map(|name| Segment::from_name(as_str(name, "segments")?))
+                    .collect::<Result<_>>()?;
+            }
+        }
+
+        if let Some(templates) = table.get("templates") {
+            let templates = as_table(templates, "templates")?;
+            if let Some(folders) = templates.get("folders") {
+                for (folder, value) in as_table(folders, "templates.folders")? {
+                    let name = as_str(value, folder)?;
+                    let keys = templates
+                        .get(name)
+                        .filter(|_| name != "folders")
+                        .ok_or_else(|| anyhow!("template `{name}` for `{folder}` not found"))?;
+                    let keys = as_array(keys, name)?
+                        .iter()
+                        .map(|key| as_str(key, name).map(String::from))
+                        .collect::<Result<_>>()?;
+                    let template = Template {
+                        name: name.to_string(),
+                        keys,
+                    };
+                    config
+                        .folder_templates
+                        .push((folder.trim_matches('/').to_string(), template));
+                }
+            }
+        }
+
+        if let Some(commands) = table.get("commands") {
+            for (name, value) in as_table(commands, "commands")? {
+                let args = as_array(value, name)?
+                    .iter()
+                    .map(|arg| as_str(arg, name).map(String::from))
+                    .collect::<Result<Vec<_>>>()?;
+                // A command with the same name replaces a default one, an
+                // empty list removes it
+                config.commands.retain(|command| command.name != *name);
+                if !args.is_empty() {
+                    config.commands.push(EntryCommand {
+                        name: name.to_string(),
+                        args,
+                    });
+                }
+            }
+        }
+
+        if let Some(archive) = table.get("archive") {
+            let archive = as_table(archive, "archive")?;
+            if let Some(value) = archive.get("folder") {
+                let folder = as_str(value, "folder")?.trim_matches('/');
+                if folder.is_empty() {
+                    return Err(anyhow!("`folder` should not be empty"));
+                }
+                config.archive_folder = folder.to_string();
+            }
+            if let Some(value) = archive.get("hidden") {
+                config.archive_hidden = as_bool(value, "hidden")?;
+            }
+        }
+
+        if let Some(audit) = table.get("audit") {
+            let audit = as_table(audit, "audit")?;
+            if let Some(value) = audit.get("days") {
+                config.audit_period = Duration::from_secs(as_count(value, "days")? * 24 * 60 * 60);
+            }
+        }
+
+        if let Some(history) = table.get("history") {
+            let history = as_table(history, "history")?;
+            if let Some(value) = history.get("difftool") {
+                config.difftool = as_array(value, "difftool")?
+                    .iter()
+                    .map(|arg| as_str(arg, "difftool").map(String::from))
+                    .collect::<Result<Vec<_>>>()?;
+            }
+        }
+
+        if let Some(otp) = table.get("otp") {
+            let otp = as_table(otp, "otp")?;
+            if let Some(value) = otp.get("export") {
+                config.otp_export = as_array(value, "export")?
+                    .iter()
+                    .map(|arg| as_str(arg, "export").map(String::from))
+                    .collect::<Result<Vec<_>>>()?;
+            }
+            if let Some(value) = otp.get("auto") {
+                config.otp_auto = as_otp_auto(value, "auto")?;
+            }
+        }
+
+        if let Some(keys) = table.get("keys") {
+            for (mode, bindings) in as_table(keys, "keys")? {
+                for (action, value) in as_table(bindings, mode)? {
+                    let keys = as_array(value, action)?
+                        .iter()
+                        .map(|keys| as_str(keys, action))
+                        .collect::<Result<Vec<_>>>()?;
+                    config
+                        .keymap
+                        .bind(mode, action, &keys)
+                        .context(format!("invalid key binding `keys.{mode}.{action}`"))?;
+                }
+            }
+        }
+
+        if let Some(cache) = table.get("cache") {
+            let cache = as_table(cache, "cache")?;
+            if let Some(value) = cache.get("ttl") {
+                config.cache_ttl = Duration::from_secs(as_count(value, "ttl")?);
+            }
+            if let Some(value) = cache.get("size") {
+                config.cache_size = as_count(value, "size")? as usize;
+            }
+        }
+
+        if let Some(security) = table.get("security") {
+            let security = as_table(security, "security")?;
+            if let Some(value) = security.get("harden_process") {
+                config.harden_process = as_bool(value, "harden_process")?;
+            }
+            if let Some(value) = security.get("allow_world_readable_store") {
+                config.allow_world_readable_store = as_bool(value, "allow_world_readable_store")?;
+            }
+            if let Some(value) = security.get("action_log") {
+                config.action_log = as_bool(value, "action_log")?;
+            }
+            if let Some(value) = security.get("lock_with_session") {
+                config.lock_with_session = as_bool(value, "lock_with_session")?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the field that is copied with the primary copy key for an
+    /// entry, the most specific folder setting wins.
+    pub fn copy_field(&self, pass_id: &str) -> &Field {
+        folder_setting(&self.folder_fields, pass_id).unwrap_or(&self.default_field)
+    }
+
+    /// Returns true if the entry is inside the archive folder.
+    pub fn is_archived(&self, pass_id: &str) -> bool {
+        pass_id
+            .strip_prefix(self.archive_folder.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Returns the template for a new entry, the most specific folder
+    /// setting wins.
+    pub fn template(&self, pass_id: &str) -> Option<&Template> {
+        folder_setting(&self.folder_templates, pass_id)
+    }
+}
+
+/// Returns the setting of the innermost folder containing the entry.
+fn folder_setting<'a, T>(settings: &'a [(String, T)], pass_id: &str) -> Option<&'a T> {
+    settings
+        .iter()
+        .filter(|(folder, _)| {
+            pass_id
+                .strip_prefix(folder.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+        .max_by_key(|(folder, _)| folder.len())
+        .map(|(_, setting)| setting)
+}
+
+/// Replaces a leading `~/` with the home directory.
+pub fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+fn as_array<'a>(value: &'a Value, key: &str) -> Result<&'a Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| anyhow!("`{key}` should be an array"))
+}
+
+/// Reads a provider by its name or a command as an array.
+fn as_provider(value: &Value, key: &str) -> Result<clipboard::Provider> {
+    if value.is_array() {
+        let args = as_array(value, key)?
+            .iter()
+            .map(|arg| as_str(arg, key).map(String::from))
+            .collect::<Result<Vec<_>>>()?;
+        if args.is_empty() {
+            return Err(anyhow!("`{key}` should not be an empty command"));
+        }
+        Ok(clipboard::Provider::Command(args))
+    } else {
+        clipboard::Provider::from_name(as_str(value, key)?)
+    }
+}
+
+fn as_otp_auto(value: &Value, key: &str) -> Result<OtpAuto> {
+    if value.is_array() {
+        let args = as_array(value, key)?
+            .iter()
+            .map(|arg| as_str(arg, key).map(String::from))
+            .collect::<Result<Vec<_>>>()?;
+        if args.is_empty() {
+            return Err(anyhow!("`{key}` should not be an empty command"));
+        }
+        Ok(OtpAuto::Type(args))
+    } else {
+        match as_str(value, key)? {
+            "off" => Ok(OtpAuto::Off),
+            "copy" => Ok(OtpAuto::Copy),
+            other => Err(anyhow!(
+                "`{key}` should be \"off\", \"copy\" or a command, not `{other}`"
+            )),
+        }
+    }
+}
+
+fn as_table<'a>(value: &'a Value, key: &str) -> Result<&'a Table> {
+    value
+        .as_table()
+        .ok_or_else(|| anyhow!("`{key}` should be a table"))
+}
+
+fn as_bool(value: &Value, key: &str) -> Result<bool> {
+    value
+        .as_bool()
+        .ok_or_else(|| anyhow!("`{key}` should be a boolean"))
+}
+
+fn as_count(value: &Value, key: &str) -> Result<u64> {
+    value
+        .as_integer()
+        .and_then(|count| u64::try_from(count).ok())
+        .ok_or_else(|| anyhow!("`{key}` should be a non-negative integer"))
+}
+
+fn as_str<'a>(value: &'a Value, key: &str) -> Result<&'a str> {
+    value
+        .as_str()
+        .ok_or_else(|| anyhow!("`{key}` should be a string"))
+}
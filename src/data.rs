@@ -0,0 +1,113 @@
+//! Files in the local data directory of passepartui, e.g. `~/.local/share/passepartui`.
+//!
+//! Without a data directory nothing is persisted. The directory and its
+//! files can only be accessed by the user, they list entries and remember
+//! values like logins.
+
+use std::{
+    fs::{self, File, OpenOptions, TryLockError},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Returns the default location of the data directory.
+pub fn default_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("passepartui"))
+}
+
+fn existing_dir(data_dir: Option<&Path>) -> io::Result<&Path> {
+    let dir =
+        data_dir.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+    create_private_dir(dir)?;
+    Ok(dir)
+}
+
+/// Creates the directory with access for the user only, the permissions of
+/// an existing directory created with the umask are restricted.
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)?;
+    let mode = fs::metadata(dir)?.permissions().mode();
+    if mode & 0o077 != 0 {
+        fs::set_permissions(dir, fs::Permissions::from_mode(mode & 0o700))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+/// Returns the options for opening a data file, new files can only be read
+/// and written by the user.
+fn private_file() -> OpenOptions {
+    let mut options = File::options();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+    options
+}
+
+/// Reads the non-empty lines of a data file, returns an empty list if the
+/// file does not exist yet.
+pub fn read_lines(data_dir: Option<&Path>, file_name: &str) -> Vec<String> {
+    data_dir
+        .and_then(|dir| fs::read_to_string(dir.join(file_name)).ok())
+        .map(|content| {
+            content
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Writes lines to a data file, creating the data directory if necessary.
+pub fn write_lines(data_dir: Option<&Path>, file_name: &str, lines: &[String]) -> io::Result<()> {
+    let dir = existing_dir(data_dir)?;
+    let mut content = lines.join("\n");
+    content.push('\n');
+    private_file()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(dir.join(file_name))?
+        .write_all(content.as_bytes())
+}
+
+/// Appends a line to a data file, creating the file and the data directory
+/// if necessary.
+pub fn append_line(data_dir: Option<&Path>, file_name: &str, line: &str) -> io::Result<()> {
+    let dir = existing_dir(data_dir)?;
+    let mut file = private_file()
+        .create(true)
+        .append(true)
+        .open(dir.join(file_name))?;
+    writeln!(file, "{line}")
+}
+
+/// Takes an exclusive lock on a data file that is held until the returned
+/// file is dropped, returns `None` if another process holds the lock.
+pub fn try_lock(data_dir: Option<&Path>, file_name: &str) -> io::Result<Option<File>> {
+    let dir = existing_dir(data_dir)?;
+    let file = private_file()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(file_name))?;
+    match file.try_lock() {
+        Ok(()) => Ok(Some(file)),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
@@ -0,0 +1,137 @@
+//! `passepartui --dmenu` speaks the protocol of launchers like dmenu, rofi
+//! and fzf, so that `passmenu`-style scripts can use the scanner and the
+//! matcher of passepartui:
+//!
+//! ```sh
+//! passepartui --dmenu < /dev/null | rofi -dmenu | passepartui --dmenu --copy
+//! ```
+//!
+//! Without `--copy` the pass IDs matching the filter read from stdin are
+//! printed one per line, the best matches first. With `--copy` the entry
+//! whose ID is read from stdin is copied like with `passepartui copy`.
+
+use std::io::{self, BufRead, IsTerminal};
+
+use crate::{
+    backend::{Backend, System},
+    clock::Clock,
+    command,
+    config::Config,
+    headless, matcher,
+    store::StoreSnapshot,
+};
+
+/// Nothing matched the filter, like with dmenu and fzf
+const EXIT_NO_MATCH: i32 = 1;
+/// The arguments, the configuration or the store are invalid
+const EXIT_USAGE: i32 = 2;
+
+/// Runs the mode and returns the exit code, failures are printed to stderr.
+pub fn run(args: &[String]) -> i32 {
+    let mut copy = false;
+    let mut copy_args = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--copy" => copy = true,
+            "--field" => {
+                copy_args.push(arg.clone());
+                copy_args.extend(args.next().cloned());
+            }
+            _ if arg.starts_with("--field=") => copy_args.push(arg.clone()),
+            _ => {
+                eprintln!(
+                    "passepartui --dmenu: unexpected argument `{arg}`, usage: passepartui \
+                     --dmenu [--copy [--field <name>]]"
+                );
+                return EXIT_USAGE;
+            }
+        }
+    }
+    if !copy_args.is_empty() && !copy {
+        eprintln!("passepartui --dmenu: --field is only used with --copy");
+        return EXIT_USAGE;
+    }
+
+    let line = match read_line() {
+        Ok(line) => line,
+        Err(e) => {
+            eprintln!("passepartui --dmenu: failed to read stdin: {e}");
+            return EXIT_USAGE;
+        }
+    };
+    if copy {
+        // A launcher prints nothing when it was cancelled
+        if line.is_empty() {
+            return EXIT_NO_MATCH;
+        }
+        copy_args.insert(0, line);
+        return headless::run("copy", &copy_args);
+    }
+    match list(&line) {
+        Ok(pass_ids) if pass_ids.is_empty() => EXIT_NO_MATCH,
+        Ok(pass_ids) => {
+            for pass_id in pass_ids {
+                println!("{pass_id}");
+            }
+            0
+        }
+        Err(message) => {
+            eprintln!("passepartui --dmenu: {message}");
+            EXIT_USAGE
+        }
+    }
+}
+
+/// Returns the first line of stdin without the line break, nothing is read
+/// from a terminal.
+fn read_line() -> io::Result<String> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Ok(String::new());
+    }
+    let mut line = String::new();
+    stdin.lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\n', '\r']).to_string())
+}
+
+/// Scans the store and returns the pass IDs matching the filter, ranked
+/// like the search of the table. Archived entries are left out if they are
+/// hidden in the table.
+fn list(filter: &str) -> Result<Vec<String>, String> {
+    let config = Config::load().map_err(|e| format!("{e:#}"))?;
+    if !config.select_store.is_empty() {
+        command::select_store(&config.select_store)
+            .map_err(|e| format!("failed to select the password store: {e}"))?;
+    }
+    let backend = System::new(
+        config.pass.clone(),
+        config.clear_after,
+        config.clipboard.clone(),
+        Clock::default(),
+    );
+    let store_dir = backend.store_dir().map_err(|e| e.message)?;
+    if !store_dir.is_dir() {
+        return Err(format!(
+            "the password store {} does not exist",
+            store_dir.display()
+        ));
+    }
+    let scan = backend.scan(
+        &store_dir,
+        Vec::new(),
+        StoreSnapshot::default(),
+        &config.excludes,
+    );
+
+    let mut matches: Vec<(i64, String)> = scan
+        .passwords
+        .into_iter()
+        .filter(|info| !(config.archive_hidden && config.is_archived(&info.id)))
+        .filter_map(|info| {
+            matcher::find(filter, &info.id, config.fuzzy_search).map(|found| (found.score, info.id))
+        })
+        .collect();
+    matches.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then_with(|| a.cmp(b)));
+    Ok(matches.into_iter().map(|(_, pass_id)| pass_id).collect())
+}
@@ -0,0 +1,307 @@
+//! Parsing of decrypted password files beyond password and login.
+
+use zeroize::Zeroize;
+
+use crate::browser;
+
+const RECOVERY_LABELS: [&str; 4] = [
+    "recovery codes",
+    "recovery-codes",
+    "backup codes",
+    "backup-codes",
+];
+const USED_MARKER: &str = " (used)";
+
+/// A one-time recovery code found in a password file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveryCode {
+    /// Zero-based line number in the password file
+    pub line: usize,
+    pub code: String,
+    pub used: bool,
+}
+
+impl Drop for RecoveryCode {
+    fn drop(&mut self) {
+        self.code.zeroize();
+    }
+}
+
+/// Returns the recovery codes of a password file.
+///
+/// A block of recovery codes starts with a label line like `recovery codes:`
+/// followed by one code per line and ends with an empty line or another
+/// `key: value` line. Codes suffixed with ` (used)` are marked as used.
+pub fn recovery_codes(file_contents: &str) -> Vec<RecoveryCode> {
+    let mut codes = Vec::new();
+    let mut in_block = false;
+    for (line_number, line) in file_contents.lines().enumerate().skip(1) {
+        let line = line.trim();
+        if let Some(label) = line.strip_suffix(':') {
+            in_block = RECOVERY_LABELS.contains(&label.trim().to_lowercase().as_str());
+            continue;
+        }
+        if line.is_empty() || line.contains(": ") {
+            in_block = false;
+            continue;
+        }
+        if in_block {
+            let (code, used) = match line.strip_suffix(USED_MARKER) {
+                Some(code) => (code, true),
+                None => (line, false),
+            };
+            codes.push(RecoveryCode {
+                line: line_number,
+                code: code.to_string(),
+                used,
+            });
+        }
+    }
+    codes
+}
+
+/// Returns the file contents with the recovery code on the given line marked
+/// as used, or `None` if the line does not hold an unused recovery code.
+pub fn mark_recovery_code_used(file_contents: &str, line: usize) -> Option<String> {
+    recovery_codes(file_contents)
+        .iter()
+        .find(|code| code.line == line && !code.used)?;
+    let mut lines: Vec<String> = file_contents.lines().map(String::from).collect();
+    lines[line].push_str(USED_MARKER);
+    let mut marked = lines.join("\n");
+    if file_contents.ends_with('\n') {
+        marked.push('\n');
+    }
+    Some(marked)
+}
+
+/// An `otpauth://` URI found in a password file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtpUri {
+    pub uri: String,
+    pub issuer: Option<String>,
+    pub account: Option<String>,
+}
+
+impl OtpUri {
+    /// Parses issuer and account from the label and the query parameters
+    /// of the URI, e.g. `otpauth://totp/Issuer:account?secret=...&issuer=Issuer`.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.trim().strip_prefix("otpauth://")?;
+        let (_otp_type, rest) = rest.split_once('/')?;
+        let (label, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let label = percent_decode(label);
+        let (label_issuer, account) = match label.split_once(':') {
+            Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim()),
+            None => (None, label.trim()),
+        };
+        let query_issuer = query.split('&').find_map(|parameter| {
+            let (key, value) = parameter.split_once('=')?;
+            (key == "issuer").then(|| percent_decode(value))
+        });
+        let non_empty = |value: String| (!value.is_empty()).then_some(value);
+        Some(Self {
+            uri: uri.trim().to_string(),
+            issuer: query_issuer.or(label_issuer).and_then(non_empty),
+            account: non_empty(account.to_string()),
+        })
+    }
+
+    /// Returns a label like `Issuer (account)` for display.
+    pub fn label(&self) -> Option<String> {
+        match (&self.issuer, &self.account) {
+            (Some(issuer), Some(account)) => Some(format!("{issuer} ({account})")),
+            (Some(label), None) | (None, Some(label)) => Some(label.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Returns all `otpauth://` URIs of a password file.
+pub fn otp_uris(file_contents: &str) -> Vec<OtpUri> {
+    file_contents
+        .lines()
+        .filter(|line| line.trim().starts_with("otpauth://"))
+        .filter_map(OtpUri::parse)
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 2;
+            }
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A field of a password file that can be copied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Field {
+    #[default]
+    Password,
+    Login,
+    Otp,
+    /// Value of a `key: value` line
+    Key(String),
+    /// Line with the number counted from 1, like `pass show --clip=<n>`
+    Line(usize),
+}
+
+impl Field {
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "password" => Self::Password,
+            "login" => Self::Login,
+            "otp" => Self::Otp,
+            number => match number.parse() {
+                Ok(number) if number > 0 => Self::Line(number),
+                _ => Self::Key(number.to_string()),
+            },
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Password => "Password".to_string(),
+            Self::Login => "Login".to_string(),
+            Self::Otp => "One-time password".to_string(),
+            Self::Key(key) => key.clone(),
+            Self::Line(number) => format!("Line {number}"),
+        }
+    }
+}
+
+/// Returns the labels of the fields a password file holds, in the order
+/// they are shown with the secrets.
+pub fn available_fields(file_contents: &str) -> Vec<&'static str> {
+    let mut lines = file_contents.lines();
+    let mut fields = Vec::new();
+    if lines.next().is_some_and(|line| !line.trim().is_empty()) {
+        fields.push("password");
+    }
+    if lines.next().is_some_and(|line| !line.trim().is_empty()) {
+        fields.push("login");
+    }
+    if !otp_uris(file_contents).is_empty() {
+        fields.push("OTP");
+    }
+    let url = browser::find_url(file_contents);
+    if url.is_some() {
+        fields.push("URL");
+    }
+    // Any other line counts as notes
+    let has_notes = lines.any(|line| {
+        let line = line.trim();
+        !line.is_empty()
+            && !line.starts_with("otpauth://")
+            && !line
+                .split_once(':')
+                .is_some_and(|(key, _)| key.trim().eq_ignore_ascii_case("url"))
+            && url.as_deref() != Some(line)
+    });
+    if has_notes {
+        fields.push("notes");
+    }
+    fields
+}
+
+/// Returns the value of the first `key: value` line with the given key,
+/// the key is matched case-insensitively.
+pub fn field_value(file_contents: &str, key: &str) -> Option<String> {
+    file_contents.lines().skip(1).find_map(|line| {
+        let (line_key, value) = line.split_once(':')?;
+        line_key
+            .trim()
+            .eq_ignore_ascii_case(key)
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// Longest key of a `key: value` line shown as a field, longer ones are
+/// more likely a sentence with a colon
+const MAX_KEY_LENGTH: usize = 24;
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.chars().count() <= MAX_KEY_LENGTH
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.'))
+}
+
+/// Returns the `key: value` lines after the password and the login, e.g.
+/// `url: ...` or `email: ...`, in the order of the file. Lines with URIs
+/// like `otpauth://...` are left out.
+pub fn key_values(file_contents: &str) -> Vec<(String, String)> {
+    file_contents
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let (key, value) = (key.trim(), value.trim());
+            (is_key(key) && !value.is_empty() && !value.starts_with("//"))
+                .then(|| (key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Returns the login on the second line of a password file.
+pub fn login(file_contents: &str) -> Option<&str> {
+    file_contents.lines().nth(1).filter(|line| is_login(line))
+}
+
+/// Returns true if the line can be a login. URIs like `otpauth://...` and
+/// `key: value` lines are not, they may hold secrets.
+pub fn is_login(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty()
+        && !line
+            .split_once(':')
+            .is_some_and(|(key, _)| is_key(key.trim()))
+}
+
+/// Returns the `WIFI:` payload of a QR code for joining a Wi-Fi network.
+///
+/// The password file holds the pre-shared key on the first line and the
+/// network name in a `ssid: ...` line, an optional `security: ...` line sets
+/// the authentication type (`WPA`, `WEP` or `nopass`).
+pub fn wifi_qr_payload(file_contents: &str) -> Option<String> {
+    let ssid = field_value(file_contents, "ssid")?;
+    let psk = file_contents.lines().next().unwrap_or_default();
+    let security = field_value(file_contents, "security")
+        .filter(|security| !security.is_empty())
+        .unwrap_or_else(|| "WPA".to_string());
+    let hidden = field_value(file_contents, "hidden").is_some_and(|hidden| hidden == "true");
+
+    let escape = |value: &str| {
+        value
+            .chars()
+            .flat_map(|c| match c {
+                '\\' | ';' | ',' | ':' | '"' => vec!['\\', c],
+                c => vec![c],
+            })
+            .collect::<String>()
+    };
+    let mut payload = format!("WIFI:T:{};S:{};", escape(&security), escape(&ssid));
+    if !security.eq_ignore_ascii_case("nopass") {
+        payload.push_str(&format!("P:{};", escape(psk)));
+    }
+    if hidden {
+        payload.push_str("H:true;");
+    }
+    payload.push(';');
+    Some(payload)
+}
@@ -1,12 +1,129 @@
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    components::Conflict, diff::DiffLine, git::Revision, secret::Secret, strength::Weakness,
+};
+
+/// What a scan of entries found out about a decrypted password file, only
+/// this is kept and not the contents.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Finding {
+    /// Whether a line contains the pattern of the content search
+    Matched(bool),
+    /// Whether the file contains an `otpauth://` URI
+    HasOtp(bool),
+    /// Hash of the password and login, `None` without a password
+    Credential(Option<u64>),
+    /// Rating of the password, `None` if it is strong or missing
+    Weakness(Option<Weakness>),
+}
+
 #[derive(Debug)]
 pub enum PasswordEvent {
     Status(Result<Option<String>, passepartout::Error>),
     PasswordFile {
         pass_id: String,
-        file_contents: String,
+        /// Lock generation the decryption was started in
+        generation: u64,
+        /// Moved into locked memory by the thread that decrypted it
+        file_contents: Arc<Secret>,
+        modified: Option<SystemTime>,
+        /// Entry the alias stands in for
+        target: Option<String>,
+    },
+    /// Modification time of a password file
+    FileModified {
+        pass_id: String,
+        modified: Option<SystemTime>,
+    },
+    /// A new password file was inserted into the store
+    EntryCreated {
+        pass_id: String,
+    },
+    /// A password file was renamed or moved
+    EntryMoved {
+        from: String,
+        to: String,
+    },
+    /// Stale entries were moved to the archive folder or deleted
+    EntriesCleaned {
+        moved: Vec<(String, String)>,
+        removed: Vec<String>,
+    },
+    /// Duplicate entries were replaced with aliases of the one they were
+    /// merged into
+    EntriesAliased {
+        target: String,
+        pass_ids: Vec<String>,
+    },
+    /// Changes were pulled from the git remote of the store
+    StorePulled {
+        message: String,
+    },
+    /// Files that are left to merge after pulling, the merge was completed
+    /// if there are none
+    Conflicts {
+        conflicts: Vec<Conflict>,
+    },
+    /// Summary of the git status of the store, `None` if it is not a
+    /// repository
+    GitStatus {
+        summary: Option<String>,
+    },
+    /// A background scan of the store finished
+    StoreScanned,
+    /// Entries were added, removed or renamed outside of passepartui
+    StoreChanged,
+    /// The desktop session was locked
+    SessionLocked,
+    /// Unpushed commits were pushed before quitting or there were none
+    QuitReady,
+    /// The selection rested after moving in the secrets view
+    SelectionRested,
+    /// Decrypting a password file failed
+    FetchFailed {
+        pass_id: String,
+        error: passepartout::Error,
     },
     OneTimePassword {
         pass_id: String,
+        /// Lock generation the decryption was started in
+        generation: u64,
         otp: String,
+        /// End of the time step the password is valid for
+        valid_until: SystemTime,
+        period: Duration,
+    },
+    /// A password file was decrypted by a scan of entries
+    EntryScanned {
+        scan_id: u64,
+        pass_id: String,
+        finding: Finding,
+    },
+    /// A scan of entries stopped before all files were decrypted
+    EntryScanFailed {
+        scan_id: u64,
+        error: passepartout::Error,
+    },
+    LastChange {
+        pass_id: String,
+        last_change: Option<String>,
+    },
+    /// Commits that changed a password file, the latest first
+    History {
+        pass_id: String,
+        revisions: Vec<Revision>,
+    },
+    /// Two versions of a password file were decrypted and compared
+    Diff {
+        pass_id: String,
+        /// Lock generation the decryption was started in
+        generation: u64,
+        old: String,
+        new: String,
+        lines: Vec<DiffLine>,
     },
 }
@@ -0,0 +1,64 @@
+use rand::{rngs::OsRng, seq::SliceRandom};
+
+const ALPHANUMERIC: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+const SYMBOLS: &str = "!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+/// Characters that are easily confused with each other
+const AMBIGUOUS: &str = "0OoIl1|`'\"";
+
+pub const DEFAULT_LENGTH: usize = 25;
+pub const MIN_LENGTH: usize = 4;
+pub const MAX_LENGTH: usize = 128;
+
+/// Options for generating a password, the default matches `pass generate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    pub length: usize,
+    pub symbols: bool,
+    pub no_ambiguous: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            length: DEFAULT_LENGTH,
+            symbols: true,
+            no_ambiguous: false,
+        }
+    }
+}
+
+impl Options {
+    /// Returns the characters a password is generated from.
+    pub fn characters(&self) -> Vec<char> {
+        let symbols = if self.symbols { SYMBOLS } else { "" };
+        ALPHANUMERIC
+            .chars()
+            .chain(symbols.chars())
+            .filter(|c| !self.no_ambiguous || !AMBIGUOUS.contains(*c))
+            .collect()
+    }
+
+    /// Returns the characters as set for `tr`, which `pass generate` reads
+    /// from `PASSWORD_STORE_CHARACTER_SET`. Symbols are written as octal
+    /// escapes so that they are not taken for ranges or classes.
+    pub fn tr_set(&self) -> String {
+        self.characters()
+            .into_iter()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_string()
+                } else {
+                    format!("\\{:03o}", c as u32)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Generates a password from the operating system's random number generator.
+pub fn generate(options: &Options) -> String {
+    let characters = options.characters();
+    (0..options.length)
+        .filter_map(|_| characters.choose(&mut OsRng))
+        .collect()
+}
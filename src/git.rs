@@ -0,0 +1,124 @@
+use std::{
+    io,
+    path::Path,
+    process::{Command, Output},
+};
+
+/// Runs git inside the password store directory.
+fn git(store_dir: &Path, args: &[&str]) -> io::Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(store_dir)
+        .args(args)
+        .output()
+}
+
+/// Returns a summary of the branch of the store like `main ↑1 ↓2 ±3` with
+/// the commits ahead of and behind the upstream branch and the number of
+/// uncommitted changes, or `None` if the store is not a git repository.
+pub fn status(store_dir: &Path) -> io::Result<Option<String>> {
+    let output = git(store_dir, &["status", "--porcelain", "--branch"])?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let output = String::from_utf8_lossy(&output.stdout);
+    let mut lines = output.lines();
+    let Some(branch_line) = lines.next().and_then(|line| line.strip_prefix("## ")) else {
+        return Ok(None);
+    };
+    // e.g. `main...origin/main [ahead 1, behind 2]`
+    let (branch, tracking) = branch_line.split_once(' ').unwrap_or((branch_line, ""));
+    let branch = branch.split_once("...").map_or(branch, |(local, _)| local);
+    let mut summary = branch.to_string();
+    for part in tracking.trim_matches(['[', ']']).split(", ") {
+        if let Some(count) = part.strip_prefix("ahead ") {
+            summary.push_str(&format!(" ↑{count}"));
+        } else if let Some(count) = part.strip_prefix("behind ") {
+            summary.push_str(&format!(" ↓{count}"));
+        }
+    }
+    let changes = lines.filter(|line| !line.is_empty()).count();
+    if changes > 0 {
+        summary.push_str(&format!(" ±{changes}"));
+    }
+    Ok(Some(summary))
+}
+
+/// Returns author and date of the last commit that touched a password file,
+/// or `None` if the store is not a git repository or the file is untracked.
+pub fn last_change(store_dir: &Path, file_path: &Path) -> io::Result<Option<String>> {
+    let output = git(
+        store_dir,
+        &[
+            "log",
+            "-1",
+            "--format=%an <%ae>, %ad",
+            "--date=format:%Y-%m-%d %H:%M",
+            "--",
+            &file_path.to_string_lossy(),
+        ],
+    )?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!line.is_empty()).then_some(line))
+}
+
+/// A commit that changed a password file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    /// Abbreviated commit hash
+    pub hash: String,
+    pub date: String,
+    pub author: String,
+    pub subject: String,
+}
+
+/// Returns the commits that changed a password file, the latest first, or
+/// an empty list if the store is not a git repository.
+pub fn history(store_dir: &Path, file_path: &Path) -> io::Result<Vec<Revision>> {
+    let output = git(
+        store_dir,
+        &[
+            "log",
+            "--format=%h%x1f%ad%x1f%an%x1f%s",
+            "--date=format:%Y-%m-%d %H:%M",
+            "--",
+            &file_path.to_string_lossy(),
+        ],
+    )?;
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+    let revisions = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            Some(Revision {
+                hash: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                subject: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+    Ok(revisions)
+}
+
+/// Returns the contents of a file at a commit without touching the working
+/// tree, e.g. an encrypted password file.
+pub fn show(file_path: &Path, revision: &str) -> io::Result<Vec<u8>> {
+    let (Some(dir), Some(name)) = (file_path.parent(), file_path.file_name()) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "not a file"));
+    };
+    // A path starting with "./" is relative to the directory git runs in
+    let object = format!("{revision}:./{}", name.to_string_lossy());
+    let output = git(dir, &["show", &object])?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(io::Error::other(message))
+    }
+}
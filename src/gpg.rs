@@ -0,0 +1,127 @@
+//! Decryption of password files that only exist in memory, e.g. older
+//! versions from the git history of the store, and control of the agent.
+//!
+//! The ciphertext is passed to `gpg` on stdin and the plaintext read from its
+//! stdout, neither is written to disk.
+//!
+//! How long the agent caches passphrases is read from its configuration, a
+//! cache time of zero makes every action ask for the passphrase again.
+
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+};
+
+/// Decrypts the ciphertext with `gpg`, the passphrase is asked for by the
+/// agent like for `pass show`.
+pub fn decrypt(cipher: &[u8]) -> Result<String, passepartout::Error> {
+    let mut child = Command::new("gpg")
+        .args(["--quiet", "--decrypt"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let cipher = cipher.to_vec();
+    // Writing on another thread keeps a full stdout pipe from blocking gpg
+    let writer = thread::spawn(move || stdin.write_all(&cipher));
+    let output = child.wait_with_output()?;
+    let _ = writer.join();
+    if output.status.success() {
+        String::from_utf8(output.stdout)
+            .map_err(|_| passepartout::Error::Pass("the file is not valid UTF-8".to_string()))
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(passepartout::Error::Pass(message))
+    }
+}
+
+/// Makes `gpg-agent` forget the cached passphrases, the next decryption asks
+/// for the passphrase again.
+pub fn forget_passphrases() -> Result<(), passepartout::Error> {
+    let output = Command::new("gpgconf")
+        .args(["--reload", "gpg-agent"])
+        .stdin(Stdio::null())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(passepartout::Error::Pass(message))
+    }
+}
+
+/// How long `gpg-agent` caches passphrases, read from `gpg-agent.conf`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Seconds a passphrase stays cached after it was last used
+    pub default_ttl: u64,
+    /// Seconds a passphrase stays cached at most after it was entered
+    pub max_ttl: u64,
+}
+
+impl Default for CachePolicy {
+    /// The defaults of `gpg-agent`
+    fn default() -> Self {
+        Self {
+            default_ttl: 600,
+            max_ttl: 7200,
+        }
+    }
+}
+
+impl CachePolicy {
+    /// Returns true if passphrases are never cached, the agent then asks for
+    /// the passphrase with pinentry for every decryption.
+    pub fn never_caches(&self) -> bool {
+        self.default_ttl == 0 || self.max_ttl == 0
+    }
+
+    /// Returns a warning if passphrases are never cached, `None` otherwise.
+    pub fn warning(&self) -> Option<String> {
+        self.never_caches()
+            .then(|| "gpg-agent caches no passphrases, every decryption asks again".to_string())
+    }
+}
+
+/// Reads the cache settings from `gpg-agent.conf` in `$GNUPGHOME` or
+/// `~/.gnupg`, options that are not set keep the defaults of the agent.
+pub fn cache_policy() -> io::Result<CachePolicy> {
+    let mut policy = CachePolicy::default();
+    let Some(path) = agent_config_path() else {
+        return Ok(policy);
+    };
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(policy),
+        Err(e) => return Err(e),
+    };
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(name), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Ok(value) = value.parse() else {
+            continue;
+        };
+        // Later lines override earlier ones like for the agent
+        match name {
+            "default-cache-ttl" => policy.default_ttl = value,
+            "max-cache-ttl" => policy.max_ttl = value,
+            _ => {}
+        }
+    }
+    Ok(policy)
+}
+
+/// Returns the path of `gpg-agent.conf`, `None` without a home directory.
+pub fn agent_config_path() -> Option<PathBuf> {
+    let home = match env::var_os("GNUPGHOME") {
+        Some(home) if !home.is_empty() => PathBuf::from(home),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".gnupg"),
+    };
+    Some(home.join("gpg-agent.conf"))
+}
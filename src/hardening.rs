@@ -0,0 +1,65 @@
+//! Settings of the process that keep decrypted secrets from ending up in
+//! core dumps or being read by other users.
+
+use std::{io, path::Path};
+
+use crate::app::StartupError;
+
+/// Disables core dumps and, on Linux, marks the process as not dumpable,
+/// which also keeps other processes of the user from attaching to it with
+/// `ptrace` or reading its memory through `/proc`.
+///
+/// Programs started by passepartui like `pass` and `gpg` inherit the limit
+/// for core dumps but not the flag.
+#[cfg(unix)]
+pub fn harden_process() -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: the limit is a valid `rlimit` for the duration of the call
+    if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    #[cfg(target_os = "linux")]
+    // SAFETY: `PR_SET_DUMPABLE` only reads its integer argument
+    if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0, 0, 0, 0) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn harden_process() -> io::Result<()> {
+    Ok(())
+}
+
+/// Refuses a store directory that other users can list, the names of the
+/// entries tell which accounts exist. A missing directory is left to the
+/// dashboard to report.
+#[cfg(unix)]
+pub fn check_store_dir(store_dir: &Path) -> Result<(), StartupError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(metadata) = store_dir.metadata() else {
+        return Ok(());
+    };
+    if metadata.permissions().mode() & 0o004 == 0 {
+        return Ok(());
+    }
+    Err(StartupError::new(
+        format!(
+            "The password store {} can be read by other users",
+            store_dir.display()
+        ),
+        format!(
+            "Run `chmod o-rwx {}` or set `allow_world_readable_store = true` under [security] in the configuration.",
+            store_dir.display()
+        ),
+    ))
+}
+
+#[cfg(not(unix))]
+pub fn check_store_dir(_store_dir: &Path) -> Result<(), StartupError> {
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! `passepartui show <pass-id>` and `passepartui copy <pass-id>` decrypt a
+//! single entry without starting the TUI, e.g. for scripts.
+
+use totp_rs::TOTP;
+
+use crate::{
+    alias,
+    backend::{Backend, System},
+    clipboard::{self, Content},
+    clock::Clock,
+    command,
+    config::Config,
+    entry::{self, Field},
+    hardening,
+};
+
+/// Decrypting, reading the field or copying failed
+const EXIT_FAILURE: i32 = 1;
+/// The arguments or the configuration are invalid
+const EXIT_USAGE: i32 = 2;
+/// The store has no entry with the ID
+const EXIT_NOT_FOUND: i32 = 3;
+
+/// A failure with the exit code it is reported with.
+type Failure = (i32, String);
+
+/// Runs the subcommand and returns the exit code, failures are printed to
+/// stderr.
+pub fn run(command: &str, args: &[String]) -> i32 {
+    match execute(command, args) {
+        Ok(()) => 0,
+        Err((code, message)) => {
+            eprintln!("passepartui {command}: {message}");
+            code
+        }
+    }
+}
+
+fn execute(command: &str, args: &[String]) -> Result<(), Failure> {
+    let (pass_id, field) = parse_args(args).map_err(|message| (EXIT_USAGE, message))?;
+    let config = Config::load().map_err(|e| (EXIT_USAGE, format!("{e:#}")))?;
+    if config.harden_process {
+        hardening::harden_process()
+            .map_err(|e| (EXIT_FAILURE, format!("failed to disable core dumps: {e}")))?;
+    }
+    if !config.select_store.is_empty() {
+        command::select_store(&config.select_store).map_err(|e| {
+            (
+                EXIT_FAILURE,
+                format!("failed to select the password store: {e}"),
+            )
+        })?;
+    }
+    let clock = Clock::from_env().map_err(|message| (EXIT_USAGE, message))?;
+    let backend = System::new(
+        config.pass.clone(),
+        config.clear_after,
+        config.clipboard.clone(),
+        clock,
+    );
+    let store_dir = backend.store_dir().map_err(|e| (EXIT_FAILURE, e.message))?;
+    let file_path = store_dir.join(format!("{pass_id}.gpg"));
+    if pass_id.split('/').any(|part| part == "..") || !file_path.is_file() {
+        return Err((
+            EXIT_NOT_FOUND,
+            format!("{pass_id} is not in the password store"),
+        ));
+    }
+    let file_contents =
+        alias::decrypt(&backend, &file_path).map_err(|e| (EXIT_FAILURE, e.to_string()))?;
+
+    if command == "show" {
+        match field {
+            Some(field) => println!("{}", field_value(&file_contents, &field)?),
+            None => print!("{file_contents}"),
+        }
+        return Ok(());
+    }
+
+    let field = field.unwrap_or_else(|| config.copy_field(&pass_id).clone());
+    let value = field_value(&file_contents, &field)?;
+    let content = match field {
+        Field::Password => Content::Password,
+        Field::Login => Content::Login,
+        Field::Otp => Content::Otp,
+        Field::Key(_) => Content::Field,
+        Field::Line(_) => Content::Line,
+    };
+    backend
+        .copy(&value, content)
+        .map_err(|e| (EXIT_FAILURE, e.to_string()))?;
+    // Exiting would take the secret off the clipboard of X11 and leave it
+    // on the others, so the process waits for clearing it
+    let label = field.label().to_lowercase();
+    match backend.clipboard_expiry() {
+        Some(expiry) => {
+            eprintln!(
+                "Copied {label} of {pass_id} to the clipboard, clearing it in {} seconds",
+                expiry.remaining().unwrap_or_default().as_secs() + 1
+            );
+            clipboard::wait_for_clear();
+        }
+        None => eprintln!("Copied {label} of {pass_id} to the clipboard"),
+    }
+    match backend.take_clear_failure() {
+        Some(failure) => Err((EXIT_FAILURE, failure)),
+        None => Ok(()),
+    }
+}
+
+/// Reads the ID of the entry and the field given with `--field <name>`.
+fn parse_args(args: &[String]) -> Result<(String, Option<Field>), String> {
+    let mut pass_id = None;
+    let mut field = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--field" {
+            let name = args.next().ok_or("--field needs the name of a field")?;
+            field = Some(Field::from_name(name));
+        } else if let Some(name) = arg.strip_prefix("--field=") {
+            field = Some(Field::from_name(name));
+        } else if arg.starts_with("--") || pass_id.is_some() {
+            return Err(format!("unexpected argument `{arg}`"));
+        } else {
+            pass_id = Some(arg.trim_end_matches(".gpg").to_string());
+        }
+    }
+    let pass_id = pass_id
+        .filter(|pass_id| !pass_id.is_empty())
+        .ok_or("usage: passepartui show|copy <pass-id> [--field <name>]")?;
+    Ok((pass_id, field))
+}
+
+/// Returns a field of the password file, a one-time password is generated
+/// for the current time.
+fn field_value(file_contents: &str, field: &Field) -> Result<String, Failure> {
+    let value = match field {
+        Field::Password => file_contents.lines().next().map(String::from),
+        Field::Login => file_contents.lines().nth(1).map(String::from),
+        Field::Otp => match entry::otp_uris(file_contents).into_iter().next() {
+            Some(otp_uri) => Some(
+                TOTP::from_url(&otp_uri.uri)
+                    .map_err(|e| e.to_string())
+                    .and_then(|totp| totp.generate_current().map_err(|e| e.to_string()))
+                    .map_err(|e| (EXIT_FAILURE, format!("failed to generate OTP: {e}")))?,
+            ),
+            None => None,
+        },
+        Field::Key(key) => entry::field_value(file_contents, key),
+        Field::Line(number) => file_contents.lines().nth(number - 1).map(String::from),
+    };
+    value.filter(|value| !value.is_empty()).ok_or_else(|| {
+        let label = field.label().to_lowercase();
+        (EXIT_FAILURE, format!("no {label} found"))
+    })
+}
@@ -0,0 +1,798 @@
+//! Key bindings for the states of the app, the defaults can be overridden
+//! in the `[keys]` table of the configuration file.
+//!
+//! A binding maps a sequence of keys to an action, e.g. `"j"`, `"<Down>"`,
+//! `"<C-s>"` or a chord like `"gg"`. Keys that are not bound in a state with
+//! text input are used for editing the text.
+
+use anyhow::{anyhow, Result};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::{
+    actions::{
+        Action, Audit, AuditAction, ConflictAction, DialogAction, DuplicatesAction, FormAction,
+        HistoryAction, NavigationAction, OtpAuditAction, PasswordAction, ScanAction, SearchAction,
+        StoreAction, StrengthAction, TableAction,
+    },
+    app::{MainState, OverlayState, SearchState, State},
+    components::SortColumn,
+};
+
+/// Names of actions with their default keys
+type Bindings = &'static [(&'static str, &'static [&'static str])];
+
+/// Bindings shared by the password list, the preview and the secrets view
+const BROWSE_BINDINGS: Bindings = &[
+    ("down", &["j", "<Down>"]),
+    ("up", &["k", "<Up>"]),
+    ("page_down", &["<PageDown>", "f"]),
+    ("page_up", &["<PageUp>", "b"]),
+    ("top", &["g", "<Home>"]),
+    ("bottom", &["G", "<End>"]),
+    ("copy", &["y"]),
+    ("search", &["/"]),
+    ("help", &["<F1>"]),
+    ("refresh", &["<F5>"]),
+    ("lock", &["<C-l>"]),
+    ("git_pull", &["<F6>"]),
+    ("git_push", &["<F7>"]),
+    ("git_status", &["<F8>"]),
+    ("file", &["i"]),
+    ("recovery_codes", &["R"]),
+    ("wifi_qr", &["w"]),
+    ("qr", &["<C-q>"]),
+    ("commands", &["!"]),
+    ("audit", &["S"]),
+    ("otp_audit", &["T"]),
+    ("duplicates", &["="]),
+    ("strength_audit", &["W"]),
+    ("history", &["L"]),
+    ("session_log", &["E"]),
+    ("insert", &["a"]),
+    ("generator", &["n"]),
+    ("move", &["<F2>"]),
+    ("goto", &[":"]),
+    ("lookup_url", &["U"]),
+    ("new_folder", &["N"]),
+    ("error_details", &["e"]),
+    ("pin", &["p"]),
+    ("archive_entry", &["A"]),
+    ("show_archive", &["H"]),
+    ("sort_by_pass_id", &["s"]),
+    ("sort_by_modified", &["M"]),
+    ("enter_folder", &[">"]),
+    ("leave_folder", &["<"]),
+    ("jump", &[";"]),
+    ("set_mark", &["m"]),
+    ("jump_to_mark", &["'", "`"]),
+    ("jump_back", &["<C-o>"]),
+    ("copy_otp", &["x"]),
+    ("copy_pass_id", &["c"]),
+    ("copy_login", &["v"]),
+    ("open_url", &["o"]),
+    ("open_url_and_copy_login", &["O"]),
+    ("leave", &["<Esc>"]),
+    ("quit", &["q", "Q"]),
+];
+
+/// Bindings of the preview and the secrets view in addition to the shared
+/// ones
+const DETAILS_BINDINGS: Bindings = &[
+    ("back", &["h", "<Left>"]),
+    ("secrets", &["l", "<Right>", "<Enter>"]),
+    ("fetch_otp", &["r"]),
+    ("next_otp", &["t"]),
+    ("grow_details", &["+"]),
+    ("shrink_details", &["-"]),
+    ("toggle_details", &["z"]),
+    ("switch_layout", &["|"]),
+    ("focus_next", &["<Tab>"]),
+    ("focus_previous", &["<BackTab>"]),
+];
+
+const DEFAULT_BINDINGS: &[(Mode, Bindings)] = &[
+    (Mode::Table, BROWSE_BINDINGS),
+    (Mode::Table, &[("preview", &["l", "<Right>", "<Enter>"])]),
+    (Mode::Preview, BROWSE_BINDINGS),
+    (Mode::Preview, DETAILS_BINDINGS),
+    (Mode::Secrets, BROWSE_BINDINGS),
+    (Mode::Secrets, DETAILS_BINDINGS),
+    (
+        Mode::Search,
+        &[
+            ("leave", &["<Esc>", "<Enter>"]),
+            ("down", &["<Down>"]),
+            ("up", &["<Up>"]),
+            ("page_down", &["<PageDown>"]),
+            ("page_up", &["<PageUp>"]),
+            ("help", &["<F1>"]),
+            ("widen_scope", &["<Tab>"]),
+            ("search_contents", &["<C-g>"]),
+        ],
+    ),
+    (
+        Mode::Goto,
+        &[
+            ("submit", &["<Enter>"]),
+            ("leave", &["<Esc>"]),
+            ("complete", &["<Tab>"]),
+            ("down", &["<Down>"]),
+            ("up", &["<Up>"]),
+            ("help", &["<F1>"]),
+        ],
+    ),
+    (
+        Mode::Help,
+        &[
+            ("back", &["<Esc>", "<F1>"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("page_down", &["<PageDown>", "f"]),
+            ("page_up", &["<PageUp>", "b"]),
+            ("top", &["g", "<Home>"]),
+            ("bottom", &["G", "<End>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::File,
+        &[
+            ("back", &["<Esc>", "i"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("copy_line", &["y", "<Enter>"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::RecoveryCodes,
+        &[
+            ("back", &["<Esc>", "R"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("copy_recovery_code", &["y", "<Enter>"]),
+            ("use_recovery_code", &["u"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::WifiQr,
+        &[
+            ("back", &["<Esc>", "w"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Qr,
+        &[
+            ("back", &["<Esc>", "<C-q>"]),
+            ("next_otp", &["t"]),
+            ("show_otp_uri", &["u"]),
+            ("export_otp", &["E"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Commands,
+        &[
+            ("back", &["<Esc>", "!"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("run_command", &["<Enter>"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Audit,
+        &[
+            ("back", &["<Esc>", "S"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("mark", &["<Space>"]),
+            ("mark_all", &["*"]),
+            ("archive", &["A"]),
+            ("delete", &["D"]),
+            ("fix_permissions", &["P"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::History,
+        &[
+            ("back", &["<Esc>", "L"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("mark_base", &["<Space>"]),
+            ("diff", &["<Enter>", "d"]),
+            ("difftool", &["D"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::OtpAudit,
+        &[
+            ("back", &["<Esc>", "T"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("go_to_entry", &["<Enter>"]),
+            ("decrypt_unchecked", &["d"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Duplicates,
+        &[
+            ("back", &["<Esc>", "="]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("merge", &["<Enter>"]),
+            ("decrypt_remaining", &["d"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Strength,
+        &[
+            ("back", &["<Esc>", "W"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("go_to_weak_entry", &["<Enter>"]),
+            ("decrypt_pending", &["d"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::SessionLog,
+        &[
+            ("back", &["<Esc>", "E"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("export_log", &["s"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Conflicts,
+        &[
+            ("back", &["<Esc>"]),
+            ("down", &["j", "<Down>"]),
+            ("up", &["k", "<Up>"]),
+            ("keep_local", &["l"]),
+            ("keep_remote", &["r"]),
+            ("edit", &["e"]),
+            ("help", &["<F1>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Dialog,
+        &[
+            ("back", &["<Esc>"]),
+            ("focus_next", &["<Tab>"]),
+            ("focus_previous", &["<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Generator,
+        &[
+            ("copy_generated", &["<C-y>"]),
+            ("generate", &["<C-s>"]),
+            ("back", &["<Esc>"]),
+            ("next_field", &["<Tab>", "<Down>"]),
+            ("previous_field", &["<BackTab>", "<Up>"]),
+        ],
+    ),
+    (
+        Mode::Prompt,
+        &[
+            ("submit", &["<Enter>"]),
+            ("back", &["<Esc>"]),
+            ("complete", &["<Tab>"]),
+            ("next_field", &["<Down>"]),
+            ("previous_field", &["<Up>", "<BackTab>"]),
+        ],
+    ),
+    (
+        Mode::Insert,
+        &[
+            ("save", &["<C-s>"]),
+            ("back", &["<Esc>"]),
+            ("next_field", &["<Tab>", "<Down>"]),
+            ("previous_field", &["<BackTab>", "<Up>"]),
+        ],
+    ),
+];
+
+/// States of the app with their own key bindings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Table,
+    Preview,
+    Secrets,
+    Search,
+    Goto,
+    Help,
+    File,
+    RecoveryCodes,
+    WifiQr,
+    Qr,
+    Commands,
+    Audit,
+    OtpAudit,
+    Duplicates,
+    Strength,
+    History,
+    SessionLog,
+    Conflicts,
+    Dialog,
+    Generator,
+    Prompt,
+    Insert,
+}
+
+impl Mode {
+    pub fn from_state(state: &State) -> Self {
+        match (state.main, state.search, state.overlay) {
+            (_, SearchState::Active, OverlayState::Inactive) => Self::Search,
+            (_, SearchState::Goto, OverlayState::Inactive) => Self::Goto,
+            (MainState::Table, _, OverlayState::Inactive) => Self::Table,
+            (MainState::Preview, _, OverlayState::Inactive) => Self::Preview,
+            (MainState::Secrets, _, OverlayState::Inactive) => Self::Secrets,
+            (_, _, OverlayState::Help) => Self::Help,
+            (_, _, OverlayState::File) => Self::File,
+            (_, _, OverlayState::RecoveryCodes) => Self::RecoveryCodes,
+            (_, _, OverlayState::WifiQr) => Self::WifiQr,
+            (_, _, OverlayState::Qr) => Self::Qr,
+            (_, _, OverlayState::Commands) => Self::Commands,
+            (_, _, OverlayState::Audit) => Self::Audit,
+            (_, _, OverlayState::OtpAudit) => Self::OtpAudit,
+            (_, _, OverlayState::Duplicates) => Self::Duplicates,
+            (_, _, OverlayState::Strength) => Self::Strength,
+            (_, _, OverlayState::History) => Self::History,
+            (_, _, OverlayState::SessionLog) => Self::SessionLog,
+            (_, _, OverlayState::Conflicts) => Self::Conflicts,
+            (_, _, OverlayState::Dialog) => Self::Dialog,
+            (_, _, OverlayState::Generator) => Self::Generator,
+            (_, _, OverlayState::Prompt) => Self::Prompt,
+            (_, _, OverlayState::Insert) => Self::Insert,
+        }
+    }
+
+    /// Returns true if digits typed before a movement are a count, states
+    /// with text input take them as text and the commands popup selects
+    /// with them.
+    pub fn takes_count(self) -> bool {
+        matches!(
+            self,
+            Self::Table
+                | Self::Preview
+                | Self::Secrets
+                | Self::Help
+                | Self::File
+                | Self::RecoveryCodes
+                | Self::Audit
+                | Self::OtpAudit
+                | Self::Duplicates
+                | Self::Strength
+                | Self::History
+                | Self::SessionLog
+                | Self::Conflicts
+        )
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let mode = match name {
+            "table" => Self::Table,
+            "preview" => Self::Preview,
+            "secrets" => Self::Secrets,
+            "search" => Self::Search,
+            "goto" => Self::Goto,
+            "help" => Self::Help,
+            "file" => Self::File,
+            "recovery_codes" => Self::RecoveryCodes,
+            "wifi_qr" => Self::WifiQr,
+            "qr" => Self::Qr,
+            "commands" => Self::Commands,
+            "audit" => Self::Audit,
+            "otp_audit" => Self::OtpAudit,
+            "duplicates" => Self::Duplicates,
+            "strength_audit" => Self::Strength,
+            "history" => Self::History,
+            "session_log" => Self::SessionLog,
+            "conflicts" => Self::Conflicts,
+            "dialog" => Self::Dialog,
+            "generator" => Self::Generator,
+            "prompt" => Self::Prompt,
+            "insert" => Self::Insert,
+            _ => return None,
+        };
+        Some(mode)
+    }
+}
+
+/// What a key sequence is bound to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyAction {
+    Run(Action),
+    /// Waits for a letter to jump to
+    JumpPrefix,
+    /// Waits for the letter of a mark to set on the selected entry
+    SetMarkPrefix,
+    /// Waits for the letter of a mark to jump to
+    JumpToMarkPrefix,
+}
+
+impl KeyAction {
+    fn from_name(name: &str) -> Option<Self> {
+        use NavigationAction as N;
+        use PasswordAction as P;
+
+        let action = match name {
+            "jump" => return Some(Self::JumpPrefix),
+            "set_mark" => return Some(Self::SetMarkPrefix),
+            "jump_to_mark" => return Some(Self::JumpToMarkPrefix),
+            "jump_back" => Action::Navigation(N::JumpBack),
+            "back" => Action::Navigation(N::Back),
+            "leave" => Action::Navigation(N::Leave),
+            "down" => Action::Navigation(N::Down),
+            "up" => Action::Navigation(N::Up),
+            "page_down" => Action::Navigation(N::PageDown),
+            "page_up" => Action::Navigation(N::PageUp),
+            "top" => Action::Navigation(N::Top),
+            "bottom" => Action::Navigation(N::Bottom),
+            "preview" => Action::Navigation(N::Preview),
+            "secrets" => Action::Navigation(N::Secrets),
+            "search" => Action::Navigation(N::Search),
+            "help" => Action::Navigation(N::Help),
+            "file" => Action::Navigation(N::File),
+            "recovery_codes" => Action::Navigation(N::RecoveryCodes),
+            "wifi_qr" => Action::Navigation(N::WifiQr),
+            "qr" => Action::Navigation(N::Qr),
+            "commands" => Action::Navigation(N::Commands),
+            "audit" => Action::Navigation(N::Audit),
+            "otp_audit" => Action::Navigation(N::OtpAudit),
+            "duplicates" => Action::Navigation(N::Duplicates),
+            "strength_audit" => Action::Navigation(N::Strength),
+            "history" => Action::Navigation(N::History),
+            "session_log" => Action::Navigation(N::SessionLog),
+            "export_log" => Action::Navigation(N::ExportSessionLog),
+            "generator" => Action::Navigation(N::Generator),
+            "insert" => Action::Navigation(N::Insert),
+            "move" => Action::Navigation(N::Move),
+            "goto" => Action::Navigation(N::Goto),
+            "lookup_url" => Action::Navigation(N::LookupUrl),
+            "new_folder" => Action::Navigation(N::NewFolder),
+            "error_details" => Action::Navigation(N::ErrorDetails),
+            "grow_details" => Action::Navigation(N::GrowDetails),
+            "shrink_details" => Action::Navigation(N::ShrinkDetails),
+            "toggle_details" => Action::Navigation(N::ToggleDetails),
+            "switch_layout" => Action::Navigation(N::SwitchLayout),
+            "focus_next" => Action::Navigation(N::FocusNext),
+            "focus_previous" => Action::Navigation(N::FocusPrevious),
+            "quit" => Action::Navigation(N::Quit),
+            "copy" => Action::Password(P::CopyDefault),
+            "copy_password" => Action::Password(P::CopyPassword),
+            "copy_login" => Action::Password(P::CopyLogin),
+            "copy_otp" => Action::Password(P::CopyOtp),
+            "copy_pass_id" => Action::Password(P::CopyPassId),
+            "fetch_otp" => Action::Password(P::FetchOtp),
+            "next_otp" => Action::Password(P::NextOtp),
+            "show_otp_uri" => Action::Password(P::ShowOtpUri),
+            "export_otp" => Action::Password(P::ExportOtp),
+            "open_url" => Action::Password(P::OpenUrl),
+            "open_url_and_copy_login" => Action::Password(P::OpenUrlAndCopyLogin),
+            "copy_line" => Action::Password(P::CopyFileLine),
+            "copy_recovery_code" => Action::Password(P::CopyRecoveryCode),
+            "use_recovery_code" => Action::Password(P::UseRecoveryCode),
+            "run_command" => Action::Password(P::RunCommand),
+            "copy_generated" => Action::Password(P::CopyGenerated),
+            "generate" => Action::Password(P::Generate),
+            "save" => Action::Password(P::Insert),
+            "refresh" => Action::Store(StoreAction::Refresh),
+            "git_pull" => Action::Store(StoreAction::GitPull),
+            "git_push" => Action::Store(StoreAction::GitPush),
+            "git_status" => Action::Store(StoreAction::GitStatus),
+            "pin" => Action::Table(TableAction::TogglePin),
+            "enter_folder" => Action::Table(TableAction::EnterFolder),
+            "leave_folder" => Action::Table(TableAction::LeaveFolder),
+            "widen_scope" => Action::Table(TableAction::WidenScope),
+            "search_contents" => Action::Search(SearchAction::SearchContents),
+            "show_archive" => Action::Table(TableAction::ToggleArchive),
+            "sort_by_pass_id" => Action::Table(TableAction::Sort(SortColumn::PassId)),
+            "sort_by_modified" => Action::Table(TableAction::Sort(SortColumn::Modified)),
+            "archive_entry" => Action::Password(P::Archive),
+            "lock" => Action::Password(P::Lock),
+            "mark" => Action::Audit(AuditAction::ToggleMark),
+            "mark_all" => Action::Audit(AuditAction::ToggleMarkAll),
+            "archive" => Action::Audit(AuditAction::Archive),
+            "delete" => Action::Audit(AuditAction::Delete),
+            "fix_permissions" => Action::Audit(AuditAction::FixPermissions),
+            "decrypt_unchecked" => Action::Scan(ScanAction::Decrypt(Audit::Otp)),
+            "go_to_entry" => Action::OtpAudit(OtpAuditAction::Open),
+            "decrypt_remaining" => Action::Scan(ScanAction::Decrypt(Audit::Duplicates)),
+            "merge" => Action::Duplicates(DuplicatesAction::Merge),
+            "decrypt_pending" => Action::Scan(ScanAction::Decrypt(Audit::Strength)),
+            "go_to_weak_entry" => Action::Strength(StrengthAction::Open),
+            "mark_base" => Action::History(HistoryAction::MarkBase),
+            "diff" => Action::History(HistoryAction::Diff),
+            "difftool" => Action::History(HistoryAction::Difftool),
+            "keep_local" => Action::Conflict(ConflictAction::KeepLocal),
+            "keep_remote" => Action::Conflict(ConflictAction::KeepRemote),
+            "edit" => Action::Conflict(ConflictAction::Edit),
+            "next_field" => Action::Form(FormAction::NextField),
+            "previous_field" => Action::Form(FormAction::PreviousField),
+            "complete" => Action::Form(FormAction::Complete),
+            "submit" => Action::Form(FormAction::Submit),
+            _ => return None,
+        };
+        Some(Self::Run(action))
+    }
+
+    /// Returns the status shown while a prefix waits for its letter.
+    pub fn prompt(&self) -> Option<&'static str> {
+        match self {
+            Self::Run(_) => None,
+            Self::JumpPrefix => Some("Jump to letter: _"),
+            Self::SetMarkPrefix => Some("Set mark: _"),
+            Self::JumpToMarkPrefix => Some("Jump to mark: _"),
+        }
+    }
+
+    /// Returns the action of a prefix followed by the letter.
+    pub fn with_letter(&self, letter: char) -> Option<Action> {
+        let action = match self {
+            Self::Run(_) => return None,
+            Self::JumpPrefix => NavigationAction::JumpToLetter(letter),
+            Self::SetMarkPrefix => NavigationAction::SetMark(letter),
+            Self::JumpToMarkPrefix => NavigationAction::JumpToMark(letter),
+        };
+        Some(Action::Navigation(action))
+    }
+}
+
+/// A key press, Shift is part of the character rather than a modifier.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event.modifiers - KeyModifiers::SHIFT,
+        }
+    }
+}
+
+/// Parses a key sequence like `gg`, `<C-s>` or `<Esc>`.
+fn parse_keys(keys: &str) -> Result<Vec<Key>> {
+    let mut parsed = Vec::new();
+    let mut rest = keys;
+    while let Some(c) = rest.chars().next() {
+        let special = rest
+            .strip_prefix('<')
+            .and_then(|inner| inner.split_once('>'))
+            .filter(|(name, _)| !name.is_empty());
+        if let Some((name, after)) = special {
+            parsed.push(parse_special(name).ok_or_else(|| anyhow!("unknown key `<{name}>`"))?);
+            rest = after;
+        } else {
+            parsed.push(Key {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE,
+            });
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if parsed.is_empty() {
+        return Err(anyhow!("empty key sequence"));
+    }
+    Ok(parsed)
+}
+
+/// Parses the name of a key between angle brackets, e.g. `C-s` or `PageDown`.
+fn parse_special(name: &str) -> Option<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = name;
+    loop {
+        if let Some(rest) = name.strip_prefix("C-") {
+            modifiers |= KeyModifiers::CONTROL;
+            name = rest;
+        } else if let Some(rest) = name.strip_prefix("A-") {
+            modifiers |= KeyModifiers::ALT;
+            name = rest;
+        } else {
+            break;
+        }
+    }
+    let code = match name.to_lowercase().as_str() {
+        "esc" => KeyCode::Esc,
+        "enter" | "cr" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" | "s-tab" => KeyCode::BackTab,
+        "backspace" | "bs" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        "gt" => KeyCode::Char('>'),
+        lower => match lower.strip_prefix('f').map(str::parse::<u8>) {
+            Some(Ok(number @ 1..=12)) => KeyCode::F(number),
+            _ => {
+                let mut chars = name.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            }
+        },
+    };
+    Some(Key { code, modifiers })
+}
+
+/// Result of looking up the keys pressed so far.
+#[derive(Debug, PartialEq)]
+pub enum Lookup<'a> {
+    Found(&'a KeyAction),
+    /// The keys start a longer chord
+    Pending,
+    NotFound,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Binding {
+    mode: Mode,
+    keys: Vec<Key>,
+    action: KeyAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut keymap = Self {
+            bindings: Vec::new(),
+        };
+        for (mode, bindings) in DEFAULT_BINDINGS {
+            for (action, keys) in bindings.iter() {
+                let action = KeyAction::from_name(action).expect("default action exists");
+                for keys in keys.iter() {
+                    keymap.bindings.push(Binding {
+                        mode: *mode,
+                        keys: parse_keys(keys).expect("default keys are valid"),
+                        action: action.clone(),
+                    });
+                }
+            }
+        }
+        keymap
+    }
+}
+
+impl Keymap {
+    /// Replaces the keys of an action in a state, the keys are taken away
+    /// from other actions of the state.
+    pub fn bind(&mut self, mode: &str, action: &str, keys: &[&str]) -> Result<()> {
+        let mode = Mode::from_name(mode).ok_or_else(|| anyhow!("unknown state `{mode}`"))?;
+        let action =
+            KeyAction::from_name(action).ok_or_else(|| anyhow!("unknown action `{action}`"))?;
+        let keys = keys
+            .iter()
+            .map(|keys| parse_keys(keys))
+            .collect::<Result<Vec<_>>>()?;
+        self.bindings.retain(|binding| {
+            binding.mode != mode || (binding.action != action && !keys.contains(&binding.keys))
+        });
+        for keys in keys {
+            self.bindings.push(Binding {
+                mode,
+                keys,
+                action: action.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Looks up the keys pressed so far, a sequence that starts a longer
+    /// chord waits for the next key even if it is bound itself.
+    pub fn lookup(&self, mode: Mode, keys: &[Key]) -> Lookup<'_> {
+        let bindings = self.bindings.iter().filter(|binding| binding.mode == mode);
+        if bindings
+            .clone()
+            .any(|binding| binding.keys.len() > keys.len() && binding.keys.starts_with(keys))
+        {
+            return Lookup::Pending;
+        }
+        bindings
+            .into_iter()
+            .find(|binding| binding.keys == keys)
+            .map_or(Lookup::NotFound, |binding| Lookup::Found(&binding.action))
+    }
+}
+
+/// Returns the action of a key that is not bound, used for editing text in
+/// the search field and in forms.
+pub fn unbound_key_action(mode: Mode, key_event: KeyEvent) -> Option<Action> {
+    match mode {
+        Mode::Search | Mode::Goto => match key_event.code {
+            KeyCode::Char(key) => Some(Action::Search(SearchAction::Insert(key))),
+            KeyCode::Backspace => Some(Action::Search(SearchAction::RemoveLeft)),
+            KeyCode::Delete => Some(Action::Search(SearchAction::RemoveRight)),
+            KeyCode::Left => Some(Action::Search(SearchAction::MoveLeft)),
+            KeyCode::Right => Some(Action::Search(SearchAction::MoveRight)),
+            KeyCode::Home => Some(Action::Search(SearchAction::MoveToStart)),
+            KeyCode::End => Some(Action::Search(SearchAction::MoveToEnd)),
+            _ => None,
+        },
+        Mode::Generator | Mode::Prompt | Mode::Insert => match key_event.code {
+            KeyCode::Enter => Some(Action::Form(FormAction::Enter)),
+            KeyCode::Char(key) => Some(Action::Form(FormAction::Insert(key))),
+            KeyCode::Backspace => Some(Action::Form(FormAction::RemoveLeft)),
+            KeyCode::Delete => Some(Action::Form(FormAction::RemoveRight)),
+            KeyCode::Left => Some(Action::Form(FormAction::MoveLeft)),
+            KeyCode::Right => Some(Action::Form(FormAction::MoveRight)),
+            KeyCode::Home => Some(Action::Form(FormAction::MoveToStart)),
+            KeyCode::End => Some(Action::Form(FormAction::MoveToEnd)),
+            _ => None,
+        },
+        Mode::Table | Mode::Preview => match key_event.code {
+            KeyCode::Char(key)
+                if !key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Some(Action::Search(SearchAction::Type(key)))
+            }
+            _ => None,
+        },
+        // Buttons of dialogs are pressed with the key in their label
+        Mode::Dialog => match key_event.code {
+            KeyCode::Char(key) => Some(Action::Dialog(DialogAction::Key(key))),
+            _ => None,
+        },
+        // Commands are selected by their number
+        Mode::Commands => match key_event.code {
+            KeyCode::Char(key @ '1'..='9') => Some(Action::Navigation(NavigationAction::Select(
+                key as usize - '1' as usize,
+            ))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,63 @@
+//! Notices when the desktop session is locked through systemd-logind, so
+//! that decrypted secrets don't outlive the screen lock.
+
+use std::{sync::mpsc::Sender, thread};
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::OwnedObjectPath,
+};
+
+use crate::event::PasswordEvent;
+
+const LOGIND: &str = "org.freedesktop.login1";
+const SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+
+/// Starts listening for the session of the user to be locked,
+/// `PasswordEvent::SessionLocked` is sent each time.
+///
+/// Desktops either ask logind to lock the session, which emits the `Lock`
+/// signal, or set the `LockedHint` property when their screen locker starts,
+/// both are watched.
+pub fn watch(event_tx: Sender<PasswordEvent>) -> zbus::Result<()> {
+    let connection = Connection::system()?;
+    // The session of the process, or the graphical session of the user if a
+    // terminal was started outside of it, e.g. by a systemd user service
+    let auto = Proxy::new(
+        &connection,
+        LOGIND,
+        "/org/freedesktop/login1/session/auto",
+        SESSION_INTERFACE,
+    )?;
+    let id: String = auto.get_property("Id")?;
+    let manager = Proxy::new(
+        &connection,
+        LOGIND,
+        "/org/freedesktop/login1",
+        "org.freedesktop.login1.Manager",
+    )?;
+    // Signals are sent from the path of the session, not from `auto`
+    let path: OwnedObjectPath = manager.call("GetSession", &(id,))?;
+    let session = Proxy::new_owned(connection, LOGIND, path, SESSION_INTERFACE)?;
+
+    let lock_signals = session.receive_signal("Lock")?;
+    let hint_changes = session.receive_property_changed::<bool>("LockedHint");
+    let lock_tx = event_tx.clone();
+    thread::spawn(move || {
+        for _ in lock_signals {
+            if lock_tx.send(PasswordEvent::SessionLocked).is_err() {
+                break;
+            }
+        }
+    });
+    thread::spawn(move || {
+        // Keeps the property cache of the proxy updated
+        let _session = session;
+        for change in hint_changes {
+            if change.get().unwrap_or(false) && event_tx.send(PasswordEvent::SessionLocked).is_err()
+            {
+                break;
+            }
+        }
+    });
+    Ok(())
+}
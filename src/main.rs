@@ -1,27 +1,127 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use ratatui::crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
 };
-use std::{env, io::stdout};
+use std::{env, io::stdout, process, sync::Arc};
 
+mod action_log;
 mod actions;
+mod alias;
 mod app;
+mod backend;
+mod bench;
+mod browser;
+mod cache;
+mod clipboard;
+mod clock;
+mod command;
+mod completion;
 mod components;
+mod config;
+mod data;
+mod diff;
+mod dmenu;
+mod entry;
 mod event;
+mod generator;
+mod git;
+mod gpg;
+mod hardening;
+mod headless;
+mod keymap;
+#[cfg(target_os = "linux")]
+mod logind;
+mod matcher;
+mod pass;
+mod remembered;
+mod row_format;
+mod secret;
+mod selftest;
+mod session;
+mod status;
+mod store;
+mod strength;
 mod theme;
+mod usage;
+mod watch;
 
 use app::App;
+use backend::Demo;
+use clock::Clock;
+use config::Config;
 
 fn main() -> Result<()> {
+    let args: Vec<String> = env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("selftest") => return selftest::run(),
+        Some("bench-scan") => return bench::run(&args[2..]),
+        Some(command @ ("show" | "copy")) => process::exit(headless::run(command, &args[2..])),
+        _ => (),
+    }
+    if let Some(index) = args.iter().position(|arg| arg == "--dmenu") {
+        let rest: Vec<String> = [&args[1..index], &args[index + 1..]].concat();
+        process::exit(dmenu::run(&rest));
+    }
     let tty_pinentry = env::args().any(|arg| arg == "--tty-pinentry");
+    let initial_scan = !env::args().any(|arg| arg == "--no-initial-scan");
+    let demo = env::args().any(|arg| arg == "--demo");
+    let query = option_value("--query").filter(|query| !query.is_empty());
+    let select = option_value("--select").filter(|pass_id| !pass_id.is_empty());
+    let url = option_value("--url").filter(|url| !url.is_empty());
+    let config = Config::load()?;
+    let clock = Clock::from_env().map_err(|e| anyhow!(e))?;
+    if config.harden_process {
+        hardening::harden_process().context("failed to disable core dumps")?;
+    }
+    if !demo && !config.select_store.is_empty() {
+        command::select_store(&config.select_store)
+            .map_err(|e| anyhow!("failed to select the password store: {e}"))?;
+    }
 
     let mut terminal = ratatui::init();
     execute!(stdout(), EnableMouseCapture)?;
     terminal.clear()?;
-    let result = App::new(tty_pinentry).run(&mut terminal);
+    let app = if demo {
+        Demo::new(config.clear_after, clock)
+            .and_then(|demo| App::with_backend(tty_pinentry, initial_scan, config, Arc::new(demo)))
+    } else {
+        App::new(tty_pinentry, initial_scan, config, clock)
+    };
+    let result = match app {
+        Ok(mut app) => {
+            if let Some(query) = &query {
+                app.search(query);
+            }
+            let opened = match (&select, &url) {
+                (Some(pass_id), _) => app.open_entry(pass_id),
+                (None, Some(url)) => app.lookup_url(url),
+                (None, None) => Ok(()),
+            };
+            opened.and_then(|()| app.run(&mut terminal))
+        }
+        Err(error) => App::show_startup_error(&mut terminal, error),
+    };
     execute!(stdout(), DisableMouseCapture)?;
     ratatui::restore();
     result?;
     Ok(())
 }
+
+/// Returns the value of an option given as `--name value` or
+/// `--name=value`.
+fn option_value(name: &str) -> Option<String> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
@@ -0,0 +1,194 @@
+//! Matching of the search pattern against password file IDs.
+//!
+//! The fuzzy matcher finds the characters of the pattern in order but not
+//! necessarily next to each other, so "ghper" matches "github/personal".
+//! Characters at the start of a word and runs of consecutive characters
+//! score higher, gaps between matched characters lower the score.
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CONSECUTIVE: i64 = 8;
+const PENALTY_GAP: i64 = 1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// Higher is better, always 0 for substring matches
+    pub score: i64,
+    /// Indices of the matched characters
+    pub positions: Vec<usize>,
+}
+
+/// Matches the pattern case-insensitively, either fuzzy or as a substring
+/// with all occurrences highlighted. An empty pattern matches everything.
+pub fn find(pattern: &str, text: &str, fuzzy: bool) -> Option<Match> {
+    let pattern: Vec<char> = pattern.chars().map(lowercase).collect();
+    let text: Vec<char> = text.chars().map(lowercase).collect();
+    if pattern.is_empty() {
+        Some(Match {
+            score: 0,
+            positions: Vec::new(),
+        })
+    } else if fuzzy {
+        find_fuzzy(&pattern, &text)
+    } else {
+        find_substring(&pattern, &text)
+    }
+}
+
+/// Maps a character to a single lowercase character so that the positions
+/// stay the same.
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_boundary(text: &[char], position: usize) -> bool {
+    position == 0 || matches!(text[position - 1], '/' | '-' | '_' | '.' | ' ' | '@')
+}
+
+fn find_substring(pattern: &[char], text: &[char]) -> Option<Match> {
+    let mut positions = Vec::new();
+    let mut start = 0;
+    while start + pattern.len() <= text.len() {
+        if text[start..start + pattern.len()] == *pattern {
+            positions.extend(start..start + pattern.len());
+            start += pattern.len();
+        } else {
+            start += 1;
+        }
+    }
+    (!positions.is_empty()).then_some(Match {
+        score: 0,
+        positions,
+    })
+}
+
+/// Finds the best scoring positions, `scores[i][j]` is the best score with
+/// the `i`-th pattern character at position `j` of the text.
+fn find_fuzzy(pattern: &[char], text: &[char]) -> Option<Match> {
+    if pattern.len() > text.len() {
+        return None;
+    }
+    let mut scores: Vec<Vec<Option<i64>>> = vec![vec![None; text.len()]; pattern.len()];
+    let mut previous = vec![vec![0; text.len()]; pattern.len()];
+
+    for (i, &pattern_char) in pattern.iter().enumerate() {
+        // Best match of the previous character with a gap before position
+        // `j`, the gap penalty is added back for comparing positions
+        let mut best_with_gap: Option<(i64, usize)> = None;
+        for j in 0..text.len() {
+            if i > 0 && j >= 2 {
+                if let Some(score) = scores[i - 1][j - 2] {
+                    let value = score + (j - 2) as i64 * PENALTY_GAP;
+                    if best_with_gap.is_none_or(|(best, _)| value > best) {
+                        best_with_gap = Some((value, j - 2));
+                    }
+                }
+            }
+            if text[j] != pattern_char {
+                continue;
+            }
+
+            let bonus = if is_boundary(text, j) {
+                BONUS_BOUNDARY
+            } else {
+                0
+            };
+            let preceding = if i == 0 {
+                Some((0, 0))
+            } else {
+                let consecutive = j
+                    .checked_sub(1)
+                    .and_then(|k| scores[i - 1][k].map(|score| (score + BONUS_CONSECUTIVE, k)));
+                let gap = best_with_gap.map(|(value, k)| (value - (j as i64 - 1) * PENALTY_GAP, k));
+                match (consecutive, gap) {
+                    (Some(consecutive), Some(gap)) if gap.0 > consecutive.0 => Some(gap),
+                    (Some(consecutive), _) => Some(consecutive),
+                    (None, gap) => gap,
+                }
+            };
+            if let Some((score, k)) = preceding {
+                scores[i][j] = Some(score + SCORE_MATCH + bonus);
+                previous[i][j] = k;
+            }
+        }
+    }
+
+    let last = pattern.len() - 1;
+    let (score, mut j) = scores[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (score, j)))
+        .max_by_key(|&(score, j)| (score, std::cmp::Reverse(j)))?;
+    let mut positions = vec![0; pattern.len()];
+    for i in (0..pattern.len()).rev() {
+        positions[i] = j;
+        j = previous[i][j];
+    }
+    Some(Match { score, positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(pattern: &str, text: &str, fuzzy: bool) -> Option<Vec<usize>> {
+        find(pattern, text, fuzzy).map(|m| m.positions)
+    }
+
+    #[test]
+    fn fuzzy_matches_characters_in_order() {
+        assert_eq!(
+            positions("ghper", "github/personal", true),
+            Some(vec![0, 3, 7, 8, 9])
+        );
+        assert_eq!(positions("rep", "github/personal", true), None);
+    }
+
+    #[test]
+    fn fuzzy_prefers_word_starts() {
+        assert_eq!(
+            positions("per", "supper/personal", true),
+            Some(vec![7, 8, 9])
+        );
+    }
+
+    #[test]
+    fn fuzzy_prefers_consecutive_characters() {
+        assert_eq!(positions("ab", "xaxbxab", true), Some(vec![5, 6]));
+        let consecutive = find("ab", "xab", true).unwrap();
+        let gap = find("ab", "xaxb", true).unwrap();
+        assert!(consecutive.score > gap.score);
+    }
+
+    #[test]
+    fn pattern_longer_than_text() {
+        assert_eq!(positions("github", "git", true), None);
+        assert_eq!(positions("github", "git", false), None);
+    }
+
+    #[test]
+    fn substring_highlights_all_occurrences() {
+        assert_eq!(positions("oo", "foo/boo", false), Some(vec![1, 2, 5, 6]));
+        // Occurrences don't overlap
+        assert_eq!(positions("aa", "aaa", false), Some(vec![0, 1]));
+        assert_eq!(positions("ob", "foo/boo", false), None);
+    }
+
+    #[test]
+    fn empty_pattern_matches_everything() {
+        assert_eq!(positions("", "github", true), Some(Vec::new()));
+        assert_eq!(positions("", "github", false), Some(Vec::new()));
+    }
+
+    #[test]
+    fn case_is_ignored_beyond_ascii() {
+        assert_eq!(
+            positions("ÄRGER", "ärger", false),
+            Some(vec![0, 1, 2, 3, 4])
+        );
+        assert_eq!(positions("öl", "Bank/Öl", true), Some(vec![5, 6]));
+        // `İ` lowercases to two characters, the positions stay the same
+        assert_eq!(positions("stan", "İstanbul", false), Some(vec![1, 2, 3, 4]));
+        assert_eq!(positions("i", "İstanbul", true), Some(vec![0]));
+    }
+}
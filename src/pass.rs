@@ -0,0 +1,152 @@
+use std::{
+    env,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::generator;
+
+/// Permission bits removed from created files and folders unless
+/// `PASSWORD_STORE_UMASK` sets others, like in `pass`
+const DEFAULT_UMASK: u32 = 0o077;
+
+/// Returns the permission bits that `pass` removes from the files and
+/// folders it creates in the store, taken from `PASSWORD_STORE_UMASK`.
+pub fn umask() -> u32 {
+    env::var("PASSWORD_STORE_UMASK")
+        .ok()
+        .and_then(|umask| u32::from_str_radix(umask.trim(), 8).ok())
+        .map_or(DEFAULT_UMASK, |umask| umask & 0o777)
+}
+
+/// How `pass` is invoked.
+///
+/// The environment of passepartui is passed on, so variables like
+/// `PASSWORD_STORE_DIR`, `PASSWORD_STORE_ENABLE_EXTENSIONS` and
+/// `PASSWORD_STORE_EXTENSIONS_DIR` are respected unless they are set here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pass {
+    /// Name or path of the executable, e.g. for a non-standard prefix
+    pub program: String,
+    /// Environment variables set in addition to the inherited ones
+    pub envs: Vec<(String, String)>,
+}
+
+impl Default for Pass {
+    fn default() -> Self {
+        Self {
+            program: "pass".to_string(),
+            envs: Vec::new(),
+        }
+    }
+}
+
+impl Pass {
+    /// Runs `pass` with the given arguments, environment variables and
+    /// optional input on stdin.
+    ///
+    /// The error output of `pass` is returned as error message if it fails.
+    fn run(
+        &self,
+        args: &[&str],
+        envs: &[(&str, &str)],
+        input: Option<&str>,
+    ) -> Result<String, passepartout::Error> {
+        let mut child = Command::new(&self.program)
+            .args(args)
+            .envs(self.envs.iter().map(|(key, value)| (key, value)))
+            .envs(envs.iter().copied())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(input) = input {
+            let mut stdin = child.stdin.take().expect("stdin is piped");
+            stdin.write_all(input.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            Err(passepartout::Error::Pass(message))
+        }
+    }
+
+    /// Inserts a new password file, `pass` asks before replacing an existing
+    /// file so the caller has to make sure that it does not exist.
+    pub fn insert(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        self.run(
+            &["insert", "--multiline", pass_id],
+            &[],
+            Some(file_contents),
+        )?;
+        Ok(())
+    }
+
+    /// Renames a password file or moves it to another folder, `pass` asks
+    /// before replacing an existing file which is answered with no.
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), passepartout::Error> {
+        self.run(&["mv", from, to], &[], None)?;
+        Ok(())
+    }
+
+    /// Removes a password file without asking for confirmation.
+    pub fn remove(&self, pass_id: &str) -> Result<(), passepartout::Error> {
+        self.run(&["rm", "--force", pass_id], &[], None)?;
+        Ok(())
+    }
+
+    /// Generates a new password file with `pass generate`, the password is
+    /// made of the characters allowed by the options.
+    pub fn generate(
+        &self,
+        pass_id: &str,
+        options: &generator::Options,
+    ) -> Result<(), passepartout::Error> {
+        let length = options.length.to_string();
+        let character_set = options.tr_set();
+        self.run(
+            &["generate", pass_id, &length],
+            &[
+                ("PASSWORD_STORE_CHARACTER_SET", &character_set),
+                ("PASSWORD_STORE_CHARACTER_SET_NO_SYMBOLS", &character_set),
+            ],
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Runs `pass git`, git must not ask for credentials on the terminal the
+    /// interface is drawn on.
+    pub fn git(&self, args: &[&str]) -> Result<String, passepartout::Error> {
+        let args: Vec<&str> = ["git"].into_iter().chain(args.iter().copied()).collect();
+        self.run(&args, &[("GIT_TERMINAL_PROMPT", "0")], None)
+    }
+
+    /// Replaces the contents of an existing password file.
+    pub fn overwrite(&self, pass_id: &str, file_contents: &str) -> Result<(), passepartout::Error> {
+        self.run(
+            &["insert", "--multiline", "--force", pass_id],
+            &[],
+            Some(file_contents),
+        )?;
+        Ok(())
+    }
+
+    /// Edits a password file with `pass edit` on the terminal, which has to
+    /// be handed over to the editor.
+    pub fn edit(&self, pass_id: &str) -> Result<(), passepartout::Error> {
+        let status = Command::new(&self.program)
+            .args(["edit", pass_id])
+            .envs(self.envs.iter().map(|(key, value)| (key, value)))
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(passepartout::Error::Pass(format!(
+                "editing {pass_id} failed ({status})"
+            )))
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Values remembered from decrypted password files for using them without
+//! decrypting the entries again, e.g. the logins shown in the login column
+//! of the table, the hosts of URLs for looking up entries or the entries
+//! aliases stand in for.
+//!
+//! The values are kept in plain text in the data directory, nothing is
+//! written to the password store.
+
+use std::{collections::HashMap, io, path::Path};
+
+use crate::data;
+
+#[derive(Debug, Default, Clone)]
+pub struct Remembered {
+    /// One value per entry
+    values: HashMap<String, String>,
+}
+
+impl Remembered {
+    /// Reads a data file, lines have the form `<pass_id>\t<value>`.
+    pub fn load(data_dir: Option<&Path>, file_name: &str) -> Self {
+        let values = data::read_lines(data_dir, file_name)
+            .iter()
+            .filter_map(|line| {
+                let (pass_id, value) = line.split_once('\t')?;
+                Some((pass_id.to_string(), value.to_string()))
+            })
+            .collect();
+        Self { values }
+    }
+
+    pub fn save(&self, data_dir: Option<&Path>, file_name: &str) -> io::Result<()> {
+        let mut lines: Vec<String> = self
+            .values
+            .iter()
+            .map(|(pass_id, value)| format!("{pass_id}\t{value}"))
+            .collect();
+        lines.sort();
+        data::write_lines(data_dir, file_name, &lines)
+    }
+
+    /// Remembers the value for an entry, an empty value is forgotten.
+    /// Returns true if the value changed.
+    pub fn record(&mut self, pass_id: &str, value: &str) -> bool {
+        let value = value.trim();
+        if value.is_empty() {
+            return self.values.remove(pass_id).is_some();
+        }
+        if self.get(pass_id) == Some(value) {
+            return false;
+        }
+        self.values.insert(pass_id.to_string(), value.to_string());
+        true
+    }
+
+    pub fn get(&self, pass_id: &str) -> Option<&str> {
+        self.values.get(pass_id).map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(pass_id, value)| (pass_id.as_str(), value.as_str()))
+    }
+
+    pub fn pass_ids(&self) -> Vec<String> {
+        self.values.keys().cloned().collect()
+    }
+
+    /// Keeps the value of an entry that was renamed or moved.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        if let Some(value) = self.values.remove(from) {
+            self.values.insert(to.to_string(), value);
+        }
+    }
+
+    /// Forgets the values the predicate rejects, returns true if any was
+    /// forgotten.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) -> bool {
+        let count = self.values.len();
+        self.values.retain(|_, value| keep(value));
+        self.values.len() != count
+    }
+
+    pub fn remove(&mut self, pass_id: &str) {
+        self.values.remove(pass_id);
+    }
+}
@@ -0,0 +1,208 @@
+//! Templates for laying out the pass ID column of the table, e.g.
+//! `{folder:<20} {name} {flags:>6}`.
+//!
+//! A placeholder is replaced with a part of the entry and can be padded to a
+//! width, aligned left with `<`, right with `>` or centered with `^`. Longer
+//! values are cut off with `…`. Braces are written as `{{` and `}}`.
+
+/// Part of an entry a placeholder stands for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Value {
+    /// The whole pass ID
+    Id,
+    /// Folders of the pass ID without the trailing slash
+    Folder,
+    /// Pass ID without its folders
+    Name,
+    /// Marks of the entry like ↪ for links
+    Flags,
+}
+
+impl Value {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "id" => Some(Self::Id),
+            "folder" => Some(Self::Folder),
+            "name" => Some(Self::Name),
+            "flags" => Some(Self::Flags),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Literal(String),
+    Placeholder {
+        value: Value,
+        align: Align,
+        width: Option<usize>,
+    },
+}
+
+/// A piece of a formatted row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Piece {
+    /// Text of the template and padding
+    Text(String),
+    /// Characters of the pass ID starting at the character offset, which
+    /// are highlighted when they match the search
+    Id { text: String, offset: usize },
+    /// Marks of the entry, styled like the marks after the pass ID
+    Flags(String),
+}
+
+/// A parsed row template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFormat {
+    parts: Vec<Part>,
+}
+
+impl RowFormat {
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err("unmatched `}`, write `}}` for a brace".to_string()),
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => return Err("unmatched `{`, write `{{` for a brace".to_string()),
+                        }
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(parse_placeholder(&placeholder)?);
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Returns true if the template places the marks of entries, they are
+    /// appended to the row otherwise.
+    pub fn has_flags(&self) -> bool {
+        self.parts.iter().any(|part| {
+            matches!(
+                part,
+                Part::Placeholder {
+                    value: Value::Flags,
+                    ..
+                }
+            )
+        })
+    }
+
+    /// Lays out the row of an entry with the given marks.
+    pub fn format(&self, pass_id: &str, flags: &str) -> Vec<Piece> {
+        let (folder, name) = pass_id.rsplit_once('/').unwrap_or(("", pass_id));
+        let name_offset = pass_id.chars().count() - name.chars().count();
+        let mut pieces = Vec::new();
+        for part in &self.parts {
+            let (value, align, width) = match part {
+                Part::Literal(text) => {
+                    pieces.push(Piece::Text(text.clone()));
+                    continue;
+                }
+                Part::Placeholder {
+                    value,
+                    align,
+                    width,
+                } => (*value, *align, *width),
+            };
+            let text = match value {
+                Value::Id => pass_id,
+                Value::Folder => folder,
+                Value::Name => name,
+                Value::Flags => flags,
+            };
+            let length = text.chars().count();
+            let (text, cut) = match width {
+                Some(width) if length > width => (
+                    text.chars()
+                        .take(width.saturating_sub(1))
+                        .collect::<String>(),
+                    width > 0,
+                ),
+                _ => (text.to_string(), false),
+            };
+            let padding = width.unwrap_or_default().saturating_sub(length);
+            let (before, after) = match align {
+                Align::Left => (0, padding),
+                Align::Right => (padding, 0),
+                Align::Center => (padding / 2, padding - padding / 2),
+            };
+
+            if before > 0 {
+                pieces.push(Piece::Text(" ".repeat(before)));
+            }
+            pieces.push(match value {
+                Value::Id | Value::Folder => Piece::Id { text, offset: 0 },
+                Value::Name => Piece::Id {
+                    text,
+                    offset: name_offset,
+                },
+                Value::Flags => Piece::Flags(text),
+            });
+            if cut {
+                pieces.push(Piece::Text("…".to_string()));
+            }
+            if after > 0 {
+                pieces.push(Piece::Text(" ".repeat(after)));
+            }
+        }
+        pieces
+    }
+}
+
+/// Parses the inside of a placeholder like `name` or `folder:<20`.
+fn parse_placeholder(placeholder: &str) -> Result<Part, String> {
+    let (name, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+    let value = Value::from_name(name.trim()).ok_or_else(|| {
+        format!("unknown placeholder `{{{name}}}`, use `id`, `folder`, `name` or `flags`")
+    })?;
+    let (align, width) = match spec.chars().next() {
+        Some('<') => (Align::Left, &spec[1..]),
+        Some('>') => (Align::Right, &spec[1..]),
+        Some('^') => (Align::Center, &spec[1..]),
+        _ => (Align::Left, spec),
+    };
+    let width = match width {
+        "" => None,
+        width => Some(
+            width
+                .parse()
+                .map_err(|_| format!("invalid width `{width}` in `{{{placeholder}}}`"))?,
+        ),
+    };
+    Ok(Part::Placeholder {
+        value,
+        align,
+        width,
+    })
+}
@@ -0,0 +1,68 @@
+//! Decrypted contents kept in memory that is locked against being swapped
+//! to disk where the system allows it, and wiped when dropped.
+
+use region::LockGuard;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Text in a buffer of its own pages, which are locked with `mlock` (or
+/// `VirtualLock` on Windows).
+///
+/// Locks are not counted by the system, unlocking a page shared with another
+/// secret would unlock it for both, so the text starts at a page boundary of
+/// a buffer that is larger by a page.
+pub struct Secret {
+    /// `None` if locking failed, e.g. above `RLIMIT_MEMLOCK`. Declared
+    /// first, fields are dropped in order and the pages have to be unlocked
+    /// before the buffer is freed.
+    _lock: Option<LockGuard>,
+    buffer: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl Secret {
+    /// Moves the text into locked memory, the string is wiped. Copies of it
+    /// made before are left to the caller.
+    pub fn new(mut text: String) -> Self {
+        let page_size = region::page::size();
+        let len = text.len();
+        let locked_size = len.div_ceil(page_size).max(1) * page_size;
+        let mut buffer = vec![0; locked_size + page_size];
+        let start = buffer.as_ptr().align_offset(page_size).min(page_size);
+        buffer[start..start + len].copy_from_slice(text.as_bytes());
+        text.zeroize();
+        let lock = region::lock(buffer[start..].as_ptr(), locked_size).ok();
+        Self {
+            _lock: lock,
+            buffer,
+            start,
+            len,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buffer[self.start..self.start + self.len])
+            .expect("copied from a string")
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        // Wiped before the fields are dropped, which releases the lock and
+        // then frees the buffer
+        self.buffer.zeroize();
+    }
+}
+
+impl PartialEq for Secret {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
@@ -0,0 +1,244 @@
+//! `passepartui selftest` checks the environment without starting the TUI,
+//! e.g. for validating a package build.
+
+use anyhow::{anyhow, bail, Context, Result};
+use arboard::Clipboard;
+use std::{
+    env, fs,
+    io::Write,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
+use totp_rs::TOTP;
+
+use crate::{
+    clipboard::Provider,
+    config::Config,
+    gpg,
+    store::{self, Excludes, StoreSnapshot},
+};
+
+const KEY_USER_ID: &str = "passepartui selftest <selftest@passepartui.invalid>";
+const FILE_CONTENTS: &str = "correct horse battery staple\nlogin: selftest\n";
+// Test vector from RFC 6238 for SHA1 with 8 digits at 59 seconds
+const OTP_URI: &str = "otpauth://totp/passepartui:selftest?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&digits=8&issuer=passepartui";
+const OTP_EXPECTED: &str = "94287082";
+
+type Check = fn() -> Result<String>;
+
+/// A problem that is reported without failing the self-test.
+#[derive(Debug)]
+struct Warning(String);
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Warning {}
+
+/// Temporary directory that is removed when dropped.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Result<Self> {
+        let path = env::temp_dir().join(format!("passepartui-{}-{name}", process::id()));
+        fs::create_dir_all(&path)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o700))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Runs all checks and prints a report, fails if any check failed.
+pub fn run() -> Result<()> {
+    let checks: [(&str, Check); 6] = [
+        ("Configuration", check_config),
+        // Before the decryption check, which points GnuPG to a temporary home
+        ("Passphrase cache", check_passphrase_cache),
+        ("Store scan", check_store_scan),
+        ("Decryption", check_decryption),
+        ("Clipboard", check_clipboard),
+        ("One-time password", check_otp),
+    ];
+    println!("passepartui {} self-test", env!("CARGO_PKG_VERSION"));
+    let mut failed = 0;
+    for (name, check) in checks {
+        match check() {
+            Ok(detail) => println!("[ ok ] {name}: {detail}"),
+            Err(e) if e.is::<Warning>() => println!("[warn] {name}: {e}"),
+            Err(e) => {
+                failed += 1;
+                println!("[fail] {name}: {e:#}");
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        count => Err(anyhow!("{count} of {} checks failed", checks.len())),
+    }
+}
+
+fn check_config() -> Result<String> {
+    Config::load()?;
+    Ok(match Config::path() {
+        Some(path) if path.exists() => format!("{} is valid", path.display()),
+        _ => "no configuration file, using defaults".to_string(),
+    })
+}
+
+/// Reports how long `gpg-agent` caches passphrases, a cache time of zero
+/// is a warning as every decryption then shows pinentry.
+fn check_passphrase_cache() -> Result<String> {
+    let policy = gpg::cache_policy().context("failed to read gpg-agent.conf")?;
+    let source = match gpg::agent_config_path() {
+        Some(path) if path.exists() => path.display().to_string(),
+        _ => "the defaults of gpg-agent".to_string(),
+    };
+    let detail = format!(
+        "default-cache-ttl {}s, max-cache-ttl {}s from {source}",
+        policy.default_ttl, policy.max_ttl
+    );
+    if policy.never_caches() {
+        return Err(Warning(format!(
+            "{detail}, passphrases are never cached and every decryption asks for it with \
+             pinentry"
+        ))
+        .into());
+    }
+    Ok(detail)
+}
+
+fn check_store_scan() -> Result<String> {
+    let store_dir = TempDir::new("store")?;
+    for file in [
+        "a.gpg",
+        "folder/b.gpg",
+        "folder/notes.txt",
+        ".git/c.gpg",
+        "old/d.gpg",
+    ] {
+        let path = store_dir.0.join(file);
+        fs::create_dir_all(path.parent().expect("file is inside the store"))?;
+        fs::write(path, "")?;
+    }
+    let excludes = Excludes::new(vec!["old/**".to_string()]);
+    let scan = store::scan(
+        &store_dir.0,
+        Vec::new(),
+        StoreSnapshot::default(),
+        &excludes,
+    );
+    let ids: Vec<&str> = scan.passwords.iter().map(|info| info.id.as_str()).collect();
+    if ids != ["a", "folder/b"] {
+        bail!("unexpected entries {ids:?}");
+    }
+    Ok(format!("found {} entries in a temporary store", ids.len()))
+}
+
+/// Encrypts a file for a key generated in a temporary GnuPG home directory
+/// and decrypts it again.
+fn check_decryption() -> Result<String> {
+    let gnupg_home = TempDir::new("gnupg")?;
+    let store_dir = TempDir::new("gpg-store")?;
+    // Used by gpg and gpgme from here on
+    env::set_var("GNUPGHOME", &gnupg_home.0);
+
+    let result = (|| {
+        gpg(
+            &gnupg_home.0,
+            &[
+                "--passphrase",
+                "",
+                "--quick-generate-key",
+                KEY_USER_ID,
+                "default",
+                "default",
+                "never",
+            ],
+            None,
+        )
+        .context("failed to generate a key")?;
+        let file_path = store_dir.0.join("selftest.gpg");
+        let output = file_path.to_string_lossy();
+        gpg(
+            &gnupg_home.0,
+            &[
+                "--trust-model",
+                "always",
+                "--recipient",
+                KEY_USER_ID,
+                "--output",
+                &output,
+                "--encrypt",
+            ],
+            Some(FILE_CONTENTS),
+        )
+        .context("failed to encrypt")?;
+        let decrypted = passepartout::decrypt_password_file(&file_path)?;
+        if decrypted != FILE_CONTENTS {
+            bail!("decrypted contents differ");
+        }
+        Ok("round-trip with a temporary key succeeded".to_string())
+    })();
+
+    let _ = Command::new("gpgconf")
+        .args(["--kill", "gpg-agent"])
+        .env("GNUPGHOME", &gnupg_home.0)
+        .output();
+    result
+}
+
+fn gpg(gnupg_home: &Path, args: &[&str], input: Option<&str>) -> Result<()> {
+    let mut child = Command::new("gpg")
+        .args(["--batch", "--yes", "--pinentry-mode", "loopback"])
+        .args(args)
+        .env("GNUPGHOME", gnupg_home)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run gpg")?;
+    if let Some(input) = input {
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+fn check_clipboard() -> Result<String> {
+    // The terminal can't be checked, it doesn't answer escape sequences for
+    // setting the clipboard
+    let provider = Config::load().map_or(Provider::default(), |config| config.clipboard.provider);
+    match provider {
+        Provider::Osc52 => Ok("configured to use the terminal with OSC 52".to_string()),
+        Provider::Command(args) => Ok(format!("configured to run `{}`", args.join(" "))),
+        Provider::Auto if Clipboard::new().is_err() => {
+            Ok("system clipboard not available, falling back to OSC 52".to_string())
+        }
+        _ => {
+            Clipboard::new()?;
+            Ok("clipboard backend is available".to_string())
+        }
+    }
+}
+
+fn check_otp() -> Result<String> {
+    let totp = TOTP::from_url(OTP_URI)?;
+    let otp = totp.generate(59);
+    if otp != OTP_EXPECTED {
+        bail!("generated {otp} instead of {OTP_EXPECTED}");
+    }
+    Ok("matches the RFC 6238 test vector".to_string())
+}
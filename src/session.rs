@@ -0,0 +1,44 @@
+//! Where passepartui runs, which decides where copied text can end up.
+
+use std::env;
+
+use crate::clipboard::Provider;
+
+/// Returns true if the terminal is connected through SSH, the system
+/// clipboard is then the one of the remote host.
+pub fn over_ssh() -> bool {
+    ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"]
+        .iter()
+        .any(|name| env::var_os(name).is_some())
+}
+
+/// Returns true if running as root, e.g. through `sudo`.
+#[cfg(unix)]
+pub fn as_root() -> bool {
+    // SAFETY: `geteuid` has no preconditions and can't fail
+    unsafe { libc::geteuid() == 0 }
+}
+
+#[cfg(not(unix))]
+pub fn as_root() -> bool {
+    false
+}
+
+/// Returns a warning about what happens to copied text as root or over
+/// SSH, `None` otherwise.
+pub fn warning(provider: &Provider) -> Option<String> {
+    let mut warnings = Vec::new();
+    if as_root() {
+        warnings
+            .push("Running as root, the store, keys and clipboard of root are used".to_string());
+    }
+    if over_ssh() {
+        warnings.push(match provider {
+            Provider::Osc52 => "Over SSH, copies go to your local terminal with OSC 52".to_string(),
+            _ => "Over SSH, copies may land on the clipboard of this host instead of your \
+                  terminal's"
+                .to_string(),
+        });
+    }
+    (!warnings.is_empty()).then(|| warnings.join(" · "))
+}
@@ -0,0 +1,143 @@
+//! Sanitizing of error messages before they are shown outside of the error
+//! popup.
+//!
+//! The error output of `pass` and `gpg` can contain file paths and, with
+//! some wrappers, parts of the decrypted file, so only a shortened version is
+//! shown in the status bar and the details of an entry.
+
+/// Maximum number of characters of a sanitized message
+const MAX_LENGTH: usize = 80;
+
+/// Minimum length of a word that is redacted if it looks like a secret
+const MIN_SECRET_LENGTH: usize = 16;
+
+/// Returns the first line of an error message with paths shortened to their
+/// file name and secret-like words removed, truncated to a length that fits
+/// the status bar.
+pub fn redact(message: &str) -> String {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+    let words: Vec<String> = strip_control(line)
+        .split_whitespace()
+        .map(redact_word)
+        .collect();
+    let redacted = words.join(" ");
+
+    if redacted.chars().count() > MAX_LENGTH {
+        let truncated: String = redacted.chars().take(MAX_LENGTH - 1).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        redacted
+    }
+}
+
+/// Removes control characters and terminal escape sequences like colors.
+fn strip_control(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip the sequence up to its final letter
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+        } else if c.is_whitespace() {
+            // Tabs still separate words
+            stripped.push(' ');
+        } else if !c.is_control() {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+fn redact_word(word: &str) -> String {
+    let core = word.trim_matches(|c: char| "\"'`()[]<>,;:".contains(c));
+    if core.is_empty() {
+        return word.to_string();
+    }
+    let replacement = if core.starts_with('/') || core.starts_with("~/") {
+        // Only the file name is kept from absolute paths
+        let file_name = core.rsplit('/').find(|part| !part.is_empty());
+        format!("…/{}", file_name.unwrap_or_default())
+    } else if looks_like_secret(core) {
+        "[redacted]".to_string()
+    } else {
+        return word.to_string();
+    };
+    word.replacen(core, &replacement, 1)
+}
+
+/// Long words mixing letters with digits or symbols are likely passwords or
+/// tokens rather than part of an error message.
+fn looks_like_secret(word: &str) -> bool {
+    word.chars().count() >= MIN_SECRET_LENGTH
+        && word.chars().any(char::is_alphabetic)
+        && word
+            .chars()
+            .any(|c| !c.is_alphabetic() && c != '-' && c != '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_shortened_to_file_names() {
+        assert_eq!(
+            redact(
+                "gpg: decryption failed: /home/jane/.password-store/email/work.gpg: No secret key"
+            ),
+            "gpg: decryption failed: …/work.gpg: No secret key"
+        );
+        assert_eq!(
+            redact("cannot open ~/.password-store/wifi/home.gpg"),
+            "cannot open …/home.gpg"
+        );
+        assert_eq!(redact("'/tmp/store/' is missing"), "'…/store' is missing");
+    }
+
+    #[test]
+    fn secret_like_words_are_redacted() {
+        assert_eq!(
+            redact("token ghp_A1b2C3d4E5f6G7h8I9 was rejected"),
+            "token [redacted] was rejected"
+        );
+        assert_eq!(redact("(s3cr3t-P4ssw0rd!!)"), "([redacted])");
+        // Long plain and hyphenated words are part of the message
+        assert_eq!(
+            redact("internationalization of well-known-certificate-authorities"),
+            "internationalization of well-known-certificate-authorities"
+        );
+    }
+
+    #[test]
+    fn colors_and_control_characters_are_stripped() {
+        assert_eq!(
+            redact("\x1b[1;31merror\x1b[0m: bad\tinput\x07"),
+            "error: bad input"
+        );
+    }
+
+    #[test]
+    fn long_messages_are_truncated() {
+        let redacted = redact(&"ä".repeat(100));
+        assert_eq!(redacted.chars().count(), MAX_LENGTH);
+        assert_eq!(redacted, format!("{}…", "ä".repeat(MAX_LENGTH - 1)));
+        let short = "ä".repeat(MAX_LENGTH);
+        assert_eq!(redact(&short), short);
+    }
+
+    #[test]
+    fn only_the_first_line_is_kept() {
+        assert_eq!(redact("\n  \n first line \nsecond line"), "first line");
+        assert_eq!(redact(""), "");
+    }
+}
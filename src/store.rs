@@ -0,0 +1,578 @@
+use passepartout::PasswordInfo;
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::pass;
+
+/// Modification times of the directories in the password store.
+///
+/// Adding, removing or renaming a password file updates the modification
+/// time of its parent directory, which allows to narrow down a re-scan to
+/// the directories that actually changed.
+#[derive(Debug, Default)]
+pub struct StoreSnapshot {
+    dirs: HashMap<PathBuf, Option<SystemTime>>,
+    /// Symbolic links that are followed, relative to the store
+    links: Vec<String>,
+    /// Password files and folders that can't be read, relative to the store
+    unreadable: Vec<String>,
+    /// Password files and folders more open than the umask of `pass` allows
+    permissive: Vec<String>,
+}
+
+impl StoreSnapshot {
+    pub fn new(store_dir: &Path, excludes: &Excludes) -> Self {
+        let walk = collect_dirs(store_dir, excludes);
+        Self {
+            dirs: walk.dirs,
+            links: walk.links,
+            unreadable: walk.unreadable,
+            permissive: walk.permissive,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty()
+    }
+
+    /// Returns the paths of the linked folders and pass IDs of the linked
+    /// entries, relative to the store.
+    pub fn links(&self) -> &[String] {
+        &self.links
+    }
+
+    /// Returns the pass IDs of the password files and the paths of the
+    /// folders, with a trailing slash, that the user has no permission to
+    /// read, e.g. ones left owned by root.
+    pub fn unreadable(&self) -> &[String] {
+        &self.unreadable
+    }
+
+    /// Returns the pass IDs of the password files and the paths of the
+    /// folders, with a trailing slash, that have permission bits the umask
+    /// of `pass` would have removed, e.g. readable by other users. The store
+    /// itself is checked at startup, see [`crate::hardening`].
+    pub fn permissive(&self) -> &[String] {
+        &self.permissive
+    }
+
+    /// Returns the directories that were added, removed or modified since
+    /// the snapshot was taken and updates the snapshot.
+    pub fn changed_dirs(&mut self, store_dir: &Path, excludes: &Excludes) -> Vec<PathBuf> {
+        let Walk {
+            dirs: current,
+            links,
+            unreadable,
+            permissive,
+            ..
+        } = collect_dirs(store_dir, excludes);
+        self.links = links;
+        self.unreadable = unreadable;
+        self.permissive = permissive;
+
+        let mut changed: Vec<PathBuf> = current
+            .iter()
+            .filter(|(dir, modified)| self.dirs.get(*dir) != Some(modified))
+            .map(|(dir, _)| dir.clone())
+            .collect();
+        changed.extend(
+            self.dirs
+                .keys()
+                .filter(|dir| !current.contains_key(*dir))
+                .cloned(),
+        );
+        changed.sort();
+
+        self.dirs = current;
+        changed
+    }
+}
+
+/// Directories of the store found by walking it.
+#[derive(Debug, Default)]
+struct Walk {
+    /// Directories with their modification times
+    dirs: HashMap<PathBuf, Option<SystemTime>>,
+    /// Symbolic links that are followed
+    links: Vec<String>,
+    /// Password files and folders that can't be read
+    unreadable: Vec<String>,
+    /// Password files and folders more open than the umask allows
+    permissive: Vec<String>,
+    /// Permission bits removed by the umask of `pass`
+    umask: u32,
+    /// Canonical paths of the directories entered
+    visited: HashSet<PathBuf>,
+    /// Links to folders, entered after all others
+    linked_dirs: Vec<PathBuf>,
+}
+
+/// Collects the directories of the store, excluded ones are not entered.
+///
+/// Linked folders are entered after all others and only if their target
+/// was not visited yet, so that entries are listed once under their real
+/// path and a link to a parent folder does not loop.
+fn collect_dirs(store_dir: &Path, excludes: &Excludes) -> Walk {
+    let mut walk = Walk {
+        umask: pass::umask(),
+        ..Walk::default()
+    };
+    walk.walk_dir(store_dir, store_dir, excludes);
+    let mut next = 0;
+    while let Some(link) = walk.linked_dirs.get(next).cloned() {
+        next += 1;
+        let Ok(target) = fs::canonicalize(&link) else {
+            continue;
+        };
+        if walk.visited.contains(&target) {
+            continue;
+        }
+        if let Some(relative) = relative_path(store_dir, &link) {
+            walk.links.push(relative);
+        }
+        walk.walk_dir(store_dir, &link, excludes);
+    }
+    walk.links.sort();
+    walk.unreadable.sort();
+    walk.permissive.sort();
+    walk
+}
+
+impl Walk {
+    /// Adds the directory and the ones below it without following links,
+    /// which are collected in `linked_dirs` for folders and `links` for
+    /// entries.
+    fn walk_dir(&mut self, store_dir: &Path, dir: &Path, excludes: &Excludes) {
+        if let Ok(canonical) = fs::canonicalize(dir) {
+            if !self.visited.insert(canonical) {
+                return;
+            }
+        }
+        let metadata = dir.metadata().ok();
+        let modified = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.modified().ok());
+        self.dirs.insert(dir.to_path_buf(), modified);
+        // The permissions of linked folders are those of their targets,
+        // which are left alone
+        let is_link = fs::symlink_metadata(dir).is_ok_and(|metadata| metadata.is_symlink());
+        if dir != store_dir
+            && !is_link
+            && metadata.is_some_and(|metadata| self.is_permissive(&metadata))
+        {
+            if let Some(relative) = relative_path(store_dir, dir) {
+                self.permissive.push(format!("{relative}/"));
+            }
+        }
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                if e.kind() == io::ErrorKind::PermissionDenied {
+                    if let Some(relative) = relative_path(store_dir, dir) {
+                        self.unreadable.push(format!("{relative}/"));
+                    }
+                }
+                return;
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(relative) = relative_path(store_dir, &path) else {
+                continue;
+            };
+            if entry.file_name() == ".git" || excludes.matches(&relative) {
+                continue;
+            }
+            let is_link = entry
+                .file_type()
+                .is_ok_and(|file_type| file_type.is_symlink());
+            if is_link && path.is_dir() {
+                self.linked_dirs.push(path);
+            } else if path.is_dir() {
+                self.walk_dir(store_dir, &path, excludes);
+            } else if is_password_file(&path) {
+                let Some(pass_id) = pass_id(store_dir, &path) else {
+                    continue;
+                };
+                if !is_readable(&path) {
+                    self.unreadable.push(pass_id.clone());
+                }
+                if !is_link
+                    && path
+                        .metadata()
+                        .is_ok_and(|metadata| self.is_permissive(&metadata))
+                {
+                    self.permissive.push(pass_id.clone());
+                }
+                if is_link && !links_into_store(store_dir, &path, excludes) {
+                    self.links.push(pass_id);
+                }
+            }
+        }
+    }
+
+    /// Returns true if the file or folder has permission bits the umask
+    /// would have removed.
+    #[cfg(unix)]
+    fn is_permissive(&self, metadata: &fs::Metadata) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+
+        metadata.permissions().mode() & self.umask & 0o777 != 0
+    }
+
+    #[cfg(not(unix))]
+    fn is_permissive(&self, _metadata: &fs::Metadata) -> bool {
+        false
+    }
+}
+
+fn relative_path(store_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(store_dir).ok()?;
+    Some(relative.to_string_lossy().into())
+}
+
+/// Counts the files and folders, which end with a slash, e.g. `2 files and
+/// 1 folder`.
+pub fn count_paths(paths: &[String]) -> String {
+    let folders = paths.iter().filter(|path| path.ends_with('/')).count();
+    let count = |n: usize, noun: &str| match n {
+        1 => format!("1 {noun}"),
+        n => format!("{n} {noun}s"),
+    };
+    match (paths.len() - folders, folders) {
+        (files, 0) => count(files, "file"),
+        (0, folders) => count(folders, "folder"),
+        (files, folders) => format!("{} and {}", count(files, "file"), count(folders, "folder")),
+    }
+}
+
+/// Returns true if the user may read the file, a password file left owned
+/// by root is listed but can't be decrypted.
+#[cfg(unix)]
+pub fn is_readable(path: &Path) -> bool {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    CString::new(path.as_os_str().as_bytes()).is_ok_and(|path| {
+        // SAFETY: the path is a valid C string that outlives the call
+        unsafe { libc::access(path.as_ptr(), libc::R_OK) == 0 }
+    })
+}
+
+#[cfg(not(unix))]
+pub fn is_readable(path: &Path) -> bool {
+    fs::File::open(path).is_ok()
+}
+
+/// Removes the permission bits of the umask from the files and folders
+/// given relative to the store like by [`StoreSnapshot::permissive`],
+/// returns how many were changed. Symlinks and paths in linked folders are
+/// skipped, changing their permissions would change those of the targets,
+/// which may be outside of the store.
+#[cfg(unix)]
+pub fn fix_permissions(store_dir: &Path, paths: &[String], umask: u32) -> io::Result<usize> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let store_dir = fs::canonicalize(store_dir)?;
+    let mut fixed = 0;
+    for path in paths {
+        let path = match path.strip_suffix('/') {
+            Some(folder) => store_dir.join(folder),
+            None => store_dir.join(format!("{path}.gpg")),
+        };
+        let metadata = fs::symlink_metadata(&path)?;
+        if metadata.is_symlink() || fs::canonicalize(&path)? != path {
+            continue;
+        }
+        let mode = metadata.permissions().mode();
+        if mode & umask & 0o777 != 0 {
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode & !umask))?;
+            fixed += 1;
+        }
+    }
+    Ok(fixed)
+}
+
+#[cfg(not(unix))]
+pub fn fix_permissions(_store_dir: &Path, _paths: &[String], _umask: u32) -> io::Result<usize> {
+    Ok(0)
+}
+
+fn is_password_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gpg"))
+}
+
+/// Returns true if the link points to an entry of the store, which is listed
+/// under its own path already.
+fn links_into_store(store_dir: &Path, link: &Path, excludes: &Excludes) -> bool {
+    let (Ok(store_dir), Ok(target)) = (fs::canonicalize(store_dir), fs::canonicalize(link)) else {
+        return false;
+    };
+    target
+        .strip_prefix(store_dir)
+        .is_ok_and(|relative| !excludes.matches(&relative.with_extension("").to_string_lossy()))
+}
+
+/// Paths of the store that are skipped when scanning, the patterns work like
+/// in `.gitignore`: `*` matches within a file or folder name, `**` across
+/// folders and a pattern without a slash matches a name at any depth.
+#[derive(Debug, Default, Clone)]
+pub struct Excludes {
+    patterns: Vec<String>,
+}
+
+impl Excludes {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self { patterns }
+    }
+
+    /// Returns true if the pass ID or folder path, relative to the store,
+    /// or one of its parent folders is excluded.
+    pub fn matches(&self, path: &str) -> bool {
+        self.patterns.iter().any(|pattern| {
+            let anchored = pattern.trim_end_matches('/').contains('/');
+            // A folder pattern excludes the folder itself
+            let pattern = pattern
+                .strip_suffix("/**")
+                .or_else(|| pattern.strip_suffix('/'))
+                .unwrap_or(pattern);
+            let pattern: Vec<char> = pattern.trim_start_matches('/').chars().collect();
+            let path: Vec<char> = path.chars().collect();
+            if anchored {
+                // The path itself and each of its parent folders
+                (1..=path.len())
+                    .filter(|&end| end == path.len() || path[end] == '/')
+                    .any(|end| glob(&pattern, &path[..end]))
+            } else {
+                path.split(|&c| c == '/').any(|name| glob(&pattern, name))
+            }
+        })
+    }
+}
+
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no folder at all
+            let rest = rest.strip_prefix(&['/']).unwrap_or(rest);
+            (0..=text.len())
+                .filter(|&start| start == 0 || text[start - 1] == '/')
+                .any(|start| glob(rest, &text[start..]))
+        }
+        ['*', rest @ ..] => (0..=text.len())
+            .take_while(|&start| start == 0 || text[start - 1] != '/')
+            .any(|start| glob(rest, &text[start..])),
+        ['?', rest @ ..] => text.first().is_some_and(|&c| c != '/') && glob(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob(rest, &text[1..]),
+    }
+}
+
+/// Result of scanning the password store.
+#[derive(Debug)]
+pub struct Scan {
+    pub passwords: Vec<PasswordInfo>,
+    pub snapshot: StoreSnapshot,
+    /// Whether entries were inserted, removed or updated
+    pub changed: bool,
+}
+
+/// Re-scans the directories that changed since the snapshot was taken and
+/// patches `passwords` accordingly, an empty snapshot results in a full scan.
+///
+/// The git repository of the store and excluded paths are skipped.
+pub fn scan(
+    store_dir: &Path,
+    mut passwords: Vec<PasswordInfo>,
+    mut snapshot: StoreSnapshot,
+    excludes: &Excludes,
+) -> Scan {
+    if snapshot.is_empty() {
+        let snapshot = StoreSnapshot::new(store_dir, excludes);
+        let mut scanned: Vec<PasswordInfo> = snapshot
+            .dirs
+            .keys()
+            .flat_map(|dir| read_dir(store_dir, dir, excludes))
+            .collect();
+        scanned.sort_by(|a, b| a.id.cmp(&b.id));
+        let changed = scanned.len() != passwords.len()
+            || scanned.iter().zip(&passwords).any(|(new, old)| {
+                new.id != old.id || new.metadata.modified().ok() != old.metadata.modified().ok()
+            });
+        return Scan {
+            passwords: scanned,
+            snapshot,
+            changed,
+        };
+    }
+    let mut changed = false;
+    for dir in snapshot.changed_dirs(store_dir, excludes) {
+        changed |= patch_dir(&mut passwords, store_dir, &dir, excludes);
+    }
+    Scan {
+        passwords,
+        snapshot,
+        changed,
+    }
+}
+
+/// Runs a blocking filesystem call on its own thread and waits at most for
+/// `timeout`, a store on a network filesystem may not respond for seconds.
+///
+/// Returns `None` if the call did not finish in time.
+pub fn with_timeout<T, F>(timeout: Duration, call: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(call());
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+/// Returns the modification time of a file if it can be determined.
+pub fn modified(file_path: &Path) -> Option<SystemTime> {
+    fs::metadata(file_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Returns the pass ID for a password file path inside the store.
+pub fn pass_id(store_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(store_dir).ok()?;
+    Some(relative.with_extension("").to_string_lossy().into())
+}
+
+/// Reads the password files located directly inside `dir`, excluded ones
+/// and links to other entries are left out.
+fn read_dir(store_dir: &Path, dir: &Path, excludes: &Excludes) -> Vec<PasswordInfo> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| {
+                    let is_link = entry
+                        .file_type()
+                        .is_ok_and(|file_type| file_type.is_symlink());
+                    let path = entry.path();
+                    is_password_file(&path)
+                        && !(is_link && links_into_store(store_dir, &path, excludes))
+                })
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let pass_id = pass_id(store_dir, &path)?;
+                    if excludes.matches(&pass_id) {
+                        return None;
+                    }
+                    let metadata = path.metadata().ok()?;
+                    Some(PasswordInfo::new(pass_id, metadata))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Re-reads the password files located directly inside `dir` and patches
+/// the sorted `passwords` vector accordingly.
+///
+/// Returns `true` if entries were inserted, removed or updated.
+pub fn patch_dir(
+    passwords: &mut Vec<PasswordInfo>,
+    store_dir: &Path,
+    dir: &Path,
+    excludes: &Excludes,
+) -> bool {
+    let mut scanned = read_dir(store_dir, dir, excludes);
+    scanned.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let in_dir = |info: &PasswordInfo| {
+        store_dir
+            .join(format!("{}.gpg", info.id))
+            .parent()
+            .is_some_and(|parent| parent == dir)
+    };
+
+    let mut changed = false;
+
+    // Remove entries that no longer exist
+    passwords.retain(|info| {
+        let keep = !in_dir(info) || scanned.iter().any(|other| other.id == info.id);
+        changed |= !keep;
+        keep
+    });
+
+    // Insert new entries and update modified ones
+    for info in scanned {
+        match passwords.binary_search_by(|other| other.id.cmp(&info.id)) {
+            Ok(index) => {
+                let old = &passwords[index];
+                if old.metadata.modified().ok() != info.metadata.modified().ok() {
+                    passwords[index] = info;
+                    changed = true;
+                }
+            }
+            Err(index) => {
+                passwords.insert(index, info);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{
+        env,
+        os::unix::fs::{symlink, PermissionsExt},
+        process,
+    };
+
+    use super::*;
+
+    fn mode(path: &Path) -> u32 {
+        fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[test]
+    fn fix_permissions_leaves_link_targets_alone() {
+        let root = env::temp_dir().join(format!("passepartui-store-{}", process::id()));
+        let store_dir = root.join("store");
+        let outside = root.join("outside");
+        fs::create_dir_all(&store_dir).unwrap();
+        fs::create_dir_all(outside.join("folder")).unwrap();
+        for path in [store_dir.join("entry.gpg"), outside.join("target.gpg")] {
+            fs::write(&path, "").unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        }
+        fs::write(outside.join("folder/inner.gpg"), "").unwrap();
+        fs::set_permissions(
+            outside.join("folder/inner.gpg"),
+            fs::Permissions::from_mode(0o644),
+        )
+        .unwrap();
+        symlink(outside.join("target.gpg"), store_dir.join("link.gpg")).unwrap();
+        symlink(outside.join("folder"), store_dir.join("linked")).unwrap();
+
+        let paths = ["entry", "link", "linked/inner"].map(String::from);
+        let fixed = fix_permissions(&store_dir, &paths, 0o077);
+        let modes = [
+            mode(&store_dir.join("entry.gpg")),
+            mode(&outside.join("target.gpg")),
+            mode(&outside.join("folder/inner.gpg")),
+        ];
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(fixed.unwrap(), 1);
+        assert_eq!(modes, [0o600, 0o644, 0o644]);
+    }
+}
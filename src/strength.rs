@@ -0,0 +1,321 @@
+//! Estimates how hard a password is to guess, in the spirit of zxcvbn.
+//!
+//! The password is split into the patterns that guessing tools try first,
+//! i.e. common passwords and words, years, repeated characters, sequences
+//! and keyboard rows, and the characters in between. Each pattern counts
+//! with the guesses needed to find it, the other characters with the size
+//! of their character classes.
+
+/// Passwords and words guessed first, compared in lowercase and with common
+/// substitutions like `0` for `o` undone
+const COMMON: &[&str] = &[
+    "password",
+    "passwort",
+    "qwerty",
+    "letmein",
+    "welcome",
+    "admin",
+    "administrator",
+    "iloveyou",
+    "monkey",
+    "dragon",
+    "football",
+    "baseball",
+    "soccer",
+    "hockey",
+    "master",
+    "sunshine",
+    "princess",
+    "shadow",
+    "superman",
+    "batman",
+    "trustno",
+    "login",
+    "starwars",
+    "hello",
+    "freedom",
+    "whatever",
+    "secret",
+    "michael",
+    "jordan",
+    "jennifer",
+    "charlie",
+    "summer",
+    "winter",
+    "spring",
+    "autumn",
+    "love",
+    "pass",
+    "user",
+    "guest",
+    "test",
+    "root",
+    "changeme",
+    "default",
+    "access",
+    "flower",
+    "cookie",
+    "pokemon",
+    "computer",
+    "internet",
+    "google",
+    "cheese",
+    "ninja",
+    "mustang",
+    "killer",
+    "pepper",
+    "ginger",
+    "orange",
+    "banana",
+    "purple",
+    "matrix",
+    "hunter",
+    "ranger",
+    "tigger",
+    "buster",
+    "thomas",
+    "robert",
+    "daniel",
+    "andrew",
+    "jessica",
+    "ashley",
+    "nicole",
+    "family",
+    "london",
+    "berlin",
+    "paris",
+    "company",
+];
+
+/// Rows of the keyboard, walked forwards or backwards
+const KEYBOARD_ROWS: &[&str] = &[
+    "qwertyuiop",
+    "asdfghjkl",
+    "zxcvbnm",
+    "qwertzuiop",
+    "yxcvbnm",
+];
+
+/// Passwords below this many bits are reported as weak
+const WEAK_BITS: f64 = 50.0;
+
+/// Weak passwords with fewer characters are reported as short
+const MIN_LENGTH: usize = 10;
+
+/// Why a password is easy to guess.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weakness {
+    /// Estimated bits of entropy, i.e. the binary logarithm of the guesses
+    pub bits: u32,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pattern {
+    Word,
+    Year,
+    Repeat,
+    Sequence,
+    Keyboard,
+}
+
+impl Pattern {
+    fn reason(self) -> &'static str {
+        match self {
+            Self::Word => "contains a common word",
+            Self::Year => "contains a year",
+            Self::Repeat => "repeats characters",
+            Self::Sequence => "contains a sequence like abc or 123",
+            Self::Keyboard => "follows a row of the keyboard",
+        }
+    }
+}
+
+/// Returns why the password is weak, `None` for strong passwords.
+pub fn weakness(password: &str) -> Option<Weakness> {
+    let chars: Vec<char> = password.chars().collect();
+    let normalized: Vec<char> = chars
+        .iter()
+        .map(|c| unleet(c.to_ascii_lowercase()))
+        .collect();
+    let length = chars.len();
+
+    // Digits or symbols appended to a common password don't help much
+    let stripped: String = password
+        .to_lowercase()
+        .trim_end_matches(|c: char| !c.is_alphabetic())
+        .chars()
+        .map(unleet)
+        .collect();
+    let common = COMMON.contains(&stripped.as_str());
+
+    let class_size = class_size(&chars);
+    let mut bits = 0.0;
+    // Characters covered by each kind of pattern
+    let mut covered = [0; 5];
+    let mut i = 0;
+    while i < length {
+        match find_pattern(&chars, &normalized, i) {
+            Some((pattern, len)) => {
+                bits += match pattern {
+                    Pattern::Word => (COMMON.len() as f64).log2() + 1.0,
+                    Pattern::Year => 7.0,
+                    Pattern::Repeat => class_size.log2() + (len as f64).log2(),
+                    Pattern::Sequence => 6.0 + (len as f64).log2(),
+                    Pattern::Keyboard => 5.0 + (len as f64).log2(),
+                };
+                covered[pattern as usize] += len;
+                i += len;
+            }
+            None => {
+                bits += class_size.log2();
+                i += 1;
+            }
+        }
+    }
+    if bits >= WEAK_BITS {
+        return None;
+    }
+
+    let patterns = [
+        Pattern::Word,
+        Pattern::Year,
+        Pattern::Repeat,
+        Pattern::Sequence,
+        Pattern::Keyboard,
+    ];
+    let reason = match patterns
+        .iter()
+        .max_by_key(|pattern| covered[**pattern as usize])
+    {
+        _ if common => "is a common password",
+        Some(pattern) if covered[*pattern as usize] * 3 >= length => pattern.reason(),
+        _ if length < MIN_LENGTH => "is short",
+        _ => "uses few kinds of characters",
+    };
+    Some(Weakness {
+        bits: bits as u32,
+        reason,
+    })
+}
+
+/// Undoes the substitutions of letters that guessing tools try.
+fn unleet(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        _ => c,
+    }
+}
+
+/// Returns the number of characters in the classes the password uses.
+fn class_size(chars: &[char]) -> f64 {
+    let has = |is_class: fn(&char) -> bool| chars.iter().any(is_class);
+    let mut size: f64 = 0.0;
+    if has(char::is_ascii_lowercase) {
+        size += 26.0;
+    }
+    if has(char::is_ascii_uppercase) {
+        size += 26.0;
+    }
+    if has(char::is_ascii_digit) {
+        size += 10.0;
+    }
+    if has(char::is_ascii_punctuation) {
+        size += 33.0;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        size += 100.0;
+    }
+    size.max(2.0)
+}
+
+/// Returns the pattern starting at the position with the number of
+/// characters it covers, the longest one if several match.
+fn find_pattern(chars: &[char], normalized: &[char], start: usize) -> Option<(Pattern, usize)> {
+    let rest: String = normalized[start..].iter().collect();
+    let mut found: Option<(Pattern, usize)> = None;
+    let mut consider = |pattern: Pattern, len: usize| {
+        if found.is_none_or(|(_, longest)| len > longest) {
+            found = Some((pattern, len));
+        }
+    };
+
+    if let Some(word) = COMMON
+        .iter()
+        .filter(|word| word.len() >= 4 && rest.starts_with(*word))
+        .max_by_key(|word| word.len())
+    {
+        consider(Pattern::Word, word.chars().count());
+    }
+
+    let digits: String = chars[start..].iter().take(4).collect();
+    if digits.len() == 4
+        && (digits.starts_with("19") || digits.starts_with("20"))
+        && digits.chars().all(|c| c.is_ascii_digit())
+    {
+        consider(Pattern::Year, 4);
+    }
+
+    let repeat = chars[start..]
+        .iter()
+        .take_while(|c| **c == chars[start])
+        .count();
+    if repeat >= 3 {
+        consider(Pattern::Repeat, repeat);
+    }
+
+    let sequence = sequence_len(&chars[start..]);
+    if sequence >= 3 {
+        consider(Pattern::Sequence, sequence);
+    }
+
+    let lowercase: String = chars[start..]
+        .iter()
+        .map(char::to_ascii_lowercase)
+        .collect();
+    let walk = KEYBOARD_ROWS
+        .iter()
+        .flat_map(|row| [row.to_string(), row.chars().rev().collect()])
+        .flat_map(|row| {
+            let row: Vec<char> = row.chars().collect();
+            (0..row.len())
+                .map(|offset| common_prefix(&row[offset..], &lowercase))
+                .collect::<Vec<_>>()
+        })
+        .max()
+        .unwrap_or_default();
+    if walk >= 4 {
+        consider(Pattern::Keyboard, walk);
+    }
+
+    found
+}
+
+/// Returns the length of the run of characters with the same step of one
+/// up or down, e.g. `abc` or `321`.
+fn sequence_len(chars: &[char]) -> usize {
+    let Some(first) = chars.first() else {
+        return 0;
+    };
+    let step = |a: char, b: char| b as i64 - a as i64;
+    let direction = match chars.get(1) {
+        Some(second) if step(*first, *second).abs() == 1 => step(*first, *second),
+        _ => return 1,
+    };
+    1 + chars
+        .windows(2)
+        .take_while(|pair| step(pair[0], pair[1]) == direction && pair[1].is_alphanumeric())
+        .count()
+}
+
+fn common_prefix(row: &[char], text: &str) -> usize {
+    row.iter()
+        .zip(text.chars())
+        .take_while(|(a, b)| **a == *b)
+        .count()
+}
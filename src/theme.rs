@@ -2,12 +2,16 @@ use ratatui::style::{palette::tailwind, Color};
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Theme {
+    pub banner_bg: Color,
+    pub banner_fg: Color,
     pub button_keyboard_label: Color,
     pub button_label: Color,
     pub debug: Color,
     pub details_border: Color,
     pub details_field_fg: Color,
     pub details_hint_fg: Color,
+    pub diff_added_fg: Color,
+    pub diff_removed_fg: Color,
     pub menu_bg: Color,
     pub menu_button_background: Color,
     pub menu_button_highlight: Color,
@@ -22,8 +26,10 @@ pub struct Theme {
     pub standard_fg: Color,
     pub status_bar_bg: Color,
     pub status_bar_fg: Color,
+    pub table_aged_marker_fg: Color,
     pub table_alt_row: Color,
     pub table_buffer_bg: Color,
+    pub table_drop_target_bg: Color,
     pub table_header_bg: Color,
     pub table_header_fg: Color,
     pub table_normal_row: Color,
@@ -34,18 +40,23 @@ pub struct Theme {
     pub table_selected_row_style_fg: Color,
     pub table_track_bg: Color,
     pub table_track_fg: Color,
+    pub table_unreadable_row_fg: Color,
 }
 
 impl Theme {
     pub fn new() -> Self {
         let palette = &tailwind::CYAN;
         Self {
+            banner_bg: tailwind::AMBER.c900,
+            banner_fg: tailwind::AMBER.c100,
             button_keyboard_label: tailwind::SLATE.c400,
             button_label: tailwind::SLATE.c300,
             debug: tailwind::BLUE.c500,
             details_border: palette.c950,
             details_field_fg: tailwind::SLATE.c200,
             details_hint_fg: tailwind::SLATE.c400,
+            diff_added_fg: tailwind::GREEN.c400,
+            diff_removed_fg: tailwind::RED.c400,
             menu_bg: palette.c950,
             menu_button_background: palette.c900,
             menu_button_highlight: palette.c800,
@@ -60,8 +71,10 @@ impl Theme {
             standard_fg: tailwind::SLATE.c200,
             status_bar_bg: palette.c950,
             status_bar_fg: tailwind::SLATE.c200,
+            table_aged_marker_fg: tailwind::AMBER.c400,
             table_alt_row: tailwind::SLATE.c900,
             table_buffer_bg: tailwind::SLATE.c900,
+            table_drop_target_bg: palette.c800,
             table_header_bg: tailwind::BLUE.c900,
             table_header_fg: tailwind::SLATE.c200,
             table_normal_row: tailwind::SLATE.c950,
@@ -72,6 +85,7 @@ impl Theme {
             table_selected_row_style_fg: tailwind::BLUE.c400,
             table_track_bg: tailwind::SLATE.c800,
             table_track_fg: tailwind::SLATE.c400,
+            table_unreadable_row_fg: tailwind::SLATE.c500,
         }
     }
 }
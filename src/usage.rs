@@ -0,0 +1,69 @@
+//! Local tracking of when entries were last used, i.e. when a secret of an
+//! entry was copied or shown.
+//!
+//! The times are kept in the data directory only, nothing is written to the
+//! password store.
+
+use std::{
+    collections::HashMap,
+    io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::data;
+
+#[derive(Debug, Default, Clone)]
+pub struct Usage {
+    /// Seconds since the Unix epoch of the last use per entry
+    last_used: HashMap<String, u64>,
+}
+
+impl Usage {
+    /// Reads the usage file, lines have the form `<seconds> <pass_id>`.
+    pub fn load(data_dir: Option<&Path>, file_name: &str) -> Self {
+        let last_used = data::read_lines(data_dir, file_name)
+            .iter()
+            .filter_map(|line| {
+                let (seconds, pass_id) = line.split_once(' ')?;
+                Some((pass_id.to_string(), seconds.parse().ok()?))
+            })
+            .collect();
+        Self { last_used }
+    }
+
+    pub fn save(&self, data_dir: Option<&Path>, file_name: &str) -> io::Result<()> {
+        let mut lines: Vec<String> = self
+            .last_used
+            .iter()
+            .map(|(pass_id, seconds)| format!("{seconds} {pass_id}"))
+            .collect();
+        lines.sort();
+        data::write_lines(data_dir, file_name, &lines)
+    }
+
+    pub fn record(&mut self, pass_id: &str, now: SystemTime) {
+        let seconds = now
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        self.last_used.insert(pass_id.to_string(), seconds);
+    }
+
+    pub fn last_used(&self, pass_id: &str) -> Option<SystemTime> {
+        self.last_used
+            .get(pass_id)
+            .map(|seconds| UNIX_EPOCH + Duration::from_secs(*seconds))
+    }
+
+    /// Keeps the usage of an entry that was renamed or moved.
+    pub fn rename(&mut self, from: &str, to: &str) {
+        if let Some(seconds) = self.last_used.remove(from) {
+            self.last_used.insert(to.to_string(), seconds);
+        }
+    }
+
+    pub fn remove(&mut self, pass_id: &str) {
+        self.last_used.remove(pass_id);
+    }
+}
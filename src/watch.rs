@@ -0,0 +1,60 @@
+//! Watches the password store for entries that are added, removed or
+//! renamed outside of passepartui, e.g. by `pass`, git or a file syncing
+//! tool.
+
+use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    path::{Component, Path},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use crate::event::PasswordEvent;
+
+/// Time without further changes before the store is scanned, e.g. a pull
+/// changes many files in a row
+const SETTLE_TIME: Duration = Duration::from_millis(300);
+
+/// Starts watching the store, `PasswordEvent::StoreChanged` is sent once a
+/// burst of changes settled. Watching stops when the watcher is dropped.
+pub fn watch(
+    store_dir: &Path,
+    event_tx: Sender<PasswordEvent>,
+) -> notify::Result<RecommendedWatcher> {
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(store_dir, RecursiveMode::Recursive)?;
+
+    thread::spawn(move || {
+        while let Ok(event) = receiver.recv() {
+            if !event.is_ok_and(|event| changes_entries(&event)) {
+                continue;
+            }
+            let disconnected = loop {
+                match receiver.recv_timeout(SETTLE_TIME) {
+                    Ok(_) => continue,
+                    Err(RecvTimeoutError::Timeout) => break false,
+                    Err(RecvTimeoutError::Disconnected) => break true,
+                }
+            };
+            if event_tx.send(PasswordEvent::StoreChanged).is_err() || disconnected {
+                break;
+            }
+        }
+    });
+    Ok(watcher)
+}
+
+/// Returns true if files were added, removed or renamed outside of the git
+/// directory, changed contents are picked up when an entry is displayed.
+fn changes_entries(event: &Event) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+    ) && event.paths.iter().any(|path| {
+        !path
+            .components()
+            .any(|component| component == Component::Normal(".git".as_ref()))
+    })
+}